@@ -1,14 +1,77 @@
-use std::marker::PhantomData;
+use std::{cell::Cell, marker::PhantomData};
 
+pub use crate::de::MissingFieldError;
 use crate::{reporter::ErrorReporter, EDeserialize};
 
-#[derive(Debug)]
-pub struct MissingFieldError(&'static str);
+/// Used to unwind the deserializer once the error budget has been exhausted.
+/// It's never surfaced: the reporter discards errors past the budget.
+pub fn abort_deserialization<E: serde::de::Error>() -> E {
+    E::custom("deserialization aborted, too many errors")
+}
+
+/// Used by fields with `#[eserde(message = "..")]`, `#[eserde(invalid = "..")]`,
+/// `#[eserde(missing = "..")]` or `#[eserde(code = "..")]`: customize the errors
+/// reported after the first `n_errors`.
+pub fn override_errors(
+    n_errors: usize,
+    message: Option<&str>,
+    code: Option<&'static str>,
+    field: impl FnOnce() -> &'static str,
+    expected: &str,
+) {
+    // The field name is only looked up if there's a message to render.
+    let field = match message {
+        Some(_) if ErrorReporter::n_errors() > n_errors => field(),
+        _ => "",
+    };
+    ErrorReporter::rewrite_errors_since(n_errors, |error| {
+        if let Some(template) = message {
+            error.details = render_message(template, &error.details, field, expected);
+        }
+        if let Some(code) = code {
+            error.code = Some(code.into());
+        }
+    });
+}
 
-impl std::fmt::Display for MissingFieldError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "missing field `{}`", self.0)
+/// Used by fields with `#[eserde(deprecated)]`: warn that the field is present in the input.
+pub fn report_deprecated(note: Option<&str>) {
+    let warning = match note {
+        Some(note) => format!("this field is deprecated: {note}"),
+        None => "this field is deprecated".to_owned(),
+    };
+    ErrorReporter::report_warning("deprecated_field", warning);
+}
+
+/// Replace the `{message}`, `{field}` and `{expected}` placeholders in `template`.
+///
+/// The template is scanned once, so placeholders in the replacements are left untouched.
+fn render_message(template: &str, message: &str, field: &str, expected: &str) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let replacement = [
+            ("{message}", message),
+            ("{field}", field),
+            ("{expected}", expected),
+        ]
+        .into_iter()
+        .find(|(placeholder, _)| rest.starts_with(placeholder));
+        match replacement {
+            Some((placeholder, value)) => {
+                rendered.push_str(value);
+                rest = &rest[placeholder.len()..];
+            }
+            None => {
+                rendered.push('{');
+                rest = &rest[1..];
+            }
+        }
     }
+    rendered.push_str(rest);
+    rendered
 }
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
@@ -20,11 +83,204 @@ pub enum MaybeInvalidOrMissing<T> {
 }
 
 impl<T> MaybeInvalidOrMissing<T> {
-    pub fn push_error_if_missing(&self, field_name: &'static str) {
+    pub fn push_error_if_missing<'de>(&self, field_name: impl FnOnce() -> &'static str)
+    where
+        T: serde::Deserialize<'de>,
+    {
+        // Like `serde`, we tolerate missing fields if their type
+        // can be deserialized out of nothing (e.g. `Option<T>`).
         if let Self::Missing = self {
-            ErrorReporter::report(MissingFieldError(field_name));
+            let is_map = Cell::new(false);
+            if T::deserialize(MissingFieldDeserializer { is_map: &is_map }).is_err() {
+                #[cfg(feature = "env")]
+                if is_map.get() {
+                    crate::env::record_missing_map();
+                }
+                ErrorReporter::report(MissingFieldError::new(field_name()));
+            }
         }
     }
+
+    /// Used by `#[eserde(with = "..")]` fields: like `serde` does for fields with
+    /// a custom deserializer, a missing value is always an error.
+    pub fn push_error_if_missing_with(&self, field_name: impl FnOnce() -> &'static str) {
+        if let Self::Missing = self {
+            ErrorReporter::report(MissingFieldError::new(field_name()));
+        }
+    }
+}
+
+/// The name `serde` gives to the field at `index` of `T`, or of its variant at
+/// `variant` if `T` is an enum—i.e. the corresponding entry of the `fields` passed to
+/// `Deserializer::deserialize_struct` (or `VariantAccess::struct_variant`).
+///
+/// `T` is the field names type generated by `#[derive(eserde::Deserialize)]`.
+/// `fallback` is returned if `serde` doesn't list the field.
+pub fn field_name<T>(variant: Option<u32>, index: usize, fallback: &'static str) -> &'static str
+where
+    T: serde::de::DeserializeOwned,
+{
+    let fields = Cell::new(None);
+    let _ = T::deserialize(FieldNamesDeserializer {
+        variant,
+        fields: &fields,
+    });
+    fields
+        .get()
+        .and_then(|fields: &[&str]| fields.get(index).copied())
+        .unwrap_or(fallback)
+}
+
+/// A deserializer that fails right away, after recording the field names `serde`
+/// asks for.
+struct FieldNamesDeserializer<'a> {
+    /// The index of the variant to pick, for enums.
+    variant: Option<u32>,
+    fields: &'a Cell<Option<&'static [&'static str]>>,
+}
+
+impl<'de> serde::Deserializer<'de> for FieldNamesDeserializer<'_> {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        Err(serde::de::Error::custom("expected a struct or an enum"))
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.fields.set(Some(fields));
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_enum(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map identifier ignored_any
+    }
+}
+
+impl<'de> serde::de::EnumAccess<'de> for FieldNamesDeserializer<'_> {
+    type Error = serde::de::value::Error;
+    type Variant = Self;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error>
+    where
+        V: serde::de::DeserializeSeed<'de>,
+    {
+        let Some(variant) = self.variant else {
+            return Err(serde::de::Error::custom("expected a struct"));
+        };
+        let value = seed
+            .deserialize(serde::de::IntoDeserializer::<Self::Error>::into_deserializer(variant))?;
+        Ok((value, self))
+    }
+}
+
+impl<'de> serde::de::VariantAccess<'de> for FieldNamesDeserializer<'_> {
+    type Error = serde::de::value::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Err(serde::de::Error::custom("expected a struct variant"))
+    }
+
+    fn newtype_variant_seed<T>(self, _seed: T) -> Result<T::Value, Self::Error>
+    where
+        T: serde::de::DeserializeSeed<'de>,
+    {
+        Err(serde::de::Error::custom("expected a struct variant"))
+    }
+
+    fn tuple_variant<V>(self, _len: usize, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        Err(serde::de::Error::custom("expected a struct variant"))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.fields.set(Some(fields));
+        Err(serde::de::Error::custom("expected a struct"))
+    }
+}
+
+/// The deserializer `serde` uses for missing fields: it yields `None`
+/// for optional types and fails for everything else.
+struct MissingFieldDeserializer<'a> {
+    /// Set if the type asked for a map or a struct.
+    is_map: &'a Cell<bool>,
+}
+
+impl<'de> serde::Deserializer<'de> for MissingFieldDeserializer<'_> {
+    type Error = serde::de::value::Error;
+
+    fn deserialize_any<V>(self, _visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        Err(serde::de::Error::custom("missing field"))
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        visitor.visit_none()
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.is_map.set(true);
+        self.deserialize_any(visitor)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error>
+    where
+        V: serde::de::Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct newtype_struct seq tuple
+        tuple_struct enum identifier ignored_any
+    }
 }
 
 /// Used by `#[eserde(compat)]` fields (NO `#[serde(default)]`).
@@ -39,7 +295,10 @@ where
         let v = match T::deserialize(deserializer) {
             Ok(_) => Self::Valid(Default::default()),
             Err(error) => {
-                ErrorReporter::report(error);
+                ErrorReporter::report(&error);
+                if ErrorReporter::should_abort() {
+                    return Err(error);
+                }
                 Self::Invalid
             }
         };
@@ -57,6 +316,9 @@ where
 {
     let v = match T::deserialize_for_errors(deserializer) {
         Ok(_) => MaybeInvalidOrMissing::Valid(Default::default()),
+        Err(_) if ErrorReporter::should_abort() => {
+            return Err(abort_deserialization());
+        }
         Err(_) => MaybeInvalidOrMissing::Invalid,
     };
     Ok(v)
@@ -77,7 +339,10 @@ impl<T> MaybeInvalid<T> {
     /// Added for simplicity in order to avoid having to distinguish in the macro
     /// between `MaybeInvalid` and `MaybeInvalidOrMissing`.
     /// To be removed in the future.
-    pub fn push_error_if_missing(&self, _field_name: &'static str) {}
+    pub fn push_error_if_missing(&self, _field_name: impl FnOnce() -> &'static str) {}
+
+    /// See [`MaybeInvalid::push_error_if_missing`].
+    pub fn push_error_if_missing_with(&self, _field_name: impl FnOnce() -> &'static str) {}
 }
 
 /// Used by `#[eserde(compat)]` `#[serde(default)]` fields.
@@ -92,7 +357,10 @@ where
         let v = match T::deserialize(deserializer) {
             Ok(_) => Self::Valid(Default::default()),
             Err(error) => {
-                ErrorReporter::report(error);
+                ErrorReporter::report(&error);
+                if ErrorReporter::should_abort() {
+                    return Err(error);
+                }
                 Self::Invalid
             }
         };
@@ -108,6 +376,9 @@ where
 {
     let v = match T::deserialize_for_errors(deserializer) {
         Ok(_) => MaybeInvalid::Valid(Default::default()),
+        Err(_) if ErrorReporter::should_abort() => {
+            return Err(abort_deserialization());
+        }
         Err(_) => MaybeInvalid::Invalid,
     };
     Ok(v)
@@ -0,0 +1,722 @@
+//! Merge layered configuration sources into a single typed value.
+//!
+//! Applications often load their configuration from several sources—e.g. a file with
+//! defaults, an environment-specific file, environment variables and command-line overrides.
+//! [`ConfigBuilder`] merges all of them into a single view, then deserializes the target type
+//! from it, reporting all errors at once.\
+//! Each error is tagged with the source that supplied the offending value,
+//! available via [`DeserializationError::origin`].
+//!
+//! # Example
+//!
+//! ```rust
+//! use eserde::config::ConfigBuilder;
+//! use eserde::env::Options;
+//!
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Config {
+//!     port: u16,
+//!     db: Db,
+//! }
+//!
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Db {
+//!     url: String,
+//!     pool_size: u32,
+//! }
+//!
+//! # fn main() {
+//! let defaults = r#"
+//!     port = 8080
+//!
+//!     [db]
+//!     url = "postgres://localhost/app"
+//!     pool_size = 10
+//! "#;
+//! let production = r#"{ "db": { "pool_size": "many" } }"#;
+//!
+//! let errors = ConfigBuilder::new()
+//!     .add_toml("defaults.toml", defaults)
+//!     .add_json("production.json", production)
+//!     .add_env_vars(
+//!         &Options::new().prefix("APP_").separator("__"),
+//!         [("APP_PORT", "http")],
+//!     )
+//!     .deserialize::<Config>()
+//!     .unwrap_err();
+//! assert_eq!(
+//!     errors.to_string(),
+//!     r#"Something went wrong during deserialization:
+//! - db.pool_size: invalid value: string "many", expected u32 (from production.json)
+//! - port: invalid value: string "http", expected u16 (from env)
+//! "#
+//! );
+//! # }
+//! ```
+//!
+//! # Merging
+//!
+//! Sources are merged in the order they were added: later sources take precedence over earlier ones.\
+//! Maps (e.g. TOML tables, JSON objects) are merged key by key, recursively.
+//! Any other value (e.g. a sequence or a string) replaces the previous one, if any.
+//!
+//! Values from environment variables and [overrides](ConfigBuilder::set_override) are always strings.
+//! Strings are parsed into numbers and booleans when the target type requires it, and into
+//! sequences by splitting on commas.
+//!
+//! # Implementation
+//!
+//! The merged view is deserialized following the same two-pass approach used by all other
+//! deserializers in `eserde`.
+//! Start by using `serde::Deserialize` to try to deserialize the target type.
+//! If it succeeds, return `Ok(value)`.
+//! If it fails, use `eserde::EDeserialize` to visit the merged view again and
+//! accumulate as many deserialization errors as possible.
+//! The errors are then returned as a vector in the `Err` variant.
+use std::collections::{btree_map::Entry, BTreeMap};
+use std::fmt;
+
+use serde::de::{
+    self,
+    value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer},
+    DeserializeSeed, IntoDeserializer, Unexpected, Visitor,
+};
+
+use crate::{
+    env::{self, Error},
+    path::{self, Path, Segment},
+    reporter::ErrorReporter,
+    DeserializationError, DeserializationErrors, DeserializationWarning, EDeserialize,
+};
+
+/// Merge configuration sources and deserialize the result.
+///
+/// Check out the [module documentation](self) for an example.
+#[derive(Debug)]
+pub struct ConfigBuilder {
+    /// The name of each source, indexed by the order in which they were added.
+    origins: Vec<String>,
+    /// The merged view over all sources.
+    root: Node,
+    /// Sources that couldn't be parsed, with the corresponding error message.
+    failed: Vec<(usize, String)>,
+}
+
+impl Default for ConfigBuilder {
+    fn default() -> Self {
+        Self {
+            origins: Vec::new(),
+            root: Node {
+                value: Value::Map(BTreeMap::new()),
+                origin: None,
+            },
+            failed: Vec::new(),
+        }
+    }
+}
+
+impl ConfigBuilder {
+    /// Start with no sources.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a source in any self-describing format supported by `serde`.
+    ///
+    /// `origin` is used to tag errors caused by values from this source—e.g. a file path.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use eserde::config::ConfigBuilder;
+    /// #[derive(eserde::Deserialize, Debug)]
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut de = serde_json::Deserializer::from_str(r#"{ "port": 8080 }"#);
+    /// let config: Config = ConfigBuilder::new()
+    ///     .add_source("config.json", &mut de)
+    ///     .deserialize()
+    ///     .unwrap();
+    /// # }
+    /// ```
+    pub fn add_source<'de, D>(mut self, origin: impl Into<String>, deserializer: D) -> Self
+    where
+        D: de::Deserializer<'de>,
+    {
+        let origin = self.push_origin(origin.into());
+        match (NodeSeed { origin }).deserialize(deserializer) {
+            Ok(node) => self.root.merge(node),
+            Err(e) => self.failed.push((origin, e.to_string())),
+        }
+        self
+    }
+
+    /// Add a source of TOML text.
+    ///
+    /// `origin` is used to tag errors caused by values from this source—e.g. a file path.
+    #[cfg(feature = "toml")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
+    pub fn add_toml(self, origin: impl Into<String>, s: &str) -> Self {
+        self.add_source(origin, toml::Deserializer::new(s))
+    }
+
+    /// Add a source of JSON text.
+    ///
+    /// `origin` is used to tag errors caused by values from this source—e.g. a file path.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn add_json(mut self, origin: impl Into<String>, s: &str) -> Self {
+        let origin = self.push_origin(origin.into());
+        let mut de = serde_json::Deserializer::from_str(s);
+        match (NodeSeed { origin })
+            .deserialize(&mut de)
+            .and_then(|node| de.end().map(|_| node))
+        {
+            Ok(node) => self.root.merge(node),
+            Err(e) => self.failed.push((origin, e.to_string())),
+        }
+        self
+    }
+
+    /// Add the environment variables of the current process as a source.
+    ///
+    /// Errors caused by values from this source are tagged with `"env"`.
+    /// Check out [`eserde::env`](crate::env) for more details on how variable names are
+    /// mapped to keys.
+    pub fn add_env(self, options: &env::Options) -> Self {
+        self.add_env_vars(
+            options,
+            std::env::vars_os().filter_map(|(name, value)| {
+                Some((name.into_string().ok()?, value.into_string().ok()?))
+            }),
+        )
+    }
+
+    /// Add an iterator of `(name, value)` pairs as a source, in lieu of the
+    /// environment variables of the current process.
+    ///
+    /// It behaves like [`ConfigBuilder::add_env`]. It's primarily useful for testing.
+    pub fn add_env_vars<I, K, V>(mut self, options: &env::Options, vars: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let origin = self.push_origin("env".into());
+        let node = Node::from_env(options.tree(vars), origin);
+        self.root.merge(node);
+        self
+    }
+
+    /// Override a single value, e.g. from a command-line argument.
+    ///
+    /// `key` is a dot-separated path to the value—e.g. `db.port`.\
+    /// Errors caused by values from this source are tagged with `"override"`.
+    pub fn set_override(mut self, key: &str, value: impl Into<String>) -> Self {
+        let origin = self.push_origin("override".into());
+        let node = key.rsplit('.').fold(
+            Node {
+                value: Value::String(value.into()),
+                origin: Some(origin),
+            },
+            |node, key| Node {
+                value: Value::Map(BTreeMap::from([(key.to_owned(), node)])),
+                origin: Some(origin),
+            },
+        );
+        self.root.merge(node);
+        self
+    }
+
+    /// Deserialize an instance of type `T` from the merged view over all sources.
+    ///
+    /// If any of the sources couldn't be parsed, the corresponding errors are returned
+    /// without attempting to deserialize `T`.
+    pub fn deserialize<T>(&self) -> Result<T, DeserializationErrors>
+    where
+        T: for<'a> EDeserialize<'a>,
+    {
+        if !self.failed.is_empty() {
+            let errors = self
+                .failed
+                .iter()
+                .map(|(origin, details)| DeserializationError {
+                    origin: Some(self.origins[*origin].clone()),
+                    ..DeserializationError::new(None, details.clone())
+                })
+                .collect::<Vec<_>>();
+            return Err(DeserializationErrors::from(errors));
+        }
+
+        let error = match T::deserialize(NodeDeserializer::new(&self.root)) {
+            Ok(v) => {
+                return Ok(v);
+            }
+            Err(e) => e,
+        };
+        let _guard = ErrorReporter::start_deserialization();
+
+        let de = path::Deserializer::new(NodeDeserializer::new(&self.root));
+
+        let errors = match T::deserialize_for_errors(de) {
+            Ok(_) => vec![],
+            Err(_) => ErrorReporter::take_errors(),
+        };
+        let errors = if errors.is_empty() {
+            vec![DeserializationError::formatted(None, error.to_string())]
+        } else {
+            errors
+                .into_iter()
+                .map(|mut e| {
+                    e.origin = self.origin_of(e.path.as_ref());
+                    e
+                })
+                .collect()
+        };
+
+        Err(DeserializationErrors::from(errors))
+    }
+
+    /// Deserialize an instance of type `T` from the merged view over all sources, collecting the
+    /// [warnings](DeserializationWarning) reported along the way.
+    ///
+    /// It behaves like [`ConfigBuilder::deserialize`], but on success it visits the merged view
+    /// a second time to collect warnings—e.g. keys that don't match any field and were ignored.
+    /// Each warning is tagged with the source that supplied the offending value.
+    ///
+    /// Use it to detect configuration drift without rejecting unknown keys via
+    /// `#[serde(deny_unknown_fields)]`—e.g. fail a CI check on unknown keys, while
+    /// tolerating them in production.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eserde::config::ConfigBuilder;
+    ///
+    /// #[derive(eserde::Deserialize, Debug)]
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// let (config, warnings) = ConfigBuilder::new()
+    ///     .add_toml("defaults.toml", "port = 8080")
+    ///     .add_json("production.json", r#"{ "prot": 80 }"#)
+    ///     .deserialize_with_warnings::<Config>()
+    ///     .unwrap();
+    /// assert_eq!(config.port, 8080);
+    /// let warnings: Vec<_> = warnings.iter().map(|w| w.to_string()).collect();
+    /// assert_eq!(
+    ///     warnings,
+    ///     ["prot: unknown field `prot` was ignored (from production.json)"]
+    /// );
+    /// ```
+    pub fn deserialize_with_warnings<T>(
+        &self,
+    ) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+    where
+        T: for<'a> EDeserialize<'a>,
+    {
+        let value = self.deserialize()?;
+        let _guard = ErrorReporter::start_deserialization();
+
+        let de = path::Deserializer::new(NodeDeserializer::new(&self.root));
+        // The merged view is known to be valid: we're only interested in warnings.
+        let _ = T::deserialize_for_errors(de);
+
+        let warnings = ErrorReporter::take_warnings()
+            .into_iter()
+            .map(|mut w| {
+                w.origin = self.origin_of(w.path.as_ref());
+                w
+            })
+            .collect();
+        Ok((value, warnings))
+    }
+
+    /// The name of the source that supplied the value at `path`, if it comes from a single source.
+    fn origin_of(&self, path: Option<&Path>) -> Option<String> {
+        path.and_then(|path| self.root.find(path))
+            .and_then(|node| node.origin)
+            .map(|origin| self.origins[origin].clone())
+    }
+
+    fn push_origin(&mut self, origin: String) -> usize {
+        self.origins.push(origin);
+        self.origins.len() - 1
+    }
+}
+
+/// A value in the merged view, tagged with the source it comes from.
+#[derive(Debug)]
+struct Node {
+    value: Value,
+    /// The index of the source that supplied this value.
+    ///
+    /// It's `None` for maps that were merged from multiple sources.
+    origin: Option<usize>,
+}
+
+#[derive(Debug)]
+enum Value {
+    Null,
+    Bool(bool),
+    I64(i64),
+    U64(u64),
+    F64(f64),
+    String(String),
+    Seq(Vec<Node>),
+    Map(BTreeMap<String, Node>),
+}
+
+impl Node {
+    fn from_env(node: env::Node, origin: usize) -> Self {
+        let value = if node.children.is_empty() {
+            Value::String(node.value.unwrap_or_default())
+        } else {
+            Value::Map(
+                node.children
+                    .into_iter()
+                    .map(|(key, node)| (key, Node::from_env(node, origin)))
+                    .collect(),
+            )
+        };
+        Node {
+            value,
+            origin: Some(origin),
+        }
+    }
+
+    /// Merge `other` on top of `self`.
+    fn merge(&mut self, other: Node) {
+        match (&mut self.value, other.value) {
+            (Value::Map(base), Value::Map(overlay)) => {
+                if self.origin != other.origin {
+                    self.origin = None;
+                }
+                for (key, node) in overlay {
+                    match base.entry(key) {
+                        Entry::Occupied(mut entry) => entry.get_mut().merge(node),
+                        Entry::Vacant(entry) => {
+                            entry.insert(node);
+                        }
+                    }
+                }
+            }
+            (value, other_value) => {
+                *value = other_value;
+                self.origin = other.origin;
+            }
+        }
+    }
+
+    /// Find the node that the given path points at, if any.
+    fn find(&self, path: &Path) -> Option<&Node> {
+        path.iter()
+            .try_fold(self, |node, segment| match (segment, &node.value) {
+                (Segment::Map { key }, Value::Map(map)) => map.get(key),
+                (Segment::Enum { variant }, Value::Map(map)) => map.get(variant),
+                (Segment::Enum { .. }, _) => Some(node),
+                (Segment::Seq { index }, Value::Seq(seq)) => seq.get(*index),
+                // A comma-separated string.
+                (Segment::Seq { .. }, Value::String(_)) => Some(node),
+                _ => None,
+            })
+    }
+}
+
+/// Build a [`Node`] out of a self-describing format, tagging each value with the given source.
+#[derive(Clone, Copy)]
+struct NodeSeed {
+    origin: usize,
+}
+
+impl NodeSeed {
+    fn node(self, value: Value) -> Node {
+        Node {
+            value,
+            origin: Some(self.origin),
+        }
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for NodeSeed {
+    type Value = Node;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Node, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for NodeSeed {
+    type Value = Node;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("any value")
+    }
+
+    fn visit_bool<E>(self, v: bool) -> Result<Node, E> {
+        Ok(self.node(Value::Bool(v)))
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Node, E> {
+        Ok(self.node(Value::I64(v)))
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Node, E> {
+        Ok(self.node(Value::U64(v)))
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Node, E> {
+        Ok(self.node(Value::F64(v)))
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Node, E> {
+        Ok(self.node(Value::String(v.to_owned())))
+    }
+
+    fn visit_string<E>(self, v: String) -> Result<Node, E> {
+        Ok(self.node(Value::String(v)))
+    }
+
+    fn visit_bytes<E>(self, v: &[u8]) -> Result<Node, E> {
+        let bytes = v.iter().map(|b| self.node(Value::U64(*b as u64))).collect();
+        Ok(self.node(Value::Seq(bytes)))
+    }
+
+    fn visit_unit<E>(self) -> Result<Node, E> {
+        Ok(self.node(Value::Null))
+    }
+
+    fn visit_none<E>(self) -> Result<Node, E> {
+        Ok(self.node(Value::Null))
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Node, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Node, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Node, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        let mut elements = Vec::new();
+        while let Some(element) = seq.next_element_seed(self)? {
+            elements.push(element);
+        }
+        Ok(self.node(Value::Seq(elements)))
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Node, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        let mut entries = BTreeMap::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let value = map.next_value_seed(self)?;
+            entries.insert(key, value);
+        }
+        Ok(self.node(Value::Map(entries)))
+    }
+}
+
+/// Deserialize the merged view.
+struct NodeDeserializer<'a> {
+    node: &'a Node,
+}
+
+impl<'a> NodeDeserializer<'a> {
+    fn new(node: &'a Node) -> Self {
+        Self { node }
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for NodeDeserializer<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                match &self.node.value {
+                    Value::String(s) => match s.parse() {
+                        Ok(v) => visitor.$visit(v),
+                        Err(_) => Err(de::Error::invalid_value(Unexpected::Str(s), &visitor)),
+                    },
+                    _ => self.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match &self.node.value {
+            Value::Null => visitor.visit_unit(),
+            Value::Bool(v) => visitor.visit_bool(*v),
+            Value::I64(v) => visitor.visit_i64(*v),
+            Value::U64(v) => visitor.visit_u64(*v),
+            Value::F64(v) => visitor.visit_f64(*v),
+            Value::String(v) => visitor.visit_str(v),
+            Value::Seq(elements) => {
+                let mut seq = SeqDeserializer::new(elements.iter().map(NodeDeserializer::new));
+                let v = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(v)
+            }
+            Value::Map(entries) => {
+                let mut map = MapDeserializer::new(
+                    entries
+                        .iter()
+                        .map(|(key, node)| (key.as_str(), NodeDeserializer::new(node))),
+                );
+                let v = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(v)
+            }
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match &self.node.value {
+            Value::Null => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let Value::String(value) = &self.node.value else {
+            return self.deserialize_any(visitor);
+        };
+        let elements: Vec<Node> = if value.is_empty() {
+            vec![]
+        } else {
+            value
+                .split(',')
+                .map(|element| Node {
+                    value: Value::String(element.to_owned()),
+                    origin: self.node.origin,
+                })
+                .collect()
+        };
+        let mut seq = SeqDeserializer::new(elements.iter().map(NodeDeserializer::new));
+        let v = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(v)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match &self.node.value {
+            Value::String(variant) => visitor.visit_enum(variant.as_str().into_deserializer()),
+            Value::Map(entries) if entries.len() == 1 => {
+                let map = MapDeserializer::new(
+                    entries
+                        .iter()
+                        .map(|(key, node)| (key.as_str(), NodeDeserializer::new(node))),
+                );
+                visitor.visit_enum(MapAccessDeserializer::new(map))
+            }
+            _ => self.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string bytes byte_buf unit unit_struct map struct identifier
+    }
+}
@@ -0,0 +1,574 @@
+//! Deserialize CSV data, one record per row.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Customer {
+//!     name: String,
+//!     age: u8,
+//!     email: String,
+//! }
+//!
+//! # fn main() {
+//! // Some CSV input data as a &str. Maybe this comes from an upload.
+//! let data = "\
+//! name,age,email
+//! Jane,34,jane@example.com
+//! John,abc,john@example.com
+//! ";
+//!
+//! match eserde::csv::from_str::<Customer>(data) {
+//!     Ok(customers) => {
+//!         println!("Imported {} customers", customers.len());
+//!     }
+//!     Err(errors) => {
+//!         for error in errors.iter() {
+//!             // E.g. highlight the offending cell in a spreadsheet view.
+//!             println!(
+//!                 "Row {}, column {:?}, cell {:?}: {}",
+//!                 error.row(),
+//!                 error.column(),
+//!                 error.cell(),
+//!                 error.message()
+//!             );
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! # Headers
+//!
+//! By default, the first row is treated as a header row: each record is deserialized as a map
+//! from column names to cells, thus the field names of `T` must match the column names.\
+//! Build a [`::csv::Reader`] with headers disabled and pass it to [`from_reader`] to
+//! deserialize each record as a sequence of cells instead (e.g. into a tuple struct).
+//!
+//! # Cells
+//!
+//! Cells are deserialized from their raw text.
+//! Numbers and booleans are parsed when the target type requires it.
+//! An empty cell is deserialized as `None` when the target type is an `Option`.
+//!
+//! # Warnings
+//!
+//! Unlike other formats, there's no `from_str_with_warnings` variant.
+//! Unknown columns are known upfront from the header row, rather than per record:
+//! compare [`::csv::Reader::headers`] against the fields of `T` to detect them.
+//!
+//! # Implementation
+//!
+//! This module relies on [`csv`](https://crates.io/crates/csv) to split the input into records.
+//!
+//! Each record follows the same two-pass approach used by all other deserializers in `eserde`.
+//! Start by using `serde::Deserialize` to try to deserialize the target type.
+//! If it succeeds, move on to the next record.
+//! If it fails, use `eserde::EDeserialize` to visit the record again and
+//! accumulate as many deserialization errors as possible.
+//! The errors from all records are then returned in the `Err` variant.
+use std::fmt;
+
+use serde::de::{
+    self,
+    value::{MapDeserializer, SeqDeserializer},
+    IntoDeserializer, Unexpected, Visitor,
+};
+
+use crate::{
+    path::{self, Segment},
+    reporter::ErrorReporter,
+    DeserializationError, EDeserialize,
+};
+
+/// Deserialize a `Vec<T>` from a string of CSV text, with a header row.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Customer {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// # fn main() {
+/// let data = "name,age\nJane,34\nJohn,27\n";
+///
+/// let customers: Vec<Customer> = eserde::csv::from_str(data).unwrap();
+/// println!("{:#?}", customers);
+/// # }
+/// ```
+pub fn from_str<T>(s: &str) -> Result<Vec<T>, CsvErrors>
+where
+    T: for<'a> EDeserialize<'a>,
+{
+    from_reader(::csv::Reader::from_reader(s.as_bytes()))
+}
+
+/// Deserialize a `Vec<T>` from a CSV reader.
+///
+/// Use [`::csv::ReaderBuilder`] to customize how the input is parsed—e.g. a different
+/// delimiter, or no header row.
+///
+/// Only one record at a time needs to be buffered in memory.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Customer(String, u8);
+///
+/// # fn main() {
+/// let data = "Jane;34\nJohn;27\n";
+/// let reader = csv::ReaderBuilder::new()
+///     .delimiter(b';')
+///     .has_headers(false)
+///     .from_reader(data.as_bytes());
+///
+/// let customers: Vec<Customer> = eserde::csv::from_reader(reader).unwrap();
+/// println!("{:#?}", customers);
+/// # }
+/// ```
+pub fn from_reader<T, R>(mut reader: ::csv::Reader<R>) -> Result<Vec<T>, CsvErrors>
+where
+    T: for<'a> EDeserialize<'a>,
+    R: std::io::Read,
+{
+    let mut values = Vec::new();
+    let mut errors = Vec::new();
+
+    let (headers, first_row) = if reader.has_headers() {
+        match reader.headers() {
+            Ok(headers) => (Some(headers.clone()), 2),
+            Err(e) => {
+                return Err(CsvErrors(vec![CellError::record(1, e.to_string())]));
+            }
+        }
+    } else {
+        (None, 1)
+    };
+
+    let mut record = ::csv::StringRecord::new();
+    for row in first_row.. {
+        match reader.read_record(&mut record) {
+            Ok(true) => {}
+            Ok(false) => break,
+            Err(e) => {
+                errors.push(CellError::record(row, e.to_string()));
+                continue;
+            }
+        }
+        match from_record(headers.as_ref(), &record) {
+            Ok(value) => {
+                if errors.is_empty() {
+                    values.push(value);
+                }
+            }
+            Err(e) => {
+                errors.extend(
+                    e.into_iter()
+                        .map(|e| CellError::new(row, headers.as_ref(), &record, e)),
+                );
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(values)
+    } else {
+        Err(CsvErrors(errors))
+    }
+}
+
+fn from_record<T>(
+    headers: Option<&::csv::StringRecord>,
+    record: &::csv::StringRecord,
+) -> Result<T, Vec<DeserializationError>>
+where
+    T: for<'a> EDeserialize<'a>,
+{
+    let error = match T::deserialize(RecordDeserializer { headers, record }) {
+        Ok(v) => {
+            return Ok(v);
+        }
+        Err(e) => e,
+    };
+    let _guard = ErrorReporter::start_deserialization();
+
+    let de = path::Deserializer::new(RecordDeserializer { headers, record });
+
+    let errors = match T::deserialize_for_errors(de) {
+        Ok(_) => vec![],
+        Err(_) => ErrorReporter::take_errors(),
+    };
+    if errors.is_empty() {
+        Err(vec![DeserializationError::formatted(
+            None,
+            error.to_string(),
+        )])
+    } else {
+        Err(errors)
+    }
+}
+
+/// A collection of errors encountered while trying to deserialize CSV records.
+///
+/// Use [`.iter()`](Self::iter) to iterate over the underlying [`CellError`]s.
+#[derive(Debug)]
+pub struct CsvErrors(Vec<CellError>);
+
+impl CsvErrors {
+    /// Iterate over references to the underlying [`CellError`]s, in row order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &CellError> {
+        self.0.iter()
+    }
+
+    /// The number of errors in the collection.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the collection contains no errors.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl IntoIterator for CsvErrors {
+    type Item = CellError;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl fmt::Display for CsvErrors {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Something went wrong during deserialization:")?;
+        for error in self.iter() {
+            writeln!(f, "- {error}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CsvErrors {}
+
+/// An error that occurred while deserializing a CSV record.
+#[derive(Debug)]
+pub struct CellError {
+    row: usize,
+    column: Option<Column>,
+    error: DeserializationError,
+}
+
+#[derive(Debug)]
+struct Column {
+    index: usize,
+    name: Option<String>,
+    cell: String,
+}
+
+impl CellError {
+    fn new(
+        row: usize,
+        headers: Option<&::csv::StringRecord>,
+        record: &::csv::StringRecord,
+        error: DeserializationError,
+    ) -> Self {
+        let index = match (error.path.as_ref().and_then(|p| p.iter().next()), headers) {
+            (Some(Segment::Map { key }), Some(headers)) => headers.iter().position(|h| h == key),
+            (Some(Segment::Seq { index }), None) => Some(*index),
+            _ => None,
+        };
+        let column = index.and_then(|index| {
+            Some(Column {
+                index,
+                name: headers.and_then(|h| h.get(index)).map(ToOwned::to_owned),
+                cell: record.get(index)?.to_owned(),
+            })
+        });
+        Self { row, column, error }
+    }
+
+    /// An error that affects the whole record—e.g. it couldn't be parsed.
+    fn record(row: usize, details: String) -> Self {
+        Self {
+            row,
+            column: None,
+            error: DeserializationError::new(None, details),
+        }
+    }
+
+    /// The 1-based number of the offending row, as shown by spreadsheet applications.
+    ///
+    /// The header row, if any, is row 1.
+    /// If a record spans multiple lines (e.g. a quoted cell contains a newline),
+    /// it still counts as a single row.
+    pub fn row(&self) -> usize {
+        self.row
+    }
+
+    /// The name of the offending column, as found in the header row.
+    ///
+    /// It's `None` if the input has no header row, or if the error
+    /// isn't about a specific cell (e.g. a missing column).
+    pub fn column(&self) -> Option<&str> {
+        self.column.as_ref()?.name.as_deref()
+    }
+
+    /// The 0-based index of the offending column.
+    ///
+    /// It's `None` if the error isn't about a specific cell (e.g. a missing column).
+    pub fn column_index(&self) -> Option<usize> {
+        self.column.as_ref().map(|c| c.index)
+    }
+
+    /// The raw text of the offending cell.
+    ///
+    /// It's `None` if the error isn't about a specific cell (e.g. a missing column).
+    pub fn cell(&self) -> Option<&str> {
+        self.column.as_ref().map(|c| c.cell.as_str())
+    }
+
+    /// An explanation of what went wrong.
+    pub fn message(&self) -> &str {
+        self.error.message()
+    }
+
+    /// The underlying error, with its path relative to the record.
+    pub fn error(&self) -> &DeserializationError {
+        &self.error
+    }
+}
+
+impl fmt::Display for CellError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "row {}", self.row)?;
+        if let Some(column) = &self.column {
+            match &column.name {
+                Some(name) => write!(f, ", column `{name}`")?,
+                None => write!(f, ", column {}", column.index + 1)?,
+            }
+        }
+        write!(f, ": {}", self.error.message())
+    }
+}
+
+impl std::error::Error for CellError {}
+
+#[derive(Debug)]
+struct Error(String);
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Deserialize a record as a map from column names to cells, or
+/// as a sequence of cells if there is no header row.
+struct RecordDeserializer<'a> {
+    headers: Option<&'a ::csv::StringRecord>,
+    record: &'a ::csv::StringRecord,
+}
+
+impl<'de> de::Deserializer<'de> for RecordDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        match self.headers {
+            Some(headers) => {
+                let mut map = MapDeserializer::new(
+                    headers.iter().zip(self.record.iter().map(CellDeserializer)),
+                );
+                let v = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(v)
+            }
+            None => self.deserialize_seq(visitor),
+        }
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let mut seq = SeqDeserializer::new(self.record.iter().map(CellDeserializer));
+        let v = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(v)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct map struct enum identifier ignored_any
+    }
+}
+
+/// Deserialize a single cell from its raw text.
+struct CellDeserializer<'a>(&'a str);
+
+impl<'de> IntoDeserializer<'de, Error> for CellDeserializer<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                match self.0.trim().parse() {
+                    Ok(v) => visitor.$visit(v),
+                    Err(_) => Err(de::Error::invalid_value(Unexpected::Str(self.0), &visitor)),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for CellDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_str(self.0)
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.is_empty() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.0.is_empty() {
+            visitor.visit_unit()
+        } else {
+            self.deserialize_any(visitor)
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_enum(self.0.into_deserializer())
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_bytes(self.0.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    serde::forward_to_deserialize_any! {
+        char str string unit_struct seq tuple tuple_struct map struct identifier
+    }
+}
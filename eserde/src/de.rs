@@ -0,0 +1,303 @@
+//! Building blocks for hand-written [`EDeserialize`] implementations.
+//!
+//! # Audience
+//!
+//! This module is meant for library authors who maintain types with a hand-written
+//! `serde::Deserialize` implementation and want to ship a first-class [`EDeserialize`]
+//! implementation alongside it—i.e. one that reports _all_ errors, rather than the
+//! single error you get from [`impl_edeserialize_compat!`](crate::impl_edeserialize_compat).
+//!
+//! If you can derive `eserde::Deserialize` for your type, do that instead.
+//!
+//! # Structs
+//!
+//! Implement [`EDeserializeStruct`] for your type, then delegate to
+//! [`deserialize_struct_for_errors`] in your [`EDeserialize`] implementation.
+//! `eserde` takes care of the rest: it keeps going after an invalid field,
+//! attributes each error to the right field and reports missing, duplicate
+//! and (optionally) unknown fields.
+//!
+//! ```rust
+//! use eserde::{
+//!     de::{deserialize_struct_for_errors, EDeserializeStruct},
+//!     EDeserialize,
+//! };
+//! use serde::{Deserialize, Deserializer};
+//!
+//! #[derive(Debug)]
+//! pub struct Endpoint {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! // The existing, hand-written `serde` implementation.
+//! impl<'de> Deserialize<'de> for Endpoint {
+//!     fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+//!         #[derive(Deserialize)]
+//!         struct Raw {
+//!             host: String,
+//!             port: Option<u16>,
+//!         }
+//!         let raw = Raw::deserialize(deserializer)?;
+//!         Ok(Endpoint {
+//!             host: raw.host,
+//!             port: raw.port.unwrap_or(443),
+//!         })
+//!     }
+//! }
+//!
+//! impl<'de> EDeserialize<'de> for Endpoint {
+//!     fn deserialize_for_errors<D>(deserializer: D) -> Result<(), ()>
+//!     where
+//!         D: Deserializer<'de>,
+//!     {
+//!         deserialize_struct_for_errors::<Self, D>(deserializer)
+//!     }
+//! }
+//!
+//! impl<'de> EDeserializeStruct<'de> for Endpoint {
+//!     const NAME: &'static str = "Endpoint";
+//!     const FIELDS: &'static [&'static str] = &["host", "port"];
+//!
+//!     fn is_required(field: &'static str) -> bool {
+//!         field != "port"
+//!     }
+//!
+//!     fn field_for_errors<D>(field: &'static str, deserializer: D) -> Result<(), ()>
+//!     where
+//!         D: Deserializer<'de>,
+//!     {
+//!         match field {
+//!             "host" => String::deserialize_for_errors(deserializer),
+//!             "port" => Option::<u16>::deserialize_for_errors(deserializer),
+//!             _ => unreachable!("Only called for the entries of `FIELDS`"),
+//!         }
+//!     }
+//! }
+//!
+//! let errors = eserde::json::from_str::<Vec<Endpoint>>(
+//!     r#"[{"host": 1, "port": -1}, {"port": 80}]"#,
+//! )
+//! .unwrap_err();
+//! assert_eq!(
+//!     errors.to_string(),
+//!     "Something went wrong during deserialization:\n\
+//!     - [0].host: invalid type: integer `1`, expected a string at line 1 column 11\n\
+//!     - [0].port: invalid value: integer `-1`, expected u16 at line 1 column 23\n\
+//!     - [1]: missing field `host`\n"
+//! );
+//! ```
+//!
+//! # Maps and sequences
+//!
+//! Maps and sequences of [`EDeserialize`] types (e.g. `HashMap<String, Endpoint>` or `Vec<Endpoint>`)
+//! already implement [`EDeserialize`]: no helpers are needed.\
+//! If you're writing your own `serde::de::Visitor`, keep in mind that the deserializer
+//! passed to [`EDeserialize::deserialize_for_errors`] tracks the current path on your behalf:
+//! errors reported via [`ErrorReporter::report`] while visiting a map value or a sequence element
+//! are attributed to that value or element.
+//!
+//! [`EDeserialize`]: crate::EDeserialize
+//! [`EDeserialize::deserialize_for_errors`]: crate::EDeserialize::deserialize_for_errors
+use std::{fmt, marker::PhantomData};
+
+use serde::de::{DeserializeSeed, Error as _, IgnoredAny, MapAccess, SeqAccess, Visitor};
+
+use crate::{_macro_impl::abort_deserialization, reporter::ErrorReporter};
+
+/// The error reported when a required field is missing from the input.
+///
+/// It renders as ``missing field `<name>` ``, like `serde`'s own error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MissingFieldError {
+    field: &'static str,
+}
+
+impl MissingFieldError {
+    /// An error for the missing field named `field`.
+    pub fn new(field: &'static str) -> Self {
+        Self { field }
+    }
+
+    /// The name of the missing field.
+    pub fn field(&self) -> &'static str {
+        self.field
+    }
+}
+
+impl fmt::Display for MissingFieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "missing field `{}`", self.field)
+    }
+}
+
+impl std::error::Error for MissingFieldError {}
+
+/// Describe the fields of a struct with a hand-written `serde::Deserialize` implementation,
+/// to collect errors via [`deserialize_struct_for_errors`].
+///
+/// Check out the [module documentation](self) for an example.
+pub trait EDeserializeStruct<'de> {
+    /// The name of the struct, as passed to `serde::Deserializer::deserialize_struct`.
+    const NAME: &'static str;
+    /// The names of the fields, as they appear in the input, in declaration order.
+    ///
+    /// The order matters for formats that represent structs as sequences
+    /// (e.g. [postcard](crate::postcard)).
+    const FIELDS: &'static [&'static str];
+    /// Report fields that aren't listed in [`FIELDS`](Self::FIELDS) as errors,
+    /// mirroring `#[serde(deny_unknown_fields)]`.
+    ///
+    /// Unknown fields are ignored by default.
+    const DENY_UNKNOWN_FIELDS: bool = false;
+
+    /// Whether the field must be present in the input.
+    ///
+    /// If it returns `true` for a field that's missing from the input, a
+    /// [`MissingFieldError`] is reported.
+    /// All fields are required by default.
+    fn is_required(field: &'static str) -> bool {
+        let _ = field;
+        true
+    }
+
+    /// Collect the errors for the value of `field`, one of the entries of [`FIELDS`](Self::FIELDS).
+    ///
+    /// It's usually a matter of calling [`EDeserialize::deserialize_for_errors`](crate::EDeserialize::deserialize_for_errors) for
+    /// the type of the field.
+    #[allow(clippy::result_unit_err)]
+    fn field_for_errors<D>(field: &'static str, deserializer: D) -> Result<(), ()>
+    where
+        D: serde::Deserializer<'de>;
+}
+
+/// Collect the errors for a struct described by [`EDeserializeStruct`].
+///
+/// Use it to implement [`EDeserialize::deserialize_for_errors`](crate::EDeserialize::deserialize_for_errors).
+/// Check out the [module documentation](self) for an example.
+#[allow(clippy::result_unit_err)]
+pub fn deserialize_struct_for_errors<'de, T, D>(deserializer: D) -> Result<(), ()>
+where
+    T: EDeserializeStruct<'de>,
+    D: serde::Deserializer<'de>,
+{
+    deserializer
+        .deserialize_struct(T::NAME, T::FIELDS, StructVisitor::<T>(PhantomData))
+        .map_err(ErrorReporter::report)?
+}
+
+struct StructVisitor<T>(PhantomData<fn() -> T>);
+
+impl<'de, T> Visitor<'de> for StructVisitor<T>
+where
+    T: EDeserializeStruct<'de>,
+{
+    // The outer `Result` is for errors that prevent us from carrying on
+    // (e.g. malformed input), the inner one tracks if any error was reported.
+    type Value = Result<(), ()>;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "struct {}", T::NAME)
+    }
+
+    fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+    where
+        A: MapAccess<'de>,
+    {
+        let mut result = Ok(());
+        let mut seen = vec![false; T::FIELDS.len()];
+        while let Some(key) = map.next_key::<String>()? {
+            let index = T::FIELDS.iter().position(|field| *field == key);
+            let error = match index {
+                Some(index) if seen[index] => Some(A::Error::duplicate_field(T::FIELDS[index])),
+                Some(_) => None,
+                None if T::DENY_UNKNOWN_FIELDS => Some(A::Error::unknown_field(&key, T::FIELDS)),
+                None => {
+                    map.next_value::<IgnoredAny>()?;
+                    continue;
+                }
+            };
+            if let Some(error) = error {
+                ErrorReporter::report(&error);
+                if ErrorReporter::should_abort() {
+                    return Err(error);
+                }
+                result = Err(());
+                map.next_value::<IgnoredAny>()?;
+                continue;
+            }
+
+            let index = index.unwrap();
+            seen[index] = true;
+            if map
+                .next_value_seed(FieldSeed::<T>::new(T::FIELDS[index]))?
+                .is_err()
+            {
+                if ErrorReporter::should_abort() {
+                    return Err(abort_deserialization());
+                }
+                result = Err(());
+            }
+        }
+
+        for (field, _) in T::FIELDS.iter().zip(seen).filter(|(_, seen)| !seen) {
+            if T::is_required(field) {
+                ErrorReporter::report(MissingFieldError::new(field));
+                result = Err(());
+            }
+        }
+        Ok(result)
+    }
+
+    fn visit_seq<A>(self, mut seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: SeqAccess<'de>,
+    {
+        let mut result = Ok(());
+        for (i, field) in T::FIELDS.iter().enumerate() {
+            match seq.next_element_seed(FieldSeed::<T>::new(field))? {
+                Some(Ok(())) => {}
+                Some(Err(())) => {
+                    if ErrorReporter::should_abort() {
+                        return Err(abort_deserialization());
+                    }
+                    result = Err(());
+                }
+                None => {
+                    ErrorReporter::report(A::Error::invalid_length(i, &self));
+                    return Ok(Err(()));
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Collect the errors for the value of a single field.
+struct FieldSeed<T> {
+    field: &'static str,
+    _struct: PhantomData<fn() -> T>,
+}
+
+impl<T> FieldSeed<T> {
+    fn new(field: &'static str) -> Self {
+        Self {
+            field,
+            _struct: PhantomData,
+        }
+    }
+}
+
+impl<'de, T> DeserializeSeed<'de> for FieldSeed<T>
+where
+    T: EDeserializeStruct<'de>,
+{
+    type Value = Result<(), ()>;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        Ok(T::field_for_errors(self.field, deserializer))
+    }
+}
@@ -0,0 +1,798 @@
+//! Deserialize configuration from environment variables.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Config {
+//!     database_url: String,
+//!     port: u16,
+//!     db: Db,
+//! }
+//!
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Db {
+//!     pool_size: u32,
+//! }
+//!
+//! # fn main() {
+//! // In production you'd use `eserde::env::Options::from_env`, which reads `std::env::vars()`.
+//! let vars = [
+//!     ("APP_DATABASE_URL", "postgres://localhost/app"),
+//!     ("APP_PORT", "not-a-port"),
+//!     ("APP_DB__POOL_SIZE", "-1"),
+//! ];
+//!
+//! let errors = eserde::env::Options::new()
+//!     .prefix("APP_")
+//!     .separator("__")
+//!     .from_iter::<Config, _, _, _>(vars)
+//!     .unwrap_err();
+//! assert_eq!(
+//!     errors.to_string(),
+//!     r#"Something went wrong during deserialization:
+//! - APP_DB__POOL_SIZE: invalid value: string "-1", expected u32
+//! - APP_PORT: invalid value: string "not-a-port", expected u16
+//! "#
+//! );
+//! # }
+//! ```
+//!
+//! # Naming
+//!
+//! Variable names are mapped to field names as follows:
+//!
+//! - The [prefix](Options::prefix), if any, is stripped. Variables that don't start with
+//!   the prefix are ignored.
+//! - The rest of the name is split on the [separator](Options::separator), if any,
+//!   to obtain one segment for each level of nesting.
+//! - Each segment is lowercased.
+//!
+//! E.g. `APP_DB__POOL_SIZE` with prefix `APP_` and separator `__` maps to `db.pool_size`.
+//!
+//! Errors are reported using the original variable name as their path,
+//! e.g. `APP_DB__POOL_SIZE` rather than `db.pool_size`.
+//! If a whole nested struct is missing, the error points at the prefix of its variables,
+//! e.g. `APP_DB__`.
+//!
+//! # Values
+//!
+//! All values are strings.
+//! They are parsed into numbers, booleans and characters when the target type requires it.
+//! Sequences (e.g. `Vec<T>`) are read from a comma-separated list of values (e.g. `a,b,c`).
+//!
+//! # Implementation
+//!
+//! All deserializers in this module follow the same two-pass approach.
+//! Start by using `serde::Deserialize` to try to deserialize the target type.
+//! If it succeeds, return `Ok(value)`.
+//! If it fails, use `eserde::EDeserialize` to visit the input again and
+//! accumulate as many deserialization errors as possible.
+//! The errors are then returned as a vector in the `Err` variant.
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+};
+
+use serde::de::{
+    self,
+    value::{MapDeserializer, SeqDeserializer},
+    IntoDeserializer, Unexpected, Visitor,
+};
+
+use crate::{
+    path::{self, Path, Segment},
+    reporter::ErrorReporter,
+    DeserializationError, DeserializationErrors, DeserializationWarning, EDeserialize,
+};
+
+/// Deserialize an instance of type `T` from the environment variables of the current process.
+///
+/// No prefix is stripped and no nesting is performed.
+/// Use [`Options`] to customize how variable names are mapped to fields.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Config {
+///     database_url: String,
+///     port: u16,
+/// }
+///
+/// # fn main() {
+/// // Reads `DATABASE_URL` and `PORT`.
+/// let config: Config = eserde::env::from_env().unwrap();
+/// println!("{:#?}", config);
+/// # }
+/// ```
+pub fn from_env<T>() -> Result<T, DeserializationErrors>
+where
+    T: for<'a> EDeserialize<'a>,
+{
+    Options::new().from_env()
+}
+
+/// Deserialize an instance of type `T` from an iterator of `(name, value)` pairs.
+///
+/// It behaves like [`from_env`], but it doesn't read the environment of the current process.
+/// It's primarily useful for testing.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Config {
+///     database_url: String,
+///     port: u16,
+/// }
+///
+/// # fn main() {
+/// let vars = [("DATABASE_URL", "postgres://localhost/app"), ("PORT", "8080")];
+///
+/// let config: Config = eserde::env::from_iter(vars).unwrap();
+/// println!("{:#?}", config);
+/// # }
+/// ```
+pub fn from_iter<T, I, K, V>(vars: I) -> Result<T, DeserializationErrors>
+where
+    T: for<'a> EDeserialize<'a>,
+    I: IntoIterator<Item = (K, V)>,
+    K: Into<String>,
+    V: Into<String>,
+{
+    Options::new().from_iter(vars)
+}
+
+/// Customize how environment variables are mapped to the fields of the target type.
+///
+/// Check out the [module documentation](self) for an example.
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    prefix: Option<String>,
+    separator: Option<String>,
+}
+
+impl Options {
+    /// Start with the default options: no prefix, no separator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only consider variables whose name starts with `prefix`.
+    ///
+    /// The prefix is stripped before mapping the variable name to a field name.
+    /// E.g. `APP_PORT` maps to `port` with prefix `APP_`.
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = Some(prefix.into());
+        self
+    }
+
+    /// Use `separator` to split variable names into nested fields.
+    ///
+    /// E.g. `DB__PORT` maps to `db.port` with separator `__`.
+    pub fn separator(mut self, separator: impl Into<String>) -> Self {
+        self.separator = Some(separator.into());
+        self
+    }
+
+    /// Deserialize an instance of type `T` from the environment variables of the current process.
+    ///
+    /// Variables whose name or value is not valid unicode are ignored.
+    pub fn from_env<T>(&self) -> Result<T, DeserializationErrors>
+    where
+        T: for<'a> EDeserialize<'a>,
+    {
+        self.from_iter(std::env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        }))
+    }
+
+    /// Deserialize an instance of type `T` from an iterator of `(name, value)` pairs.
+    pub fn from_iter<T, I, K, V>(&self, vars: I) -> Result<T, DeserializationErrors>
+    where
+        T: for<'a> EDeserialize<'a>,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        self.deserialize_tree(&self.tree(vars))
+    }
+
+    /// Deserialize an instance of type `T` from the environment variables of the current process,
+    /// collecting the [warnings](DeserializationWarning) reported along the way—e.g. variables
+    /// that match the prefix but don't map to any field.
+    ///
+    /// It behaves like [`Options::from_env`], but on success it visits the variables a second time.
+    /// Check out [`json::from_str_with_warnings`](crate::json::from_str_with_warnings)
+    /// for more details.
+    pub fn from_env_with_warnings<T>(
+        &self,
+    ) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+    where
+        T: for<'a> EDeserialize<'a>,
+    {
+        self.from_iter_with_warnings(std::env::vars_os().filter_map(|(name, value)| {
+            Some((name.into_string().ok()?, value.into_string().ok()?))
+        }))
+    }
+
+    /// Deserialize an instance of type `T` from an iterator of `(name, value)` pairs,
+    /// collecting the [warnings](DeserializationWarning) reported along the way.
+    ///
+    /// Warnings are reported using the original variable name as their path,
+    /// like errors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #[derive(eserde::Deserialize, Debug)]
+    /// struct Config {
+    ///     port: u16,
+    /// }
+    ///
+    /// # fn main() {
+    /// let vars = [("APP_PORT", "8080"), ("APP_PROT", "8081")];
+    ///
+    /// let (config, warnings) = eserde::env::Options::new()
+    ///     .prefix("APP_")
+    ///     .from_iter_with_warnings::<Config, _, _, _>(vars)
+    ///     .unwrap();
+    /// assert_eq!(config.port, 8080);
+    /// assert_eq!(
+    ///     warnings[0].to_string(),
+    ///     "APP_PROT: unknown field `prot` was ignored"
+    /// );
+    /// # }
+    /// ```
+    pub fn from_iter_with_warnings<T, I, K, V>(
+        &self,
+        vars: I,
+    ) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+    where
+        T: for<'a> EDeserialize<'a>,
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let root = self.tree(vars);
+        let value = self.deserialize_tree(&root)?;
+        let _guard = ErrorReporter::start_deserialization();
+
+        let de = path::Deserializer::new(NodeDeserializer::new(&root));
+        // The input is known to be valid: we're only interested in warnings.
+        let _ = T::deserialize_for_errors(de);
+
+        let warnings = ErrorReporter::take_warnings()
+            .into_iter()
+            .map(|mut w| {
+                w.path = self.variable_path(&root, w.path.take());
+                w
+            })
+            .collect();
+        Ok((value, warnings))
+    }
+
+    fn deserialize_tree<T>(&self, root: &Node) -> Result<T, DeserializationErrors>
+    where
+        T: for<'a> EDeserialize<'a>,
+    {
+        let error = match T::deserialize(NodeDeserializer::new(root)) {
+            Ok(v) => {
+                return Ok(v);
+            }
+            Err(e) => e,
+        };
+        let _guard = ErrorReporter::start_deserialization();
+        let missing_maps = MissingMapsGuard::start();
+
+        let de = path::Deserializer::new(NodeDeserializer::new(root));
+
+        let errors = match T::deserialize_for_errors(de) {
+            Ok(_) => vec![],
+            Err(_) => ErrorReporter::take_errors(),
+        };
+        let errors = if errors.is_empty() {
+            vec![DeserializationError::formatted(None, error.to_string())]
+        } else {
+            let missing_maps = missing_maps.take();
+            errors
+                .into_iter()
+                .enumerate()
+                .map(|(i, e)| self.use_variable_names(root, e, missing_maps.contains(&i)))
+                .collect()
+        };
+
+        Err(DeserializationErrors::from(errors))
+    }
+
+    /// Arrange the variables that match our prefix into a tree, one level per segment.
+    pub(crate) fn tree<I, K, V>(&self, vars: I) -> Node
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: Into<String>,
+    {
+        let mut root = Node::default();
+        for (name, value) in vars {
+            let name = name.into();
+            let Some(keys) = self.keys(&name) else {
+                continue;
+            };
+            let mut node = &mut root;
+            for key in keys {
+                node = node.children.entry(key).or_default();
+            }
+            node.value = Some(value.into());
+            node.variable = Some(name);
+        }
+        root
+    }
+
+    /// Map a variable name to the sequence of (lowercased) keys it points at.
+    ///
+    /// Returns `None` if the variable should be ignored.
+    fn keys(&self, name: &str) -> Option<Vec<String>> {
+        let name = match &self.prefix {
+            Some(prefix) => name.strip_prefix(prefix.as_str())?,
+            None => name,
+        };
+        let keys: Vec<String> = match &self.separator {
+            Some(separator) if !separator.is_empty() => name
+                .split(separator.as_str())
+                .map(|key| key.to_lowercase())
+                .collect(),
+            _ => vec![name.to_lowercase()],
+        };
+        if keys.iter().any(|key| key.is_empty()) {
+            return None;
+        }
+        Some(keys)
+    }
+
+    /// The inverse of [`Options::keys`]: build the variable name that maps to the given keys.
+    fn variable_name(&self, keys: &[&str]) -> Option<String> {
+        if keys.len() > 1 && self.separator.as_deref().is_none_or(str::is_empty) {
+            return None;
+        }
+        let mut name = self.prefix.clone().unwrap_or_default();
+        name.push_str(
+            &keys
+                .iter()
+                .map(|key| key.to_uppercase())
+                .collect::<Vec<_>>()
+                .join(self.separator.as_deref().unwrap_or_default()),
+        );
+        Some(name)
+    }
+
+    /// The prefix shared by the variables nested under the given keys—e.g. `APP_DB__`.
+    fn variable_prefix(&self, keys: &[&str]) -> Option<String> {
+        let separator = self.separator.as_deref().filter(|s| !s.is_empty())?;
+        let mut prefix = self.variable_name(keys)?;
+        prefix.push_str(separator);
+        Some(prefix)
+    }
+
+    /// The name of the variable the given keys were read from, or would be read from
+    /// if it's missing.
+    fn variable(&self, root: &Node, keys: &[&str]) -> Option<String> {
+        root.find(keys)
+            .and_then(|node| node.variable.clone())
+            .or_else(|| self.variable_name(keys))
+    }
+
+    /// Replace the leading map keys in `path` with the name of the environment
+    /// variable they were read from.
+    fn variable_path(&self, root: &Node, path: Option<Path>) -> Option<Path> {
+        let segments: Vec<Segment> = path.iter().flat_map(|path| path.iter().cloned()).collect();
+        let keys: Vec<&str> = segments
+            .iter()
+            .map_while(|segment| match segment {
+                Segment::Map { key } => Some(key.as_str()),
+                _ => None,
+            })
+            .collect();
+        if keys.is_empty() {
+            return path;
+        }
+        let Some(variable) = self.variable(root, &keys) else {
+            return path;
+        };
+        let mut variable_path = vec![Segment::Map { key: variable }];
+        variable_path.extend(segments[keys.len()..].iter().cloned());
+        Some(Path::from(variable_path))
+    }
+
+    /// Replace the leading map keys in the path of the error with the name
+    /// of the environment variable they were read from.
+    ///
+    /// `missing_map` is set if the error was reported for a missing field whose
+    /// value is a map or a struct.
+    fn use_variable_names(
+        &self,
+        root: &Node,
+        mut error: DeserializationError,
+        missing_map: bool,
+    ) -> DeserializationError {
+        let segments: Vec<Segment> = error
+            .path
+            .as_ref()
+            .map(|path| path.iter().cloned().collect())
+            .unwrap_or_default();
+        let mut keys: Vec<&str> = segments
+            .iter()
+            .map_while(|segment| match segment {
+                Segment::Map { key } => Some(key.as_str()),
+                _ => None,
+            })
+            .collect();
+        let rest = &segments[keys.len()..];
+
+        // Missing fields are reported against their parent, but the user has to know
+        // which variable to set.
+        let missing = match error.field.as_deref() {
+            Some(field) if rest.is_empty() && error.code() == "missing_field" => Some(field),
+            _ => None,
+        };
+        if let Some(field) = missing {
+            keys.push(field);
+        }
+        if keys.is_empty() {
+            return error;
+        }
+
+        let variable = if missing.is_some() && missing_map {
+            // A whole nested struct is missing: point at the prefix
+            // shared by the variables that populate it.
+            self.variable_prefix(&keys)
+        } else {
+            self.variable(root, &keys)
+        };
+        let Some(variable) = variable else {
+            return error;
+        };
+
+        let mut path = vec![Segment::Map { key: variable }];
+        path.extend(rest.iter().cloned());
+        error.path = Some(Path::from(path));
+        if missing.is_some() && error.details == format!("missing field `{}`", keys[keys.len() - 1])
+        {
+            error.details = if missing_map {
+                "missing environment variables".into()
+            } else {
+                "missing environment variable".into()
+            };
+        }
+        error
+    }
+}
+
+thread_local! {
+    /// The indices of the errors reported for missing fields whose value is a map or
+    /// a struct—e.g. a whole nested configuration section.
+    ///
+    /// It's only set while environment variables are being deserialized.
+    static MISSING_MAPS: RefCell<Option<HashSet<usize>>> = const { RefCell::new(None) };
+}
+
+/// Record that the next error is reported for a missing field whose value is a map
+/// or a struct.
+///
+/// It's a no-op unless environment variables are being deserialized.
+pub(crate) fn record_missing_map() {
+    MISSING_MAPS.with_borrow_mut(|maps| {
+        if let Some(maps) = maps {
+            maps.insert(ErrorReporter::n_errors());
+        }
+    });
+}
+
+/// Collects the missing maps recorded via [`record_missing_map`] until dropped.
+struct MissingMapsGuard;
+
+impl MissingMapsGuard {
+    fn start() -> Self {
+        MISSING_MAPS.set(Some(HashSet::new()));
+        Self
+    }
+
+    fn take(&self) -> HashSet<usize> {
+        MISSING_MAPS.take().unwrap_or_default()
+    }
+}
+
+impl Drop for MissingMapsGuard {
+    fn drop(&mut self) {
+        let _ = MISSING_MAPS.try_with(|c| {
+            if let Ok(mut v) = c.try_borrow_mut() {
+                *v = None;
+            }
+        });
+    }
+}
+
+/// A level of nesting in the set of environment variables.
+#[derive(Debug, Default)]
+pub(crate) struct Node {
+    /// The name of the variable that was mapped to this node, if any.
+    pub(crate) variable: Option<String>,
+    pub(crate) value: Option<String>,
+    pub(crate) children: BTreeMap<String, Node>,
+}
+
+impl Node {
+    fn find(&self, keys: &[&str]) -> Option<&Node> {
+        keys.iter()
+            .try_fold(self, |node, key| node.children.get(*key))
+    }
+}
+
+/// Errors encountered while deserializing environment variables.
+#[derive(Debug)]
+pub(crate) struct Error(String);
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Error {}
+
+impl de::Error for Error {
+    fn custom<T: Display>(msg: T) -> Self {
+        Error(msg.to_string())
+    }
+}
+
+/// Deserialize a [`Node`], parsing its value according to the type requested by the visitor.
+pub(crate) struct NodeDeserializer<'a> {
+    node: &'a Node,
+}
+
+impl<'a> NodeDeserializer<'a> {
+    pub(crate) fn new(node: &'a Node) -> Self {
+        Self { node }
+    }
+
+    fn value<'de, V>(&self, visitor: &V) -> Result<&'a str, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.node
+            .value
+            .as_deref()
+            .ok_or_else(|| de::Error::invalid_type(Unexpected::Map, visitor))
+    }
+
+    fn parse<'de, V, T>(&self, visitor: &V) -> Result<T, Error>
+    where
+        V: Visitor<'de>,
+        T: std::str::FromStr,
+    {
+        let value = self.value(visitor)?;
+        value
+            .parse()
+            .map_err(|_| de::Error::invalid_value(Unexpected::Str(value), visitor))
+    }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for NodeDeserializer<'_> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident,)*) => {
+        $(
+            fn $method<V>(self, visitor: V) -> Result<V::Value, Error>
+            where
+                V: Visitor<'de>,
+            {
+                let v = self.parse(&visitor)?;
+                visitor.$visit(v)
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NodeDeserializer<'_> {
+    type Error = Error;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.node.children.is_empty() {
+            self.deserialize_str(visitor)
+        } else {
+            self.deserialize_map(visitor)
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_i128 => visit_i128,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_u128 => visit_u128,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let value = self.value(&visitor)?;
+        visitor.visit_str(value)
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_bytes<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let value = self.value(&visitor)?;
+        visitor.visit_bytes(value.as_bytes())
+    }
+
+    fn deserialize_byte_buf<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        // If there is a node, there is a value.
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_unit<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_unit(visitor)
+    }
+
+    fn deserialize_newtype_struct<V>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let value = self.value(&visitor)?;
+        let elements: Vec<Node> = if value.is_empty() {
+            vec![]
+        } else {
+            value
+                .split(',')
+                .map(|element| Node {
+                    variable: None,
+                    value: Some(element.to_owned()),
+                    children: BTreeMap::new(),
+                })
+                .collect()
+        };
+        let mut seq = SeqDeserializer::new(elements.iter().map(NodeDeserializer::new));
+        let v = visitor.visit_seq(&mut seq)?;
+        seq.end()?;
+        Ok(v)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        if self.node.children.is_empty() {
+            if let Some(value) = &self.node.value {
+                return Err(de::Error::invalid_type(Unexpected::Str(value), &visitor));
+            }
+        }
+        let mut map = MapDeserializer::new(
+            self.node
+                .children
+                .iter()
+                .map(|(key, node)| (key.as_str(), NodeDeserializer::new(node))),
+        );
+        let v = visitor.visit_map(&mut map)?;
+        map.end()?;
+        Ok(v)
+    }
+
+    fn deserialize_struct<V>(
+        self,
+        _name: &'static str,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        let value = self.value(&visitor)?;
+        visitor.visit_enum(value.into_deserializer())
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, Error>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+}
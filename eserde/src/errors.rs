@@ -1,4 +1,6 @@
-use crate::path::Path;
+use std::borrow::Cow;
+
+use crate::{messages, path::Path};
 
 /// A collection of errors encountered while trying to deserialize a type.
 ///
@@ -63,9 +65,71 @@ impl std::error::Error for DeserializationErrors {}
 pub struct DeserializationError {
     pub(crate) path: Option<Path>,
     pub(crate) details: String,
+    pub(crate) origin: Option<String>,
+    /// The byte offset at which the error was detected, for non-self-describing formats.
+    pub(crate) offset: Option<usize>,
+    /// Set when the error is reported, or when a custom code is assigned.
+    /// If `None`, the code is inferred from `details`.
+    pub(crate) code: Option<Cow<'static, str>>,
+    /// Set when the error is reported, from the original details.
+    pub(crate) field: Option<String>,
 }
 
 impl DeserializationError {
+    /// An error whose code is inferred from `details`.
+    pub(crate) fn new(path: Option<Path>, details: String) -> Self {
+        Self {
+            path,
+            details,
+            origin: None,
+            offset: None,
+            code: None,
+            field: None,
+        }
+    }
+
+    /// An error raised by the data format rather than reported via
+    /// [`ErrorReporter`](crate::reporter::ErrorReporter)—e.g. the error of the first pass,
+    /// if the second one finds none.
+    ///
+    /// Like reported errors, its message goes through the installed [formatter](crate::messages).
+    #[cfg_attr(
+        not(any(
+            feature = "csv",
+            feature = "env",
+            feature = "json",
+            feature = "json5",
+            feature = "postcard",
+            feature = "ron",
+            feature = "toml",
+            feature = "urlencoded",
+            feature = "xml"
+        )),
+        allow(dead_code)
+    )]
+    pub(crate) fn formatted(path: Option<Path>, details: String) -> Self {
+        let code = error_code(&details);
+        let field = error_field(&details).map(ToOwned::to_owned);
+        let details = messages::format_message(details, code, field.as_deref(), path.as_ref());
+        Self {
+            code: Some(code.into()),
+            field,
+            ..Self::new(path, details)
+        }
+    }
+
+    /// The error appended to a report truncated after the first `max_errors` errors.
+    #[cfg(feature = "json")]
+    pub(crate) fn too_many_errors(max_errors: usize) -> Self {
+        let code = "too_many_errors";
+        let details = format!("too many errors, stopped after the first {max_errors}");
+        let details = messages::format_message(details, code, None, None);
+        Self {
+            code: Some(code.into()),
+            ..Self::new(None, details)
+        }
+    }
+
     /// An explanation of what went wrong during deserialization.
     pub fn message(&self) -> &str {
         self.details.as_ref()
@@ -78,6 +142,77 @@ impl DeserializationError {
     pub fn path(&self) -> Option<&Path> {
         self.path.as_ref()
     }
+
+    /// A stable, machine-readable identifier for the kind of error.
+    ///
+    /// Use it to handle specific errors programmatically, or to translate them on the
+    /// client side, rather than matching on [`message`](Self::message).
+    ///
+    /// The built-in codes are:
+    ///
+    /// - `missing_field`, a required field is missing
+    /// - `unknown_field`, a field that isn't expected (with `#[serde(deny_unknown_fields)]`)
+    /// - `duplicate_field`, the same field appears more than once
+    /// - `unknown_variant`, an enum variant that doesn't exist
+    /// - `invalid_type`, a value of the wrong type—e.g. a string instead of a number
+    /// - `out_of_range`, a number that doesn't fit in the expected numeric type
+    /// - `invalid_value`, a value of the right type that isn't acceptable
+    /// - `invalid_length`, a sequence or a tuple with the wrong number of elements
+    /// - `too_many_errors`, the error budget has been exhausted (see
+    ///   [`json::from_slice_with_max_errors`](crate::json::from_slice_with_max_errors))
+    /// - `invalid`, for everything else—e.g. syntax errors or custom errors raised by
+    ///   `Deserialize` implementations
+    ///
+    /// Custom codes can be set on fields with `#[eserde(code = "..")]`, or when reporting
+    /// errors from hand-written deserialization logic via
+    /// [`ErrorReporter::report_with_code`](crate::reporter::ErrorReporter::report_with_code).
+    pub fn code(&self) -> &str {
+        match &self.code {
+            Some(code) => code,
+            None => error_code(&self.details),
+        }
+    }
+
+    /// The input source that supplied the offending value, when available.
+    ///
+    /// It is only set when deserializing from multiple sources at once—e.g. a file path
+    /// or `"env"` when using `eserde::config`.
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+
+    /// The byte offset in the input at which the error was detected, when available.
+    ///
+    /// It is only set by formats that can't report a path to every value—e.g.
+    /// `eserde::postcard`.
+    pub fn offset(&self) -> Option<usize> {
+        self.offset
+    }
+
+    /// The name of the offending field, for `missing_field`, `unknown_field` and
+    /// `duplicate_field` errors.
+    ///
+    /// Missing fields are reported against their parent, so their name isn't part
+    /// of the [`path`](Self::path).
+    /// Unlike the [`message`](Self::message), it isn't affected by custom messages
+    /// or by [message formatters](crate::messages).
+    ///
+    /// ```rust
+    /// #[derive(eserde::Deserialize, Debug)]
+    /// struct Server {
+    ///     #[eserde(missing = "Please provide a port")]
+    ///     port: u16,
+    /// }
+    ///
+    /// let errors = eserde::json::from_str::<Server>("{}").unwrap_err();
+    /// let error = errors.iter().next().unwrap();
+    /// assert_eq!(error.message(), "Please provide a port");
+    /// assert_eq!(error.code(), "missing_field");
+    /// assert_eq!(error.field(), Some("port"));
+    /// ```
+    pub fn field(&self) -> Option<&str> {
+        self.field.as_deref()
+    }
 }
 
 impl std::fmt::Display for DeserializationError {
@@ -87,6 +222,125 @@ impl std::fmt::Display for DeserializationError {
                 write!(f, "{}: ", path)?;
             }
         }
-        write!(f, "{}", self.details.trim())
+        write!(f, "{}", self.details.trim())?;
+        if let Some(offset) = self.offset {
+            write!(f, " at byte offset {}", offset)?;
+        }
+        if let Some(origin) = &self.origin {
+            write!(f, " (from {})", origin)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+/// A problem that didn't prevent deserialization from succeeding—e.g. a deprecated field
+/// was used, or an unknown field was ignored.
+///
+/// Use [`json::from_str_with_warnings`](crate::json::from_str_with_warnings) to collect them.
+pub struct DeserializationWarning {
+    pub(crate) path: Option<Path>,
+    pub(crate) details: String,
+    pub(crate) origin: Option<String>,
+    pub(crate) code: Cow<'static, str>,
+}
+
+impl DeserializationWarning {
+    /// An explanation of the problem.
+    pub fn message(&self) -> &str {
+        self.details.as_ref()
+    }
+
+    /// The input path of the offending value, when available.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_ref()
+    }
+
+    /// A stable, machine-readable identifier for the kind of warning.
+    ///
+    /// The built-in codes are:
+    ///
+    /// - `deprecated_field`, a field marked with `#[eserde(deprecated)]` is present in the input
+    /// - `unknown_field`, a field that isn't expected was ignored
+    ///
+    /// Custom codes can be set when reporting warnings via
+    /// [`ErrorReporter::report_warning`](crate::reporter::ErrorReporter::report_warning).
+    pub fn code(&self) -> &str {
+        &self.code
+    }
+
+    /// The input source that supplied the offending value, when available.
+    ///
+    /// It is only set when deserializing from multiple sources at once—e.g. a file path
+    /// or `"env"` when using `eserde::config`.
+    pub fn origin(&self) -> Option<&str> {
+        self.origin.as_deref()
+    }
+}
+
+impl std::fmt::Display for DeserializationWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(path) = &self.path {
+            if !path.is_empty() {
+                write!(f, "{}: ", path)?;
+            }
+        }
+        write!(f, "{}", self.details.trim())?;
+        if let Some(origin) = &self.origin {
+            write!(f, " (from {})", origin)?;
+        }
+        Ok(())
+    }
+}
+
+/// Extract the field name from `serde`'s "missing field", "unknown field"
+/// and "duplicate field" errors—e.g. ``missing field `port` ``.
+pub(crate) fn error_field(details: &str) -> Option<&str> {
+    let rest = details
+        .strip_prefix("missing field `")
+        .or_else(|| details.strip_prefix("unknown field `"))
+        .or_else(|| details.strip_prefix("duplicate field `"))?;
+    rest.split_once('`').map(|(field, _)| field)
+}
+
+/// Infer the code of an error from its message.
+///
+/// `serde` doesn't expose the kind of an error, but the messages of the errors
+/// built via `serde::de::Error`'s provided methods (e.g. `missing_field`) follow
+/// a fixed format.
+pub(crate) fn error_code(details: &str) -> &'static str {
+    const NUMBERS: &[&str] = &[
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+        "f32", "f64",
+    ];
+
+    if details.starts_with("missing field") {
+        "missing_field"
+    } else if details.starts_with("unknown field") {
+        "unknown_field"
+    } else if details.starts_with("duplicate field") {
+        "duplicate_field"
+    } else if details.starts_with("unknown variant") {
+        "unknown_variant"
+    } else if details.starts_with("invalid type:") {
+        "invalid_type"
+    } else if let Some(rest) = details.strip_prefix("invalid value: ") {
+        // E.g. "invalid value: integer `300`, expected u8".
+        let is_number = rest.starts_with("integer") || rest.starts_with("floating point");
+        let expected = rest
+            .split_once(", expected ")
+            .and_then(|(_, expected)| expected.split_whitespace().next());
+        if is_number && expected.is_some_and(|expected| NUMBERS.contains(&expected)) {
+            "out_of_range"
+        } else {
+            "invalid_value"
+        }
+    } else if details.starts_with("invalid length") {
+        "invalid_length"
+    } else if details.contains("out of range") {
+        // E.g. `serde_json`'s "number out of range".
+        "out_of_range"
+    } else {
+        "invalid"
     }
 }
@@ -262,7 +262,11 @@ macro_rules! impl_edeserialize_seq {
                     where
                         D: $crate::_serde::Deserializer<'de>,
                     {
-                        let _ =  $g ::deserialize_for_errors(deserializer);
+                        if $g ::deserialize_for_errors(deserializer).is_err()
+                            && $crate::reporter::ErrorReporter::should_abort()
+                        {
+                            return Err($crate::_macro_impl::abort_deserialization());
+                        }
                         Ok(Self(::std::marker::PhantomData))
                     }
                 }
@@ -273,11 +277,14 @@ macro_rules! impl_edeserialize_seq {
                     Ok(vec) => {
                         $(
                             if vec.len() != $n {
-                                $crate::reporter::ErrorReporter::report(::std::format!(
-                                    "expected sequence of {} elements, found {} elements.",
-                                    $n,
-                                    vec.len(),
-                                ));
+                                $crate::reporter::ErrorReporter::report_with_code(
+                                    "invalid_length",
+                                    ::std::format!(
+                                        "expected sequence of {} elements, found {} elements.",
+                                        $n,
+                                        vec.len(),
+                                    ),
+                                );
                             }
                         )?
                         let _ = vec;
@@ -371,7 +378,11 @@ macro_rules! impl_edeserialize_map {
                     where
                         D: $crate::_serde::Deserializer<'de>,
                     {
-                        let _ = T::deserialize_for_errors(deserializer);
+                        if T::deserialize_for_errors(deserializer).is_err()
+                            && $crate::reporter::ErrorReporter::should_abort()
+                        {
+                            return Err($crate::_macro_impl::abort_deserialization());
+                        }
                         Ok(Self(::std::marker::PhantomData))
                     }
                 }
@@ -446,7 +457,11 @@ where
             where
                 D: serde::Deserializer<'de>,
             {
-                let _ = T::deserialize_for_errors(deserializer);
+                if T::deserialize_for_errors(deserializer).is_err()
+                    && crate::reporter::ErrorReporter::should_abort()
+                {
+                    return Err(crate::_macro_impl::abort_deserialization());
+                }
                 Ok(())
             }
         }
@@ -37,6 +37,13 @@
 //! # }
 //! ```
 //!
+//! # JSON Lines
+//!
+//! Use [`lines`] to deserialize [JSON Lines](https://jsonlines.org/) (a.k.a. NDJSON) input,
+//! where each line is a separate JSON document.
+//! Errors are reported per line, with the (zero-based) line index prepended to
+//! their path—e.g. `[3].age`.
+//!
 //! # Implementation
 //!
 //! This module relies on [`serde_json`](https://crates.io/crates/serde_json) as
@@ -57,9 +64,14 @@
 //! us to perform two passes over the input.\
 //! We are restricted to input types that are buffered in memory (byte slices,
 //! string slices, etc.).
+use std::{marker::PhantomData, sync::Arc};
+
 use crate::{
-    impl_edeserialize_compat, path, reporter::ErrorReporter, DeserializationError,
-    DeserializationErrors, EDeserialize,
+    impl_edeserialize_compat,
+    messages::{self, with_formatter, ErrorDescription, MessageFormatter},
+    path::{self, Path, Segment},
+    reporter::ErrorReporter,
+    DeserializationError, DeserializationErrors, DeserializationWarning, EDeserialize,
 };
 
 /// Deserialize an instance of type `T` from a string of JSON text.
@@ -86,6 +98,14 @@ use crate::{
 /// # }
 /// ```
 pub fn from_str<'a, T>(s: &'a str) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_str_impl(s, 0)
+}
+
+/// Deserialize `s`, shifting the line numbers in the errors by `line_offset`.
+fn from_str_impl<'a, T>(s: &'a str, line_offset: usize) -> Result<T, DeserializationErrors>
 where
     T: EDeserialize<'a>,
 {
@@ -96,27 +116,110 @@ where
         }
         Err(e) => e,
     };
-    let _guard = ErrorReporter::start_deserialization();
+    let collect_errors = || {
+        let _guard = ErrorReporter::start_deserialization();
 
-    let mut de = serde_json::Deserializer::from_str(s);
-    let de = path::Deserializer::new(&mut de);
+        let mut de = serde_json::Deserializer::from_str(s);
+        let de = path::Deserializer::new(&mut de);
 
-    let errors = match T::deserialize_for_errors(de) {
-        Ok(_) => vec![],
-        Err(_) => ErrorReporter::take_errors(),
+        let errors = match T::deserialize_for_errors(de) {
+            Ok(_) => vec![],
+            Err(_) => ErrorReporter::take_errors(),
+        };
+        if errors.is_empty() {
+            vec![DeserializationError::formatted(None, error.to_string())]
+        } else {
+            errors
+        }
     };
-    let errors = if errors.is_empty() {
-        vec![DeserializationError {
-            path: None,
-            details: error.to_string(),
-        }]
+    let errors = if line_offset == 0 {
+        collect_errors()
     } else {
-        errors
+        let formatter = OffsetLines {
+            line_offset,
+            formatter: messages::installed_formatter(),
+        };
+        with_formatter(Arc::new(formatter), "", collect_errors)
     };
 
     Err(DeserializationErrors::from(errors))
 }
 
+/// Deserialize an instance of type `T` from a string of JSON text, collecting
+/// at most `max_errors` errors.
+///
+/// It behaves like [`from_str`], but it stops looking for errors once `max_errors`
+/// errors have been found.
+/// Check out [`from_slice_with_max_errors`] for more details.
+pub fn from_str_with_max_errors<'a, T>(
+    s: &'a str,
+    max_errors: usize,
+) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_slice_with_max_errors(s.as_bytes(), max_errors)
+}
+
+/// Deserialize an instance of type `T` from a string of JSON text, collecting the
+/// [warnings](DeserializationWarning) reported along the way.
+///
+/// It behaves like [`from_str`], but on success it visits the input a second time
+/// to look for problems that don't prevent deserialization from succeeding:
+///
+/// - fields marked with `#[eserde(deprecated)]` that are present in the input
+/// - unknown fields that were ignored, for structs without `#[serde(deny_unknown_fields)]`
+/// - warnings reported by custom deserialization logic, via
+///   [`ErrorReporter::report_warning`]
+///
+/// Unknown fields are reported with their full path—e.g. `servers[1].tls`—so you can
+/// fail a CI check on them while tolerating them at runtime.
+/// They can't be detected for structs that use `#[serde(flatten)]`, since `serde`
+/// forwards the unknown fields to the flattened fields.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Settings {
+///     #[eserde(deprecated = "use `timeout_ms` instead")]
+///     #[serde(default)]
+///     timeout: Option<u64>,
+///     #[serde(default)]
+///     timeout_ms: Option<u64>,
+/// }
+///
+/// let (settings, warnings) = eserde::json::from_str_with_warnings::<Settings>(
+///     r#"{"timeout": 5, "retries": 3}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(settings.timeout, Some(5));
+/// let warnings: Vec<_> = warnings.iter().map(|w| w.to_string()).collect();
+/// assert_eq!(
+///     warnings,
+///     [
+///         "timeout: this field is deprecated: use `timeout_ms` instead",
+///         "retries: unknown field `retries` was ignored",
+///     ]
+/// );
+/// ```
+pub fn from_str_with_warnings<'a, T>(
+    s: &'a str,
+) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let value = from_str(s)?;
+    let _guard = ErrorReporter::start_deserialization();
+
+    let mut de = serde_json::Deserializer::from_str(s);
+    let de = path::Deserializer::new(&mut de);
+    // The input is known to be valid: we're only interested in warnings.
+    let _ = T::deserialize_for_errors(de);
+
+    Ok((value, ErrorReporter::take_warnings()))
+}
+
 /// Deserialize an instance of type `T` from bytes of JSON text.
 ///
 /// # Example
@@ -141,6 +244,77 @@ where
 /// # }
 /// ```
 pub fn from_slice<'a, T>(s: &'a [u8]) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_slice_impl(s, None)
+}
+
+/// Deserialize an instance of type `T` from bytes of JSON text, collecting
+/// at most `max_errors` errors.
+///
+/// It behaves like [`from_slice`], but it stops looking for errors once `max_errors`
+/// errors have been found, bounding the amount of work performed on inputs with
+/// many errors (e.g. attacker-controlled payloads).
+/// If more errors would have been reported, the list is truncated and a final error
+/// is appended: `too many errors, stopped after the first {max_errors}`.
+///
+/// `max_errors` is clamped to be at least 1.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Point {
+///     x: u32,
+///     y: u32,
+///     z: u32,
+/// }
+///
+/// # fn main() {
+/// let j = br#"{ "x": -1, "y": -2, "z": -3 }"#;
+///
+/// let errors = eserde::json::from_slice_with_max_errors::<Point>(j, 2).unwrap_err();
+/// assert_eq!(errors.len(), 3);
+/// # }
+/// ```
+pub fn from_slice_with_max_errors<'a, T>(
+    s: &'a [u8],
+    max_errors: usize,
+) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_slice_impl(s, Some(max_errors.max(1)))
+}
+
+/// Deserialize an instance of type `T` from bytes of JSON text, collecting the
+/// [warnings](DeserializationWarning) reported along the way—e.g. unknown fields that
+/// were ignored.
+///
+/// It behaves like [`from_slice`], but on success it visits the input a second time.
+/// Check out [`from_str_with_warnings`] for more details.
+pub fn from_slice_with_warnings<'a, T>(
+    s: &'a [u8],
+) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let value = from_slice(s)?;
+    let _guard = ErrorReporter::start_deserialization();
+
+    let mut de = serde_json::Deserializer::from_slice(s);
+    let de = path::Deserializer::new(&mut de);
+    // The input is known to be valid: we're only interested in warnings.
+    let _ = T::deserialize_for_errors(de);
+
+    Ok((value, ErrorReporter::take_warnings()))
+}
+
+fn from_slice_impl<'a, T>(
+    s: &'a [u8],
+    max_errors: Option<usize>,
+) -> Result<T, DeserializationErrors>
 where
     T: EDeserialize<'a>,
 {
@@ -151,20 +325,29 @@ where
         }
         Err(e) => e,
     };
-    let _guard = ErrorReporter::start_deserialization();
+    // Collect one more error than requested, to know if the list was truncated.
+    let _guard = match max_errors {
+        Some(max_errors) => {
+            ErrorReporter::start_deserialization_with_max_errors(max_errors.saturating_add(1))
+        }
+        None => ErrorReporter::start_deserialization(),
+    };
 
     let mut de = serde_json::Deserializer::from_slice(s);
     let de = path::Deserializer::new(&mut de);
 
-    let errors = match T::deserialize_for_errors(de) {
+    let mut errors = match T::deserialize_for_errors(de) {
         Ok(_) => vec![],
         Err(_) => ErrorReporter::take_errors(),
     };
+    if let Some(max_errors) = max_errors {
+        if errors.len() > max_errors {
+            errors.truncate(max_errors);
+            errors.push(DeserializationError::too_many_errors(max_errors));
+        }
+    }
     let errors = if errors.is_empty() {
-        vec![DeserializationError {
-            path: None,
-            details: error.to_string(),
-        }]
+        vec![DeserializationError::formatted(None, error.to_string())]
     } else {
         errors
     };
@@ -172,6 +355,178 @@ where
     Err(DeserializationErrors::from(errors))
 }
 
+/// Deserialize a stream of instances of type `T` from [JSON Lines](https://jsonlines.org/) text,
+/// one per line.
+///
+/// Blank lines are skipped.
+/// Each line is deserialized on its own: errors are reported per line, with
+/// the (zero-based) line index prepended to their path.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Record {
+///     id: u64,
+///     name: String,
+/// }
+///
+/// # fn main() {
+/// let input = r#"{ "id": 1, "name": "foo" }
+/// { "id": -2, "name": "bar" }
+/// { "id": 3, "name": "baz" }"#;
+///
+/// for record in eserde::json::lines::<Record>(input) {
+///     match record {
+///         Ok(record) => println!("{record:?}"),
+///         Err(errors) => {
+///             for error in errors.iter() {
+///                 // E.g. "[1].id: invalid value: integer `-2`, expected u64 at line 2 column 10"
+///                 println!("{error}")
+///             }
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub fn lines<'a, T>(s: &'a str) -> Lines<'a, T>
+where
+    T: EDeserialize<'a>,
+{
+    Lines {
+        lines: s.lines().enumerate(),
+        _marker: PhantomData,
+    }
+}
+
+/// An iterator over the instances deserialized from [JSON Lines](https://jsonlines.org/) text.
+///
+/// It is created by [`lines`]. Check out its documentation for more details.
+pub struct Lines<'a, T> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<'a, T> Lines<'a, T>
+where
+    T: EDeserialize<'a>,
+{
+    /// Deserialize all remaining lines.
+    ///
+    /// If any line fails, return the errors from all failed lines as a single report,
+    /// in line order.\
+    /// The report contains at most `max_errors` errors: once the cap is exceeded, the
+    /// remaining lines are not checked and a final error is appended to point that out.\
+    /// `max_errors` is clamped to be at least 1.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// #[derive(eserde::Deserialize, Debug)]
+    /// struct Record {
+    ///     id: u64,
+    /// }
+    ///
+    /// # fn main() {
+    /// let input = "{ \"id\": -1 }\n{ \"id\": -2 }\n{ \"id\": -3 }";
+    ///
+    /// let errors = eserde::json::lines::<Record>(input)
+    ///     .collect_all(2)
+    ///     .unwrap_err();
+    /// assert_eq!(errors.len(), 3);
+    /// # }
+    /// ```
+    pub fn collect_all(self, max_errors: usize) -> Result<Vec<T>, DeserializationErrors> {
+        let max_errors = max_errors.max(1);
+        let mut values = Vec::new();
+        let mut errors = Vec::new();
+        for result in self {
+            match result {
+                Ok(value) => {
+                    if errors.is_empty() {
+                        values.push(value);
+                    }
+                }
+                Err(e) => {
+                    errors.extend(e);
+                    if errors.len() > max_errors {
+                        errors.truncate(max_errors);
+                        errors.push(DeserializationError::too_many_errors(max_errors));
+                        break;
+                    }
+                }
+            }
+        }
+        if errors.is_empty() {
+            Ok(values)
+        } else {
+            Err(DeserializationErrors::from(errors))
+        }
+    }
+}
+
+impl<'a, T> Iterator for Lines<'a, T>
+where
+    T: EDeserialize<'a>,
+{
+    type Item = Result<T, DeserializationErrors>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (index, line) = self.lines.find(|(_, line)| !line.trim().is_empty())?;
+        // Each line is parsed on its own, so `serde_json` counts lines from 1.
+        // Point at the actual line in the input instead.
+        Some(from_str_impl(line, index).map_err(|errors| {
+            let errors = errors
+                .into_iter()
+                .map(|mut e| {
+                    let segments = std::iter::once(Segment::Seq { index })
+                        .chain(e.path.iter().flat_map(|path| path.iter().cloned()))
+                        .collect::<Vec<_>>();
+                    e.path = Some(Path::from(segments));
+                    e
+                })
+                .collect::<Vec<_>>();
+            DeserializationErrors::from(errors)
+        }))
+    }
+}
+
+/// Shift the line numbers in the messages of `serde_json`'s errors by `line_offset`,
+/// then hand them over to the formatter installed by the caller, if any.
+///
+/// Used when the input is a part of a larger document, as in [`lines`].
+struct OffsetLines {
+    line_offset: usize,
+    formatter: Option<(Arc<dyn MessageFormatter>, String)>,
+}
+
+impl MessageFormatter for OffsetLines {
+    fn format(&self, error: &ErrorDescription<'_>, _locale: &str) -> Option<String> {
+        let shifted = offset_line(error.message(), self.line_offset);
+        let formatted = self.formatter.as_ref().and_then(|(formatter, locale)| {
+            let message = shifted.as_deref().unwrap_or(error.message());
+            let error = ErrorDescription::new(error.code(), message, error.field(), error.path());
+            formatter.format(&error, locale)
+        });
+        formatted.or(shifted)
+    }
+}
+
+/// Shift the line number in the position `serde_json` appends to its messages—e.g.
+/// ` at line 1 column 11`—by `line_offset`.
+///
+/// Returns `None` if the message doesn't end with a position.
+fn offset_line(message: &str, line_offset: usize) -> Option<String> {
+    let (message, position) = message.rsplit_once(" at line ")?;
+    let (line, column) = position.split_once(" column ")?;
+    let line = line.parse::<usize>().ok()?;
+    let column = column.parse::<usize>().ok()?;
+    Some(format!(
+        "{message} at line {} column {column}",
+        line.saturating_add(line_offset)
+    ))
+}
+
 impl_edeserialize_compat! {
     serde_json::value::Number,
     serde_json::value::Value,
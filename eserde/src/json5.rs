@@ -0,0 +1,146 @@
+//! Deserialize [JSON5](https://json5.org/) documents.
+//!
+//! JSON5 is a superset of JSON that's easier to write and maintain by hand.
+//! Among other things, it allows comments, trailing commas and unquoted keys.
+//! This makes it a good fit for human-edited configuration files, including those
+//! written in JSON with comments (a.k.a. JSONC).
+//!
+//! # Example
+//!
+//! ```rust
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Config {
+//!     name: String,
+//!     port: u16,
+//!     tags: Vec<String>,
+//! }
+//!
+//! # fn main() {
+//! let data = r#"
+//!     // The service configuration.
+//!     {
+//!         name: "api",
+//!         /* Must be between 1024 and 65535. */
+//!         port: 8080,
+//!         tags: [
+//!             "web",
+//!             "public",
+//!         ],
+//!     }"#;
+//!
+//! match eserde::json5::from_str::<Config>(data) {
+//!     Ok(config) => {
+//!         println!("Starting {} on port {}", config.name, config.port);
+//!     }
+//!     Err(errors) => {
+//!         println!("Something went wrong during deserialization");
+//!         for error in errors.iter() {
+//!             println!("{error}")
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! # Dynamic values
+//!
+//! JSON5 doesn't have a dedicated value type.
+//! Use `serde_json::Value` (and enable the `json` feature) if you need to
+//! deserialize a document of unknown shape.
+//!
+//! # Implementation
+//!
+//! This module relies on [`json5`](https://crates.io/crates/json5) as
+//! the underlying deserializer.
+//!
+//! All deserializers in this module follow the same two-pass approach.
+//! Start by using `serde::Deserialize` to try to deserialize the target type.
+//! If it succeeds, return `Ok(value)`.
+//! If it fails, use `eserde::EDeserialize` to visit the input again and
+//! accumulate as many deserialization errors as possible.
+//! The errors are then returned as a vector in the `Err` variant.
+use crate::{
+    path, reporter::ErrorReporter, scalars::ScalarsAsAny, DeserializationError,
+    DeserializationErrors, DeserializationWarning, EDeserialize,
+};
+
+/// Deserialize an instance of type `T` from a string of JSON5 text.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct User {
+///     fingerprint: String,
+///     location: String,
+/// }
+///
+/// # fn main() {
+/// let j = "
+///     {
+///         fingerprint: '0xF9BA143B95FF6D82', // Single quotes are fine too.
+///         location: 'Menlo Park, CA',
+///     }";
+///
+/// let u: User = eserde::json5::from_str(j).unwrap();
+/// println!("{:#?}", u);
+/// # }
+/// ```
+pub fn from_str<'a, T>(s: &'a str) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    // The input is parsed upfront: syntax errors are caught here,
+    // before we get a chance to visit it.
+    let mut de = json5::Deserializer::from_str(s).map_err(|e| {
+        DeserializationErrors::from(vec![DeserializationError::formatted(None, e.to_string())])
+    })?;
+    let error = match T::deserialize(ScalarsAsAny(&mut de)) {
+        Ok(v) => {
+            return Ok(v);
+        }
+        Err(e) => e,
+    };
+    let _guard = ErrorReporter::start_deserialization();
+
+    let mut de = json5::Deserializer::from_str(s)
+        .expect("The input was successfully parsed on the first pass");
+    let de = path::Deserializer::new(ScalarsAsAny(&mut de));
+
+    let errors = match T::deserialize_for_errors(de) {
+        Ok(_) => vec![],
+        Err(_) => ErrorReporter::take_errors(),
+    };
+    let errors = if errors.is_empty() {
+        vec![DeserializationError::formatted(None, error.to_string())]
+    } else {
+        errors
+    };
+
+    Err(DeserializationErrors::from(errors))
+}
+
+/// Deserialize an instance of type `T` from a string of JSON5 text, collecting the
+/// [warnings](DeserializationWarning) reported along the way—e.g. unknown fields that
+/// were ignored.
+///
+/// It behaves like [`from_str`], but on success it visits the input a second time.
+/// Check out [`json::from_str_with_warnings`](crate::json::from_str_with_warnings)
+/// for more details.
+pub fn from_str_with_warnings<'a, T>(
+    s: &'a str,
+) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let value = from_str(s)?;
+    let _guard = ErrorReporter::start_deserialization();
+
+    let mut de = json5::Deserializer::from_str(s)
+        .expect("The input was successfully parsed on the first pass");
+    let de = path::Deserializer::new(ScalarsAsAny(&mut de));
+    // The input is known to be valid: we're only interested in warnings.
+    let _ = T::deserialize_for_errors(de);
+
+    Ok((value, ErrorReporter::take_warnings()))
+}
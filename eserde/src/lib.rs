@@ -153,9 +153,30 @@
 //! `eserde::json` doesn't support deserializing from a reader, i.e. there is no equivalent to
 //! `serde_json::from_reader`.
 //!
+//! For [JSON Lines](https://jsonlines.org/) (a.k.a. NDJSON) input, use [`eserde::json::lines`](crate::json::lines).
+//! Errors are reported per line, with the line index prepended to their path (e.g. `[3].age`).
+//!
 //! There is also an `axum` integration, [`eserde_axum`](https://docs.rs/eserde_axum).
 //! It provides an `eserde`-powered JSON extractor as a drop-in replacement for `axum`'s built-in
-//! one.
+//! one, as well as a content-negotiated `Body` extractor that accepts JSON, URL-encoded forms,
+//! TOML or XML depending on the `Content-Type` of the request.
+//!
+//! For `actix-web`, use [`eserde_actix`](https://docs.rs/eserde_actix). It provides `Json`, `Query`
+//! and `Form` extractors. Both integrations return the same
+//! [problem details](https://www.rfc-editor.org/rfc/rfc9457.html) error responses, built with
+//! [`eserde_problem`](https://docs.rs/eserde_problem).
+//!
+//! ### JSON5
+//!
+//! `eserde` supports [JSON5](https://json5.org/), a superset of JSON that allows comments, trailing commas
+//! and unquoted keys, gated behind the `json5` Cargo feature.
+//! ```toml
+//! [dependencies]
+//! eserde = { version = "0.1", features = ["json5"] }
+//! serde = "1"
+//! ```
+//! If you're working with human-edited JSON files (e.g. JSON with comments):
+//! - Replace `json5::from_str` or `serde_json::from_str` with `eserde::json5::from_str`
 //!
 //! ### TOML
 //!
@@ -168,6 +189,110 @@
 //! If you're working with TOML:
 //! - Replace `toml::from_str` with `eserde::toml::from_str`
 //!
+//! ### RON
+//!
+//! `eserde` provides first-class support for [RON](https://github.com/ron-rs/ron) deserialization,
+//! gated behind the `ron` Cargo feature.
+//! ```toml
+//! [dependencies]
+//! eserde = { version = "0.1", features = ["ron"] }
+//! serde = "1"
+//! ```
+//! If you're working with RON:
+//! - Replace `ron::from_str` with `eserde::ron::from_str`
+//!
+//! ### URL-encoded
+//!
+//! `eserde` provides first-class support for URL-encoded data (e.g. query strings and HTML forms),
+//! gated behind the `urlencoded` Cargo feature.
+//! ```toml
+//! [dependencies]
+//! eserde = { version = "0.1", features = ["urlencoded"] }
+//! serde = "1"
+//! ```
+//! If you're working with URL-encoded data:
+//! - Replace `serde_urlencoded::from_str` with `eserde::urlencoded::from_str`
+//! - Replace `serde_urlencoded::from_bytes` with `eserde::urlencoded::from_bytes`
+//!
+//! Repeated keys (e.g. `tag=a&tag=b`) can be deserialized into a `Vec<T>`.
+//!
+//! ### XML
+//!
+//! `eserde` provides first-class support for XML deserialization, gated behind the `xml` Cargo feature.
+//! ```toml
+//! [dependencies]
+//! eserde = { version = "0.1", features = ["xml"] }
+//! serde = "1"
+//! ```
+//! If you're working with XML:
+//! - Replace `quick_xml::de::from_str` with `eserde::xml::from_str`
+//!
+//! Error paths start with the name of the root element and render attributes with an `@` prefix,
+//! e.g. `order.item[2].@sku`.
+//!
+//! ### CSV
+//!
+//! `eserde` provides first-class support for CSV deserialization, gated behind the `csv` Cargo feature.
+//! ```toml
+//! [dependencies]
+//! eserde = { version = "0.1", features = ["csv"] }
+//! serde = "1"
+//! ```
+//! If you're working with CSV:
+//! - Use `eserde::csv::from_str` or `eserde::csv::from_reader` to deserialize all records into a `Vec<T>`
+//!
+//! Errors from all records are collected, each with its row number, column name and raw cell text.
+//!
+//! ### Postcard
+//!
+//! `eserde` supports [postcard](https://github.com/jamesmunns/postcard), a non-self-describing binary format,
+//! gated behind the `postcard` Cargo feature.
+//! ```toml
+//! [dependencies]
+//! eserde = { version = "0.1", features = ["postcard"] }
+//! serde = "1"
+//! ```
+//! If you're working with postcard:
+//! - Replace `postcard::from_bytes` with `eserde::postcard::from_bytes`
+//!
+//! Non-self-describing formats can't skip over an invalid value, so only the first error is reported,
+//! together with its path and the byte offset at which it was detected.
+//!
+//! ### Environment variables
+//!
+//! `eserde` can deserialize configuration from environment variables, gated behind the `env` Cargo feature.
+//! ```toml
+//! [dependencies]
+//! eserde = { version = "0.1", features = ["env"] }
+//! serde = "1"
+//! ```
+//! If you're loading configuration from environment variables:
+//! - Use `eserde::env::from_env` to read them from the current process
+//! - Use `eserde::env::Options` to strip a prefix (e.g. `APP_`) and map nested fields (e.g. `APP_DB__PORT`)
+//!
+//! Errors are reported using the name of the offending environment variable.
+//!
+//! ### Layered configuration
+//!
+//! `eserde` can merge configuration from multiple sources (e.g. files, environment variables and overrides),
+//! gated behind the `config` Cargo feature.
+//! ```toml
+//! [dependencies]
+//! eserde = { version = "0.1", features = ["config", "toml"] }
+//! serde = "1"
+//! ```
+//! If you're loading configuration from multiple sources:
+//! - Use `eserde::config::ConfigBuilder` to add sources in order of precedence, then call `deserialize`
+//!
+//! Each error is tagged with the source that supplied the offending value (e.g. `(from defaults.toml)`).
+//!
+//! To detect configuration drift without rejecting unknown keys, call `deserialize_with_warnings` instead:
+//! it returns the keys that were ignored, with their full path and source, alongside the deserialized value.
+//! Most formats provide a `*_with_warnings` variant of their entrypoints for the same purpose
+//! (e.g. `eserde::toml::from_str_with_warnings` or `eserde::env::Options::from_iter_with_warnings`).
+//! `eserde::csv` and `eserde::postcard` don't: CSV columns are known upfront from the header row,
+//! and postcard doesn't encode field names.
+//!
 //! ### Other formats
 //!
 //! The approach used by `eserde` is compatible, in principle, with all existing `serde`-based
@@ -204,6 +329,10 @@
 //!
 //! Check out the [documentation of `eserde`'s derive macro for more details](crate::Deserialize).
 //!
+//! If you maintain a type with a hand-written `serde::Deserialize` implementation, use the
+//! building blocks in [`eserde::de`](crate::de) to ship an `eserde::EDeserialize` implementation
+//! that reports all errors, rather than just the first one.
+//!
 //! ## Under the hood
 //!
 //! But how does `eserde` actually work? Let's keep using JSON as an example—the same applies to other data formats.\
@@ -299,19 +428,55 @@
 #![deny(missing_docs)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
 
+#[cfg(feature = "config")]
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+pub mod config;
+
+#[cfg(feature = "csv")]
+#[cfg_attr(docsrs, doc(cfg(feature = "csv")))]
+pub mod csv;
+
+#[cfg(feature = "env")]
+#[cfg_attr(docsrs, doc(cfg(feature = "env")))]
+pub mod env;
+
 #[cfg(feature = "json")]
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 pub mod json;
 
+#[cfg(feature = "json5")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json5")))]
+pub mod json5;
+
+#[cfg(feature = "postcard")]
+#[cfg_attr(docsrs, doc(cfg(feature = "postcard")))]
+pub mod postcard;
+
+#[cfg(feature = "ron")]
+#[cfg_attr(docsrs, doc(cfg(feature = "ron")))]
+pub mod ron;
+
 #[cfg(feature = "toml")]
 #[cfg_attr(docsrs, doc(cfg(feature = "toml")))]
 pub mod toml;
 
+#[cfg(feature = "urlencoded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+pub mod urlencoded;
+
+#[cfg(feature = "xml")]
+#[cfg_attr(docsrs, doc(cfg(feature = "xml")))]
+pub mod xml;
+
+pub mod de;
 mod errors;
 mod impl_;
+pub mod messages;
 pub mod path;
 pub mod reporter;
-pub use errors::{DeserializationError, DeserializationErrors};
+#[cfg(any(feature = "json5", feature = "ron"))]
+mod scalars;
+pub use errors::{DeserializationError, DeserializationErrors, DeserializationWarning};
 pub(crate) use impl_::impl_edeserialize_map;
 pub(crate) use impl_::impl_edeserialize_seq;
 pub(crate) use impl_::impl_edeserialize_transparent;
@@ -440,6 +605,132 @@ pub mod _macro_impl;
 /// There's a catch, though: you'll get at most one deserialization error from a field annotated
 /// with `#[eserde(compat)]`, since we can't rely on [`EDeserialize`]'s error machinery.
 ///
+/// ## Custom deserialization logic
+///
+/// A `#[serde(deserialize_with = "..")]` function returns a single `Result`, so it can
+/// report at most one error.\
+/// If you want a custom parser to report several errors, annotate the field with
+/// `#[eserde(with = "module")]`. The module must provide two functions, mirroring
+/// `serde::Deserialize::deserialize` and [`EDeserialize::deserialize_for_errors`]:
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Server {
+///     #[eserde(with = "ports")]
+///     ports: Vec<u16>,
+/// }
+///
+/// /// Comma-separated ports—e.g. `"80,443"`.
+/// mod ports {
+///     use eserde::reporter::ErrorReporter;
+///     use serde::{de::Error, Deserialize, Deserializer};
+///
+///     pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<u16>, D::Error> {
+///         let s: &str = Deserialize::deserialize(deserializer)?;
+///         s.split(',')
+///             .map(|p| p.parse().map_err(|_| D::Error::custom(format!("invalid port `{p}`"))))
+///             .collect()
+///     }
+///
+///     pub fn deserialize_for_errors<'de, D: Deserializer<'de>>(deserializer: D) -> Result<(), ()> {
+///         let s: &str = Deserialize::deserialize(deserializer).map_err(ErrorReporter::report)?;
+///         let mut result = Ok(());
+///         for p in s.split(',').filter(|p| p.parse::<u16>().is_err()) {
+///             ErrorReporter::report(format!("invalid port `{p}`"));
+///             result = Err(());
+///         }
+///         result
+///     }
+/// }
+///
+/// let errors = eserde::json::from_str::<Server>(r#"{"ports": "http,80,https"}"#).unwrap_err();
+/// assert_eq!(errors.len(), 2);
+/// ```
+///
+/// Errors reported by `deserialize_for_errors` are attributed to the field they occur in,
+/// or to a nested value if you delegate to the [`EDeserialize`] implementation of another type.
+///
+/// ## Custom error messages
+///
+/// By default, errors carry the message produced by `serde` or by the format—e.g.
+/// ``invalid type: string "2", expected u32``.\
+/// Use `#[eserde(message = "..")]` on a field to replace it with your own text.
+/// `#[eserde(missing = "..")]` and `#[eserde(invalid = "..")]` override the message
+/// for a missing or an invalid value, respectively.
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Signup {
+///     #[eserde(message = "Age must be a whole number")]
+///     age: u32,
+///     #[eserde(missing = "Please tell us your {field}", invalid = "{field}: {message}")]
+///     name: String,
+/// }
+///
+/// let errors = eserde::json::from_str::<Signup>(r#"{"age": "2"}"#).unwrap_err();
+/// let messages: Vec<_> = errors.iter().map(|e| e.message()).collect();
+/// assert_eq!(messages, ["Age must be a whole number", "Please tell us your name"]);
+/// ```
+///
+/// Messages can use the following placeholders:
+/// - `{message}`, the original message
+/// - `{field}`, the name of the field, as it appears in the input
+/// - `{expected}`, the type of the field, as written in its definition (e.g. `Vec<String>`)
+///
+/// The custom message applies to all the errors reported while deserializing the field,
+/// including errors in nested values.
+///
+/// ## Error codes
+///
+/// Every error carries a machine-readable code, available via [`DeserializationError::code`]—e.g.
+/// `missing_field` or `invalid_type`. Use `#[eserde(code = "..")]` on a field to replace the code
+/// of the errors reported while deserializing it:
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Signup {
+///     #[eserde(code = "invalid_age")]
+///     age: u32,
+///     name: String,
+/// }
+///
+/// let errors = eserde::json::from_str::<Signup>(r#"{"age": "2"}"#).unwrap_err();
+/// let codes: Vec<_> = errors.iter().map(|e| e.code()).collect();
+/// assert_eq!(codes, ["invalid_age", "missing_field"]);
+/// ```
+///
+/// Custom validation logic can pick its own code via [`ErrorReporter::report_with_code`](reporter::ErrorReporter::report_with_code).
+/// To translate the messages themselves, check out the [`messages`] module.
+///
+/// ## Deprecated fields
+///
+/// Mark a field with `#[eserde(deprecated)]`, or `#[eserde(deprecated = "..")]` to add a note,
+/// to report a [warning](DeserializationWarning) when it's present in the input.
+/// Warnings don't cause deserialization to fail: use
+/// [`json::from_str_with_warnings`] to collect them.
+///
+/// ## Identifier enums
+///
+/// Enums annotated with `#[serde(field_identifier)]` or `#[serde(variant_identifier)]` are
+/// deserialized from a single string (or integer), so `eserde` treats them as leaves, with the
+/// same behaviour as `#[eserde(compat)]`.
+/// An unknown identifier is reported as one error, listing the accepted identifiers:
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// #[serde(variant_identifier)]
+/// enum Level {
+///     Debug,
+///     Info,
+/// }
+///
+/// let errors = eserde::json::from_str::<Vec<Level>>(r#"["Info", "Trace"]"#).unwrap_err();
+/// assert_eq!(
+///     errors.iter().next().unwrap().to_string(),
+///     "[1]: unknown variant `Trace`, expected `Debug` or `Info` at line 1 column 16"
+/// );
+/// ```
+///
 /// ## Limitations
 ///
 /// `eserde` doesn't support _all_ `serde` attributes (yet).
@@ -451,8 +742,6 @@ pub mod _macro_impl;
 /// - `#[serde(try_from = "...")]`
 /// - `#[serde(from = "...")]`
 /// - `#[serde(bound = "...")]`
-/// - `#[serde(variant_identifier)]`
-/// - `#[serde(field_identifier)]`
 ///
 /// The following [variant attributes](https://serde.rs/variant-attrs.html) will be rejected at compile-time:
 /// - `#[serde(skip_deserializing)]`
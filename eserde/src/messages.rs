@@ -0,0 +1,244 @@
+//! Localize the messages of deserialization errors.
+//!
+//! `eserde` forwards the messages produced by `serde` and by the underlying data
+//! format, which are in English.
+//! Implement [`MessageFormatter`] and install it via [`with_formatter`] to replace
+//! them with your own text—e.g. a translation in the language of your users.
+//!
+//! # Example
+//!
+//! ```rust
+//! use std::sync::Arc;
+//! use eserde::messages::{with_formatter, ErrorDescription};
+//!
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Signup {
+//!     name: String,
+//!     age: u32,
+//! }
+//!
+//! let formatter = |error: &ErrorDescription<'_>, locale: &str| match (locale, error.code()) {
+//!     ("it", "missing_field") => Some(format!("il campo `{}` è obbligatorio", error.field()?)),
+//!     ("it", "invalid_type") => Some(format!("atteso {}", error.expected()?)),
+//!     // Keep the original message.
+//!     _ => None,
+//! };
+//!
+//! let errors = with_formatter(Arc::new(formatter), "it", || {
+//!     eserde::json::from_str::<Signup>(r#"{"age": "2"}"#)
+//! })
+//! .unwrap_err();
+//! let messages: Vec<_> = errors.iter().map(|e| e.message()).collect();
+//! assert_eq!(messages, ["atteso u32", "il campo `name` è obbligatorio"]);
+//! ```
+//!
+//! # Scope
+//!
+//! The formatter is applied to the errors reported via
+//! [`ErrorReporter`](crate::reporter::ErrorReporter), at the time they're recorded,
+//! as well as to the errors raised by the data format itself—e.g. syntax errors.
+//! Custom messages set via `#[eserde(message = "..")]` take precedence over the formatter,
+//! and [error codes](crate::DeserializationError::code) are never affected.
+use std::{cell::RefCell, sync::Arc};
+
+use crate::path::{Path, Segment};
+
+/// Produce the message of a deserialization error for a given locale.
+///
+/// Check out the [module documentation](self) for an example.
+pub trait MessageFormatter: Send + Sync + 'static {
+    /// The message for `error` in `locale`—e.g. `it` or `pt-BR`.
+    ///
+    /// Return `None` to keep the original message (e.g. for unsupported locales).
+    fn format(&self, error: &ErrorDescription<'_>, locale: &str) -> Option<String>;
+}
+
+impl<F> MessageFormatter for F
+where
+    F: Fn(&ErrorDescription<'_>, &str) -> Option<String> + Send + Sync + 'static,
+{
+    fn format(&self, error: &ErrorDescription<'_>, locale: &str) -> Option<String> {
+        self(error, locale)
+    }
+}
+
+/// A structured description of a deserialization error, passed to [`MessageFormatter`].
+///
+/// [`expected`](Self::expected) and [`unexpected`](Self::unexpected) are extracted
+/// from the original message, depending on the [code](Self::code):
+///
+/// | Code | `expected` | `unexpected` |
+/// |------|------------|--------------|
+/// | `invalid_type`, `invalid_value`, `out_of_range` | E.g. `u32` | E.g. ``string "2"`` |
+/// | `invalid_length` | E.g. `a tuple of size 2` | The actual length—e.g. `1` |
+/// | `unknown_variant` | The known variants | The unknown variant |
+/// | `unknown_field` | The known fields | `None` |
+///
+/// Both are `None` for all other codes, as well as for messages that don't follow
+/// the wording used by `serde` (e.g. custom errors raised by a data format).
+#[derive(Debug, Clone)]
+pub struct ErrorDescription<'a> {
+    code: &'a str,
+    message: &'a str,
+    expected: Option<&'a str>,
+    unexpected: Option<&'a str>,
+    field: Option<&'a str>,
+    path: Option<&'a Path>,
+}
+
+impl<'a> ErrorDescription<'a> {
+    pub(crate) fn new(
+        code: &'a str,
+        message: &'a str,
+        field: Option<&'a str>,
+        path: Option<&'a Path>,
+    ) -> Self {
+        let mut description = Self {
+            code,
+            message,
+            expected: None,
+            unexpected: None,
+            field,
+            path,
+        };
+        // E.g. `serde_json` appends " at line 1 column 11" to its messages.
+        let details = match message.rsplit_once(" at line ") {
+            Some((details, position)) if is_position(position) => details,
+            _ => message,
+        };
+        if let Some(rest) = details
+            .strip_prefix("invalid type: ")
+            .or_else(|| details.strip_prefix("invalid value: "))
+            .or_else(|| details.strip_prefix("invalid length "))
+        {
+            if let Some((unexpected, expected)) = rest.split_once(", expected ") {
+                description.unexpected = Some(unexpected);
+                description.expected = Some(expected);
+            }
+        } else if let Some(rest) = details.strip_prefix("expected ") {
+            // Reported by `eserde` for arrays—e.g. `expected sequence of 2 elements, found 1 elements.`
+            if let Some((expected, rest)) = rest.split_once(", found ") {
+                description.expected = Some(expected);
+                description.unexpected = rest.strip_suffix(" elements.");
+            }
+        } else if let Some(rest) = details.strip_prefix("unknown variant ") {
+            let (variant, expected) = split_name(rest);
+            description.unexpected = variant;
+            description.expected = expected;
+        } else if let Some(rest) = details.strip_prefix("unknown field ") {
+            description.expected = split_name(rest).1;
+        }
+        if description.field.is_none() {
+            description.field = path.and_then(|path| match path.iter().next_back()? {
+                Segment::Map { key } => Some(key.as_str()),
+                _ => None,
+            });
+        }
+        description
+    }
+
+    /// The [code](crate::DeserializationError::code) of the error—e.g. `missing_field`.
+    pub fn code(&self) -> &'a str {
+        self.code
+    }
+
+    /// The original message, as produced by `serde` or by the data format.
+    pub fn message(&self) -> &'a str {
+        self.message
+    }
+
+    /// What the deserializer was expecting, when available—e.g. `u32` or
+    /// ``one of `a`, `b` ``.
+    pub fn expected(&self) -> Option<&'a str> {
+        self.expected
+    }
+
+    /// What the deserializer found instead, when available—e.g. ``string "2"``
+    /// or the name of an unknown enum variant.
+    pub fn unexpected(&self) -> Option<&'a str> {
+        self.unexpected
+    }
+
+    /// The name of the field the error refers to, when available.
+    ///
+    /// For missing, duplicate and unknown fields, it's the offending field.
+    /// Otherwise, it's the last field in the [`path`](Self::path).
+    pub fn field(&self) -> Option<&'a str> {
+        self.field
+    }
+
+    /// The input path at which the error occurred, when available.
+    pub fn path(&self) -> Option<&'a Path> {
+        self.path
+    }
+}
+
+/// Split `` `name`, expected .. `` into the name and the expectation.
+fn split_name(s: &str) -> (Option<&str>, Option<&str>) {
+    let Some((name, rest)) = s.strip_prefix('`').and_then(|s| s.split_once('`')) else {
+        return (None, None);
+    };
+    (Some(name), rest.strip_prefix(", expected "))
+}
+
+/// Returns `true` for strings like `1 column 11`.
+fn is_position(s: &str) -> bool {
+    let Some((line, column)) = s.split_once(" column ") else {
+        return false;
+    };
+    [line, column]
+        .iter()
+        .all(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Run `f`, formatting the messages of the errors reported along the way via `formatter`,
+/// for the given `locale`.
+///
+/// The formatter only applies to the current thread.
+///
+/// Check out the [module documentation](self) for an example.
+pub fn with_formatter<R>(
+    formatter: Arc<dyn MessageFormatter>,
+    locale: &str,
+    f: impl FnOnce() -> R,
+) -> R {
+    let previous = FORMATTER.replace(Some((formatter, locale.to_owned())));
+    // Restore the previous formatter even if `f` panics.
+    let _guard = RestoreGuard(Some(previous));
+    f()
+}
+
+struct RestoreGuard(Option<Option<(Arc<dyn MessageFormatter>, String)>>);
+
+impl Drop for RestoreGuard {
+    fn drop(&mut self) {
+        if let Some(previous) = self.0.take() {
+            let _ = FORMATTER.try_with(|c| c.replace(previous));
+        }
+    }
+}
+
+/// The formatter installed via [`with_formatter`], with its locale.
+#[cfg_attr(not(feature = "json"), allow(dead_code))]
+pub(crate) fn installed_formatter() -> Option<(Arc<dyn MessageFormatter>, String)> {
+    FORMATTER.with_borrow(|f| f.clone())
+}
+
+/// Format `details` via the installed formatter, if any.
+pub(crate) fn format_message(
+    details: String,
+    code: &str,
+    field: Option<&str>,
+    path: Option<&Path>,
+) -> String {
+    let Some((formatter, locale)) = installed_formatter() else {
+        return details;
+    };
+    let description = ErrorDescription::new(code, &details, field, path);
+    formatter.format(&description, &locale).unwrap_or(details)
+}
+
+thread_local! {
+    /// The formatter installed via [`with_formatter`], with its locale.
+    static FORMATTER: RefCell<Option<(Arc<dyn MessageFormatter>, String)>> = const { RefCell::new(None) };
+}
@@ -1,6 +1,7 @@
 use super::tracker::PathTracker;
 use super::wrap::{Wrap, WrapVariant};
 use super::Segment;
+use crate::reporter::ErrorReporter;
 use serde::de::{self, DeserializeSeed, Visitor};
 use std::fmt;
 
@@ -234,7 +235,8 @@ where
     where
         V: Visitor<'de>,
     {
-        self.de.deserialize_struct(name, fields, Wrap::new(visitor))
+        self.de
+            .deserialize_struct(name, fields, Wrap::with_fields(visitor, fields))
     }
 
     fn deserialize_enum<V>(
@@ -279,14 +281,16 @@ where
     where
         V: de::SeqAccess<'de>,
     {
-        self.delegate.visit_seq(SeqAccess::new(visitor))
+        self.delegate
+            .visit_seq(SeqAccess::new(visitor, self.fields))
     }
 
     fn visit_map<V>(self, visitor: V) -> Result<Self::Value, V::Error>
     where
         V: de::MapAccess<'de>,
     {
-        self.delegate.visit_map(MapAccess::new(visitor))
+        self.delegate
+            .visit_map(MapAccess::new(visitor, self.fields))
     }
 
     fn visit_enum<V>(self, visitor: V) -> Result<Self::Value, V::Error>
@@ -488,13 +492,16 @@ where
             .delegate
             .variant_seed(CaptureKey::new(seed, &mut variant));
 
-        let pop_path_segment_before_exit = variant.is_some();
-        if let Some(variant) = variant {
-            PathTracker::push(Segment::Enum { variant });
-        }
+        // The error is about the variant itself (e.g. an unknown variant), so it
+        // belongs to the enum's path. No need to push a segment that
+        // no `VariantAccess` would be around to pop.
         if outcome.is_err() {
             PathTracker::stash_current_path_for_error();
         }
+        let pop_path_segment_before_exit = variant.is_some() && outcome.is_ok();
+        if let (Some(variant), true) = (variant, pop_path_segment_before_exit) {
+            PathTracker::push(Segment::Enum { variant });
+        }
 
         outcome.map(move |(v, vis)| (v, WrapVariant::new(vis, pop_path_segment_before_exit)))
     }
@@ -545,7 +552,9 @@ where
     where
         V: Visitor<'de>,
     {
-        let outcome = self.delegate.struct_variant(fields, Wrap::new(visitor));
+        let outcome = self
+            .delegate
+            .struct_variant(fields, Wrap::with_fields(visitor, fields));
         if self.pop_path_segment_before_exit {
             PathTracker::pop();
         }
@@ -1108,14 +1117,22 @@ where
 }
 
 // Seq visitor that tracks the index of its elements.
+//
+// Non-self-describing formats (e.g. `postcard`) visit structs as sequences:
+// if the field names are known, they are used instead of the element index.
 struct SeqAccess<X> {
     delegate: X,
     index: usize,
+    fields: Option<&'static [&'static str]>,
 }
 
 impl<X> SeqAccess<X> {
-    fn new(delegate: X) -> Self {
-        SeqAccess { delegate, index: 0 }
+    fn new(delegate: X, fields: Option<&'static [&'static str]>) -> Self {
+        SeqAccess {
+            delegate,
+            index: 0,
+            fields,
+        }
     }
 }
 
@@ -1130,7 +1147,13 @@ where
     where
         T: DeserializeSeed<'de>,
     {
-        PathTracker::push(Segment::Seq { index: self.index });
+        let segment = match self.fields.and_then(|fields| fields.get(self.index)) {
+            Some(field) => Segment::Map {
+                key: (*field).to_owned(),
+            },
+            None => Segment::Seq { index: self.index },
+        };
+        PathTracker::push(segment);
         self.index += 1;
         let outcome = self.delegate.next_element_seed(TrackedSeed::new(seed));
         if outcome.is_err() {
@@ -1151,14 +1174,18 @@ struct MapAccess<X> {
     delegate: X,
     key: Option<String>,
     pop_path_segment_on_value: bool,
+    // The field names of the struct being visited, if any.
+    // Used to report unknown fields that are silently ignored.
+    fields: Option<&'static [&'static str]>,
 }
 
 impl<X> MapAccess<X> {
-    fn new(delegate: X) -> Self {
+    fn new(delegate: X, fields: Option<&'static [&'static str]>) -> Self {
         MapAccess {
             delegate,
             key: None,
             pop_path_segment_on_value: false,
+            fields,
         }
     }
 }
@@ -1176,8 +1203,18 @@ where
         let key = &mut self.key;
         let outcome = self.delegate.next_key_seed(CaptureKey::new(seed, key));
         if let Some(key) = key.take() {
+            // With `#[serde(deny_unknown_fields)]`, unknown fields are rejected while
+            // deserializing the key.
+            let is_unknown = outcome.is_ok()
+                && self
+                    .fields
+                    .is_some_and(|fields| !fields.contains(&key.as_str()));
+            let warning = is_unknown.then(|| format!("unknown field `{key}` was ignored"));
             PathTracker::push(Segment::Map { key });
             self.pop_path_segment_on_value = true;
+            if let Some(warning) = warning {
+                ErrorReporter::report_warning("unknown_field", warning);
+            }
         }
         if outcome.is_err() {
             PathTracker::stash_current_path_for_error();
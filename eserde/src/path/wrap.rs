@@ -1,6 +1,9 @@
 // Wrapper that attaches context to a `Visitor`, `SeqAccess` or `EnumAccess`.
 pub struct Wrap<X> {
     pub(crate) delegate: X,
+    // The field names of the struct being visited, if any.
+    // Used to label the elements of structs that are visited as sequences.
+    pub(crate) fields: Option<&'static [&'static str]>,
 }
 
 // Wrapper that attaches context to a `VariantAccess`.
@@ -11,7 +14,17 @@ pub struct WrapVariant<X> {
 
 impl<X> Wrap<X> {
     pub(crate) fn new(delegate: X) -> Self {
-        Wrap { delegate }
+        Wrap {
+            delegate,
+            fields: None,
+        }
+    }
+
+    pub(crate) fn with_fields(delegate: X, fields: &'static [&'static str]) -> Self {
+        Wrap {
+            delegate,
+            fields: Some(fields),
+        }
     }
 }
 
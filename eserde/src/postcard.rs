@@ -0,0 +1,127 @@
+//! Deserialize [postcard](https://github.com/jamesmunns/postcard) messages.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Reading {
+//!     sensor: String,
+//!     celsius: i16,
+//! }
+//!
+//! # fn main() {
+//! // A postcard message, as it may come over the wire from a microcontroller.
+//! let bytes = [0x04, b't', b'e', b'm', b'p', 0x2A];
+//!
+//! match eserde::postcard::from_bytes::<Reading>(&bytes) {
+//!     Ok(reading) => {
+//!         println!("{} reports {}°C", reading.sensor, reading.celsius);
+//!     }
+//!     Err(errors) => {
+//!         println!("Something went wrong during deserialization");
+//!         for error in errors.iter() {
+//!             println!("{error}")
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! # Non-self-describing formats
+//!
+//! postcard messages don't carry any information about their own structure:
+//! no field names, no type tags, no lengths for fixed-size values.
+//! The deserializer relies on the target type to know how many bytes to consume
+//! and how to interpret them.
+//!
+//! This has a few consequences:
+//!
+//! - **Only the first error is reported.** Once a value turns out to be invalid,
+//!   there is no way to know where the next one starts: carrying on would
+//!   interpret garbage. `eserde` switches to fail-fast mode
+//!   (see [`ErrorReporter::start_fail_fast_deserialization`]) and stops at the
+//!   first error.
+//! - **The error includes the byte offset** at which the error was detected, in
+//!   addition to the path of the value that was being deserialized—e.g.
+//!   `items[1].quantity: Found a varint that didn't terminate... at byte offset 15`.
+//!   It's exposed via [`DeserializationError::offset`], separately from the
+//!   [message](DeserializationError::message).
+//! - **Some `serde` features can't be used**, since they require the format to be
+//!   self-describing: `#[serde(flatten)]`, internally and adjacently tagged enums
+//!   and any type that calls `deserialize_any` (e.g. `serde_json::Value`).
+//!
+//! postcard discards the message attached to errors raised by `Deserialize`
+//! implementations (e.g. an unknown enum variant index); they surface as
+//! `Serde Deserialization Error`, but the path and byte offset still point
+//! at the offending value.
+//!
+//! # Implementation
+//!
+//! This module relies on [`postcard`](https://crates.io/crates/postcard) as
+//! the underlying deserializer.
+//!
+//! All deserializers in this module follow the same two-pass approach.
+//! Start by using `serde::Deserialize` to try to deserialize the target type.
+//! If it succeeds, return `Ok(value)`.
+//! If it fails, use `eserde::EDeserialize` to visit the input again, in
+//! fail-fast mode, to determine where the error occurred.
+//! The error is then returned in the `Err` variant.
+use crate::{
+    path, reporter::ErrorReporter, DeserializationError, DeserializationErrors, EDeserialize,
+};
+
+/// Deserialize an instance of type `T` from a postcard-encoded byte slice.
+///
+/// Any bytes left over after `T` has been deserialized are ignored,
+/// as in [`postcard::from_bytes`].
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct User {
+///     id: u32,
+///     name: String,
+/// }
+///
+/// # fn main() {
+/// let bytes = [0x07, 0x03, b'b', b'o', b'b'];
+///
+/// let u: User = eserde::postcard::from_bytes(&bytes).unwrap();
+/// println!("{:#?}", u);
+/// # }
+/// ```
+pub fn from_bytes<'a, T>(s: &'a [u8]) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let mut de = postcard::Deserializer::from_bytes(s);
+    let error = match T::deserialize(&mut de) {
+        Ok(v) => {
+            return Ok(v);
+        }
+        Err(e) => e,
+    };
+    let _guard = ErrorReporter::start_fail_fast_deserialization();
+
+    let mut de = postcard::Deserializer::from_bytes(s);
+    let errors = match T::deserialize_for_errors(path::Deserializer::new(&mut de)) {
+        Ok(_) => vec![],
+        Err(_) => ErrorReporter::take_errors(),
+    };
+    let offset = s.len() - de.finalize().map_or(0, |remaining| remaining.len());
+    let errors = if errors.is_empty() {
+        vec![DeserializationError::formatted(None, error.to_string())]
+    } else {
+        errors
+    };
+    let errors = errors
+        .into_iter()
+        .map(|e| DeserializationError {
+            offset: Some(offset),
+            ..e
+        })
+        .collect::<Vec<_>>();
+
+    Err(DeserializationErrors::from(errors))
+}
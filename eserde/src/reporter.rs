@@ -8,9 +8,18 @@
 //! a new data format.
 //! As an application developer, you should never need to work with the types in this
 //! module directly.
-use std::{cell::RefCell, fmt::Display};
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    fmt::Display,
+};
 
-use crate::{path::PathTracker, DeserializationError};
+use crate::{
+    errors::{error_code, error_field},
+    messages,
+    path::PathTracker,
+    DeserializationError, DeserializationWarning,
+};
 
 /// The entrypoint for reporting errors that occurred during [`EDeserialize::deserialize_for_errors`](crate::EDeserialize::deserialize_for_errors).
 ///
@@ -34,9 +43,56 @@ impl ErrorReporter {
     pub fn start_deserialization() -> ErrorReporterGuard {
         PathTracker::init();
         DESERIALIZATION_ERRORS.set(Some(Vec::new()));
+        DESERIALIZATION_WARNINGS.set(Some(Vec::new()));
+        MAX_ERRORS.set(None);
         ErrorReporterGuard
     }
 
+    #[must_use = "The guard returned by this method must be kept alive for the duration of the whole \
+        deserialization operation to ensure that errors are correctly reported."]
+    /// Kick-off a deserialization operation that stops after `max_errors` errors.
+    ///
+    /// It behaves like [`ErrorReporter::start_deserialization`], but it bounds the amount
+    /// of work performed on inputs with many errors (e.g. attacker-controlled payloads).
+    /// Once `max_errors` errors have been reported, the permissive types generated by
+    /// `#[derive(eserde::Deserialize)]` propagate errors instead of recovering from them,
+    /// and any further report is discarded.
+    ///
+    /// `max_errors` is clamped to be at least 1.
+    pub fn start_deserialization_with_max_errors(max_errors: usize) -> ErrorReporterGuard {
+        let guard = Self::start_deserialization();
+        MAX_ERRORS.set(Some(max_errors.max(1)));
+        guard
+    }
+
+    #[must_use = "The guard returned by this method must be kept alive for the duration of the whole \
+        deserialization operation to ensure that errors are correctly reported."]
+    /// Kick-off a deserialization operation that stops at the first error.
+    ///
+    /// It behaves like [`ErrorReporter::start_deserialization_with_max_errors`] with a
+    /// budget of a single error. It's meant for non-self-describing formats
+    /// (e.g. [`postcard`](crate::postcard)).
+    /// Those formats can't skip over an invalid value, since they rely on the
+    /// type being deserialized to know how many bytes to consume: any attempt at
+    /// carrying on after an error would read garbage.
+    pub fn start_fail_fast_deserialization() -> ErrorReporterGuard {
+        Self::start_deserialization_with_max_errors(1)
+    }
+
+    /// Returns `true` if the error budget of the current deserialization operation
+    /// has been exhausted.
+    /// See [`ErrorReporter::start_deserialization_with_max_errors`].
+    ///
+    /// Hand-written [`EDeserialize`](crate::EDeserialize) implementations should
+    /// propagate errors, rather than recovering from them, when this returns `true`.
+    pub fn should_abort() -> bool {
+        match MAX_ERRORS.get() {
+            Some(max_errors) => DESERIALIZATION_ERRORS
+                .with_borrow(|v| v.as_ref().is_some_and(|v| v.len() >= max_errors)),
+            None => false,
+        }
+    }
+
     /// Report an error that occurred during deserialization.
     ///
     /// # Panics
@@ -44,17 +100,44 @@ impl ErrorReporter {
     /// This method will panic if called outside of a deserialization operation.
     /// Check out [`ErrorReporter::start_deserialization`] for more information.
     pub fn report<E: Display>(e: E) {
+        let details = e.to_string();
+        // Inferred eagerly: custom messages may replace the details later on.
+        let code = error_code(&details);
+        Self::push_error(details, code.into());
+    }
+
+    /// Report an error with a custom [code](crate::DeserializationError::code)—e.g.
+    /// `password_too_short`.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if called outside of a deserialization operation.
+    /// Check out [`ErrorReporter::start_deserialization`] for more information.
+    pub fn report_with_code<E: Display>(code: impl Into<Cow<'static, str>>, e: E) {
+        Self::push_error(e.to_string(), code.into());
+    }
+
+    fn push_error(details: String, code: Cow<'static, str>) {
         let path = match PathTracker::unstash_current_path_for_error() {
             Some(p) => Some(p),
             None => PathTracker::current_path(),
         };
+        // Extracted before formatting, which may rewrite the details.
+        let field = error_field(&details).map(ToOwned::to_owned);
+        let details = messages::format_message(details, &code, field.as_deref(), path.as_ref());
         let error = DeserializationError {
-            path,
-            details: e.to_string(),
+            code: Some(code),
+            field,
+            ..DeserializationError::new(path, details)
         };
+        let max_errors = MAX_ERRORS.get();
         let success = DESERIALIZATION_ERRORS.with_borrow_mut(|v| {
             if let Some(v) = v {
-                v.push(error);
+                // Once the budget is exhausted, later reports are the last error
+                // bubbling up through the enclosing types.
+                if max_errors.is_none_or(|max_errors| v.len() < max_errors) {
+                    v.push(error);
+                }
                 true
             } else {
                 false
@@ -73,6 +156,31 @@ impl ErrorReporter {
         };
     }
 
+    /// Report a problem that doesn't prevent deserialization from succeeding—e.g. a value
+    /// that had to be clamped—with a [code](crate::DeserializationWarning::code) of your choice.
+    ///
+    /// Warnings are only surfaced by the functions that collect them, such as
+    /// [`json::from_str_with_warnings`](crate::json::from_str_with_warnings).
+    /// Unlike errors, warnings reported outside of a deserialization operation are
+    /// silently discarded.
+    pub fn report_warning<W: Display>(code: impl Into<Cow<'static, str>>, warning: W) {
+        let path = PathTracker::current_path();
+        let code = code.into();
+        let warning = warning.to_string();
+        let field = error_field(&warning).map(ToOwned::to_owned);
+        let details = messages::format_message(warning, &code, field.as_deref(), path.as_ref());
+        DESERIALIZATION_WARNINGS.with_borrow_mut(|v| {
+            if let Some(v) = v {
+                v.push(DeserializationWarning {
+                    path,
+                    details,
+                    origin: None,
+                    code,
+                });
+            }
+        });
+    }
+
     /// Retrieve all errors that occurred during deserialization up to this point.
     ///
     /// The buffer is cleared after this call—i.e. subsequent calls to this method will return
@@ -94,6 +202,34 @@ impl ErrorReporter {
                 need to take care of this initialization step yourself.")
     }
 
+    /// Retrieve all warnings reported during deserialization up to this point.
+    ///
+    /// The buffer is cleared after this call—i.e. subsequent calls to this method will return
+    /// an empty vector until new warnings are reported.
+    ///
+    /// # Panics
+    ///
+    /// This method will panic if called outside of a deserialization operation.
+    /// Check out [`ErrorReporter::start_deserialization`] for more information.
+    pub fn take_warnings() -> Vec<DeserializationWarning> {
+        DESERIALIZATION_WARNINGS.with_borrow_mut(|v| v.replace(Vec::new()))
+            .expect(
+                "Attempted to collect deserialization warnings outside of a deserialization operation. \
+                You can't call `ErrorReporter::take_warnings` without first calling `ErrorReporter::start_deserialization`. \
+                This initialization step is usually taken care of by the format-specific functions provided by `eserde`, \
+                such as `eserde::json::from_str_with_warnings`. If you're implementing your own deserialization logic, you \
+                need to take care of this initialization step yourself.")
+    }
+
+    /// Apply `f` to the errors reported after the first `n`.
+    pub(crate) fn rewrite_errors_since(n: usize, f: impl FnMut(&mut DeserializationError)) {
+        DESERIALIZATION_ERRORS.with_borrow_mut(|v| {
+            if let Some(v) = v {
+                v.iter_mut().skip(n).for_each(f);
+            }
+        });
+    }
+
     /// Retrieve the number of errors that occurred during deserialization up to this point.
     ///
     /// # Panics
@@ -131,6 +267,12 @@ impl Drop for ErrorReporterGuard {
                 *v = None;
             }
         });
+        let _ = DESERIALIZATION_WARNINGS.try_with(|c| {
+            if let Ok(mut v) = c.try_borrow_mut() {
+                *v = None;
+            }
+        });
+        let _ = MAX_ERRORS.try_with(|c| c.set(None));
         PathTracker::try_unset();
     }
 }
@@ -143,4 +285,12 @@ thread_local! {
     /// We use a thread-local since we are constrained by the signature of `serde`'s `Deserialize`
     /// trait, so we can't pass down a `&mut Vec<_>` to accumulate errors.
     static DESERIALIZATION_ERRORS: RefCell<Option<Vec<DeserializationError>>> = const { RefCell::new(None) };
+
+    /// Warnings reported during deserialization.
+    /// See [`ErrorReporter::report_warning`].
+    static DESERIALIZATION_WARNINGS: RefCell<Option<Vec<DeserializationWarning>>> = const { RefCell::new(None) };
+
+    /// The maximum number of errors to collect in the current deserialization operation.
+    /// See [`ErrorReporter::start_deserialization_with_max_errors`].
+    static MAX_ERRORS: Cell<Option<usize>> = const { Cell::new(None) };
 }
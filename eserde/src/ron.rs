@@ -0,0 +1,175 @@
+//! Deserialize [RON](https://github.com/ron-rs/ron) (Rusty Object Notation) documents.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Scene {
+//!     name: String,
+//!     shapes: Vec<Shape>,
+//! }
+//!
+//! #[derive(eserde::Deserialize, Debug)]
+//! enum Shape {
+//!     Circle { radius: f32 },
+//!     Rectangle(u32, u32),
+//! }
+//!
+//! # fn main() {
+//! // Some RON input data as a &str. Maybe this comes from an asset file.
+//! let data = r#"
+//!     Scene(
+//!         name: "intro",
+//!         shapes: [
+//!             Circle(radius: 1.5),
+//!             Rectangle(4, 3),
+//!         ],
+//!     )"#;
+//!
+//! match eserde::ron::from_str::<Scene>(data) {
+//!     Ok(scene) => {
+//!         println!("Loaded {} with {} shapes", scene.name, scene.shapes.len());
+//!     }
+//!     Err(errors) => {
+//!         println!("Something went wrong during deserialization");
+//!         for error in errors.iter() {
+//!             println!("{error}")
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! # Enums
+//!
+//! RON has native syntax for enum variants, e.g. `Circle(radius: 1.5)`.
+//! When an error occurs inside a variant, the variant name is included in the
+//! error path—e.g. `shapes[0].Circle.radius`.
+//!
+//! # Implementation
+//!
+//! This module relies on [`ron`](https://crates.io/crates/ron) as
+//! the underlying deserializer.
+//!
+//! All deserializers in this module follow the same two-pass approach.
+//! Start by using `serde::Deserialize` to try to deserialize the target type.
+//! If it succeeds, return `Ok(value)`.
+//! If it fails, use `eserde::EDeserialize` to visit the input again and
+//! accumulate as many deserialization errors as possible.
+//! The errors are then returned as a vector in the `Err` variant.
+use crate::{
+    impl_edeserialize_compat, path, reporter::ErrorReporter, scalars::ScalarsAsAny,
+    DeserializationError, DeserializationErrors, DeserializationWarning, EDeserialize,
+};
+
+/// Deserialize an instance of type `T` from a string of RON text.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct User {
+///     fingerprint: String,
+///     location: String,
+/// }
+///
+/// # fn main() {
+/// let r = r#"
+///     User(
+///         fingerprint: "0xF9BA143B95FF6D82",
+///         location: "Menlo Park, CA",
+///     )"#;
+///
+/// let u: User = eserde::ron::from_str(r).unwrap();
+/// println!("{:#?}", u);
+/// # }
+/// ```
+pub fn from_str<'a, T>(s: &'a str) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_bytes(s.as_bytes())
+}
+
+/// Deserialize an instance of type `T` from bytes of RON text.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct User {
+///     fingerprint: String,
+///     location: String,
+/// }
+///
+/// # fn main() {
+/// let r = br#"
+///     User(
+///         fingerprint: "0xF9BA143B95FF6D82",
+///         location: "Menlo Park, CA",
+///     )"#;
+///
+/// let u: User = eserde::ron::from_bytes(r).unwrap();
+/// println!("{:#?}", u);
+/// # }
+/// ```
+pub fn from_bytes<'a, T>(s: &'a [u8]) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let mut de = ron::Deserializer::from_bytes(s).map_err(|e| {
+        DeserializationErrors::from(vec![DeserializationError::formatted(None, e.to_string())])
+    })?;
+    let error = match T::deserialize(ScalarsAsAny(&mut de)).and_then(|v| de.end().map(|_| v)) {
+        Ok(v) => {
+            return Ok(v);
+        }
+        Err(e) => de.span_error(e),
+    };
+    let _guard = ErrorReporter::start_deserialization();
+
+    let mut de = ron::Deserializer::from_bytes(s)
+        .expect("The input was successfully parsed on the first pass");
+    let de = path::Deserializer::new(ScalarsAsAny(&mut de));
+
+    let errors = match T::deserialize_for_errors(de) {
+        Ok(_) => vec![],
+        Err(_) => ErrorReporter::take_errors(),
+    };
+    let errors = if errors.is_empty() {
+        vec![DeserializationError::formatted(None, error.to_string())]
+    } else {
+        errors
+    };
+
+    Err(DeserializationErrors::from(errors))
+}
+
+/// Deserialize an instance of type `T` from a string of RON text, collecting the
+/// [warnings](DeserializationWarning) reported along the way—e.g. unknown fields that
+/// were ignored.
+///
+/// It behaves like [`from_str`], but on success it visits the input a second time.
+/// Check out [`json::from_str_with_warnings`](crate::json::from_str_with_warnings)
+/// for more details.
+pub fn from_str_with_warnings<'a, T>(
+    s: &'a str,
+) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let value = from_str(s)?;
+    let _guard = ErrorReporter::start_deserialization();
+
+    let mut de = ron::Deserializer::from_str(s)
+        .expect("The input was successfully parsed on the first pass");
+    let de = path::Deserializer::new(ScalarsAsAny(&mut de));
+    // The input is known to be valid: we're only interested in warnings.
+    let _ = T::deserialize_for_errors(de);
+
+    Ok((value, ErrorReporter::take_warnings()))
+}
+
+impl_edeserialize_compat! {
+    ron::value::Value,
+}
@@ -0,0 +1,271 @@
+//! Adapters over third-party deserializers.
+use std::fmt;
+
+use serde::de::{self, DeserializeSeed, Visitor};
+
+/// Deserializer adapter that asks for any value when a scalar is requested.
+///
+/// The visitor then checks that the value has the expected type and range.
+/// This is useful for deserializers that don't consume invalid scalars (e.g. `ron`),
+/// thus preventing us from moving on to the next value, or that silently truncate
+/// out-of-range numbers (e.g. `json5` turns `80800` into `14264` for a `u16`).
+pub(crate) struct ScalarsAsAny<X>(pub(crate) X);
+
+macro_rules! forward_deserialize {
+    ($($method:ident($($arg:ident: $ty:ty),*) => $target:ident,)*) => {
+        $(
+            fn $method<V>(self, $($arg: $ty,)* visitor: V) -> Result<V::Value, D::Error>
+            where
+                V: Visitor<'de>,
+            {
+                self.0.$target($($arg,)* ScalarsAsAny(visitor))
+            }
+        )*
+    };
+}
+
+impl<'de, D> de::Deserializer<'de> for ScalarsAsAny<D>
+where
+    D: de::Deserializer<'de>,
+{
+    type Error = D::Error;
+
+    forward_deserialize! {
+        deserialize_any() => deserialize_any,
+        deserialize_i8() => deserialize_any,
+        deserialize_i16() => deserialize_any,
+        deserialize_i32() => deserialize_any,
+        deserialize_i64() => deserialize_any,
+        deserialize_i128() => deserialize_any,
+        deserialize_u8() => deserialize_any,
+        deserialize_u16() => deserialize_any,
+        deserialize_u32() => deserialize_any,
+        deserialize_u64() => deserialize_any,
+        deserialize_u128() => deserialize_any,
+        deserialize_f32() => deserialize_any,
+        deserialize_f64() => deserialize_any,
+        deserialize_bool() => deserialize_any,
+        deserialize_char() => deserialize_any,
+        deserialize_str() => deserialize_any,
+        deserialize_string() => deserialize_any,
+        deserialize_bytes() => deserialize_bytes,
+        deserialize_byte_buf() => deserialize_byte_buf,
+        deserialize_option() => deserialize_option,
+        deserialize_unit() => deserialize_unit,
+        deserialize_unit_struct(name: &'static str) => deserialize_unit_struct,
+        deserialize_newtype_struct(name: &'static str) => deserialize_newtype_struct,
+        deserialize_seq() => deserialize_seq,
+        deserialize_tuple(len: usize) => deserialize_tuple,
+        deserialize_tuple_struct(name: &'static str, len: usize) => deserialize_tuple_struct,
+        deserialize_map() => deserialize_map,
+        deserialize_struct(name: &'static str, fields: &'static [&'static str]) => deserialize_struct,
+        deserialize_enum(name: &'static str, variants: &'static [&'static str]) => deserialize_enum,
+        deserialize_identifier() => deserialize_identifier,
+        deserialize_ignored_any() => deserialize_ignored_any,
+    }
+}
+
+macro_rules! forward_visit {
+    ($($method:ident($ty:ty),)*) => {
+        $(
+            fn $method<E>(self, v: $ty) -> Result<Self::Value, E>
+            where
+                E: de::Error,
+            {
+                self.0.$method(v)
+            }
+        )*
+    };
+}
+
+impl<'de, V> Visitor<'de> for ScalarsAsAny<V>
+where
+    V: Visitor<'de>,
+{
+    type Value = V::Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        self.0.expecting(formatter)
+    }
+
+    forward_visit! {
+        visit_bool(bool),
+        visit_i8(i8),
+        visit_i16(i16),
+        visit_i32(i32),
+        visit_i64(i64),
+        visit_i128(i128),
+        visit_u8(u8),
+        visit_u16(u16),
+        visit_u32(u32),
+        visit_u64(u64),
+        visit_u128(u128),
+        visit_f32(f32),
+        visit_f64(f64),
+        visit_char(char),
+        visit_str(&str),
+        visit_borrowed_str(&'de str),
+        visit_string(String),
+        visit_bytes(&[u8]),
+        visit_borrowed_bytes(&'de [u8]),
+        visit_byte_buf(Vec<u8>),
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.0.visit_none()
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: de::Error,
+    {
+        self.0.visit_unit()
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.0.visit_some(ScalarsAsAny(deserializer))
+    }
+
+    fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.0.visit_newtype_struct(ScalarsAsAny(deserializer))
+    }
+
+    fn visit_seq<A>(self, seq: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::SeqAccess<'de>,
+    {
+        self.0.visit_seq(ScalarsAsAny(seq))
+    }
+
+    fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::MapAccess<'de>,
+    {
+        self.0.visit_map(ScalarsAsAny(map))
+    }
+
+    fn visit_enum<A>(self, data: A) -> Result<Self::Value, A::Error>
+    where
+        A: de::EnumAccess<'de>,
+    {
+        self.0.visit_enum(ScalarsAsAny(data))
+    }
+}
+
+impl<'de, S> DeserializeSeed<'de> for ScalarsAsAny<S>
+where
+    S: DeserializeSeed<'de>,
+{
+    type Value = S::Value;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: de::Deserializer<'de>,
+    {
+        self.0.deserialize(ScalarsAsAny(deserializer))
+    }
+}
+
+impl<'de, A> de::SeqAccess<'de> for ScalarsAsAny<A>
+where
+    A: de::SeqAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_element_seed<T>(&mut self, seed: T) -> Result<Option<T::Value>, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0.next_element_seed(ScalarsAsAny(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A> de::MapAccess<'de> for ScalarsAsAny<A>
+where
+    A: de::MapAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, A::Error>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        self.0.next_key_seed(ScalarsAsAny(seed))
+    }
+
+    fn next_value_seed<V>(&mut self, seed: V) -> Result<V::Value, A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        self.0.next_value_seed(ScalarsAsAny(seed))
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        self.0.size_hint()
+    }
+}
+
+impl<'de, A> de::EnumAccess<'de> for ScalarsAsAny<A>
+where
+    A: de::EnumAccess<'de>,
+{
+    type Error = A::Error;
+    type Variant = ScalarsAsAny<A::Variant>;
+
+    fn variant_seed<V>(self, seed: V) -> Result<(V::Value, Self::Variant), A::Error>
+    where
+        V: DeserializeSeed<'de>,
+    {
+        let (value, variant) = self.0.variant_seed(ScalarsAsAny(seed))?;
+        Ok((value, ScalarsAsAny(variant)))
+    }
+}
+
+impl<'de, A> de::VariantAccess<'de> for ScalarsAsAny<A>
+where
+    A: de::VariantAccess<'de>,
+{
+    type Error = A::Error;
+
+    fn unit_variant(self) -> Result<(), A::Error> {
+        self.0.unit_variant()
+    }
+
+    fn newtype_variant_seed<T>(self, seed: T) -> Result<T::Value, A::Error>
+    where
+        T: DeserializeSeed<'de>,
+    {
+        self.0.newtype_variant_seed(ScalarsAsAny(seed))
+    }
+
+    fn tuple_variant<V>(self, len: usize, visitor: V) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.tuple_variant(len, ScalarsAsAny(visitor))
+    }
+
+    fn struct_variant<V>(
+        self,
+        fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, A::Error>
+    where
+        V: Visitor<'de>,
+    {
+        self.0.struct_variant(fields, ScalarsAsAny(visitor))
+    }
+}
@@ -1,7 +1,8 @@
 //! Deserialize TOML documents.
 
 use crate::{
-    path, reporter::ErrorReporter, DeserializationError, DeserializationErrors, EDeserialize,
+    path, reporter::ErrorReporter, DeserializationError, DeserializationErrors,
+    DeserializationWarning, EDeserialize,
 };
 use toml;
 
@@ -47,13 +48,64 @@ where
         Err(_) => ErrorReporter::take_errors(),
     };
     let errors = if errors.is_empty() {
-        vec![DeserializationError {
-            path: None,
-            details: error.to_string(),
-        }]
+        vec![DeserializationError::formatted(None, error.to_string())]
     } else {
         errors
     };
 
     Err(DeserializationErrors::from(errors))
 }
+
+/// Deserialize an instance of type `T` from a string of TOML text, collecting the
+/// [warnings](DeserializationWarning) reported along the way—e.g. unknown keys that
+/// were ignored.
+///
+/// It behaves like [`from_str`], but on success it visits the input a second time.
+/// Check out [`json::from_str_with_warnings`](crate::json::from_str_with_warnings)
+/// for more details.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Config {
+///     server: Server,
+/// }
+///
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Server {
+///     port: u16,
+/// }
+///
+/// let (config, warnings) = eserde::toml::from_str_with_warnings::<Config>(
+///     r#"
+///     [server]
+///     port = 8080
+///     max_connections = 100
+///     "#,
+/// )
+/// .unwrap();
+/// assert_eq!(config.server.port, 8080);
+/// let unknown: Vec<_> = warnings
+///     .iter()
+///     .filter(|w| w.code() == "unknown_field")
+///     .map(|w| w.path().unwrap().to_string())
+///     .collect();
+/// assert_eq!(unknown, ["server.max_connections"]);
+/// ```
+pub fn from_str_with_warnings<T>(
+    s: &str,
+) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+where
+    T: for<'a> EDeserialize<'a>,
+{
+    let value = from_str(s)?;
+    let _guard = ErrorReporter::start_deserialization();
+
+    let de = toml::Deserializer::new(s);
+    let de = path::Deserializer::new(de);
+    // The input is known to be valid: we're only interested in warnings.
+    let _ = T::deserialize_for_errors(de);
+
+    Ok((value, ErrorReporter::take_warnings()))
+}
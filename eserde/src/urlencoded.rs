@@ -0,0 +1,164 @@
+//! Deserialize `application/x-www-form-urlencoded` data, e.g. query strings and HTML forms.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Search {
+//!     q: String,
+//!     page: u32,
+//!     tag: Vec<String>,
+//! }
+//!
+//! # fn main() {
+//! // A query string, e.g. from `/search?q=rust&page=2&tag=serde&tag=web`.
+//! let query = "q=rust&page=2&tag=serde&tag=web";
+//!
+//! match eserde::urlencoded::from_str::<Search>(query) {
+//!     Ok(search) => {
+//!         println!("Looking for {} on page {}", search.q, search.page);
+//!     }
+//!     Err(errors) => {
+//!         println!("Something went wrong during deserialization");
+//!         for error in errors.iter() {
+//!             println!("{error}")
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! # Repeated keys
+//!
+//! A key can appear multiple times in the input (e.g. `tag=a&tag=b`).
+//! Its values can be collected into a sequence type, such as `Vec<T>`.
+//! Each value is deserialized on its own, so an error is reported for
+//! every invalid value, with the position of the value in the path
+//! (e.g. `tag[1]`).
+//!
+//! # Implementation
+//!
+//! This module relies on [`serde_html_form`](https://crates.io/crates/serde_html_form) as
+//! the underlying deserializer.
+//!
+//! All deserializers in this module follow the same two-pass approach.
+//! Start by using `serde::Deserialize` to try to deserialize the target type.
+//! If it succeeds, return `Ok(value)`.
+//! If it fails, use `eserde::EDeserialize` to visit the input again and
+//! accumulate as many deserialization errors as possible.
+//! The errors are then returned as a vector in the `Err` variant.
+use crate::{
+    path, reporter::ErrorReporter, DeserializationError, DeserializationErrors,
+    DeserializationWarning, EDeserialize,
+};
+
+/// Deserialize an instance of type `T` from a URL-encoded string.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Pagination {
+///     page: u32,
+///     per_page: u32,
+/// }
+///
+/// # fn main() {
+/// let query = "page=2&per_page=50";
+///
+/// let p: Pagination = eserde::urlencoded::from_str(query).unwrap();
+/// println!("{:#?}", p);
+/// # }
+/// ```
+pub fn from_str<'a, T>(s: &'a str) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_bytes(s.as_bytes())
+}
+
+/// Deserialize an instance of type `T` from bytes of URL-encoded data.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct Pagination {
+///     page: u32,
+///     per_page: u32,
+/// }
+///
+/// # fn main() {
+/// let body = b"page=2&per_page=50";
+///
+/// let p: Pagination = eserde::urlencoded::from_bytes(body).unwrap();
+/// println!("{:#?}", p);
+/// # }
+/// ```
+pub fn from_bytes<'a, T>(s: &'a [u8]) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let de = serde_html_form::Deserializer::from_bytes(s);
+    let error = match T::deserialize(de) {
+        Ok(v) => {
+            return Ok(v);
+        }
+        Err(e) => e,
+    };
+    let _guard = ErrorReporter::start_deserialization();
+
+    let de = serde_html_form::Deserializer::from_bytes(s);
+    let de = path::Deserializer::new(de);
+
+    let errors = match T::deserialize_for_errors(de) {
+        Ok(_) => vec![],
+        Err(_) => ErrorReporter::take_errors(),
+    };
+    let errors = if errors.is_empty() {
+        vec![DeserializationError::formatted(None, error.to_string())]
+    } else {
+        errors
+    };
+
+    Err(DeserializationErrors::from(errors))
+}
+
+/// Deserialize an instance of type `T` from a URL-encoded string, collecting the
+/// [warnings](DeserializationWarning) reported along the way—e.g. unknown fields that
+/// were ignored.
+///
+/// It behaves like [`from_str`], but on success it visits the input a second time.
+/// Check out [`json::from_str_with_warnings`](crate::json::from_str_with_warnings)
+/// for more details.
+pub fn from_str_with_warnings<'a, T>(
+    s: &'a str,
+) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    from_bytes_with_warnings(s.as_bytes())
+}
+
+/// Deserialize an instance of type `T` from bytes of URL-encoded data, collecting the
+/// [warnings](DeserializationWarning) reported along the way—e.g. unknown fields that
+/// were ignored.
+///
+/// It behaves like [`from_bytes`], but on success it visits the input a second time.
+/// Check out [`json::from_str_with_warnings`](crate::json::from_str_with_warnings)
+/// for more details.
+pub fn from_bytes_with_warnings<'a, T>(
+    s: &'a [u8],
+) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let value = from_bytes(s)?;
+    let _guard = ErrorReporter::start_deserialization();
+
+    let de = path::Deserializer::new(serde_html_form::Deserializer::from_bytes(s));
+    // The input is known to be valid: we're only interested in warnings.
+    let _ = T::deserialize_for_errors(de);
+
+    Ok((value, ErrorReporter::take_warnings()))
+}
@@ -0,0 +1,193 @@
+//! Deserialize XML documents.
+//!
+//! # Example
+//!
+//! ```rust
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Order {
+//!     #[serde(rename = "@id")]
+//!     id: u64,
+//!     item: Vec<Item>,
+//! }
+//!
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Item {
+//!     #[serde(rename = "@sku")]
+//!     sku: String,
+//!     quantity: u32,
+//! }
+//!
+//! # fn main() {
+//! // Some XML input data as a &str. Maybe this comes from a partner system.
+//! let data = r#"
+//!     <order id="42">
+//!         <item sku="A-1"><quantity>2</quantity></item>
+//!         <item sku="B-7"><quantity>1</quantity></item>
+//!     </order>"#;
+//!
+//! match eserde::xml::from_str::<Order>(data) {
+//!     Ok(order) => {
+//!         println!("Order {} has {} items", order.id, order.item.len());
+//!     }
+//!     Err(errors) => {
+//!         println!("Something went wrong during deserialization");
+//!         for error in errors.iter() {
+//!             println!("{error}")
+//!         }
+//!     }
+//! }
+//! # }
+//! ```
+//!
+//! # Paths
+//!
+//! Error paths start with the name of the root element, e.g. `order.item[2].quantity`.
+//!
+//! Attributes are mapped to fields whose name starts with `@`
+//! (e.g. `#[serde(rename = "@sku")]`), so they're rendered distinctly
+//! from child elements in error paths—e.g. `order.item[2].@sku`.\
+//! The special `$text` and `$value` fields, used to capture the text content of an element,
+//! are omitted from error paths: errors are reported against the element itself.
+//!
+//! Check out [`quick-xml`'s documentation](https://docs.rs/quick-xml/latest/quick_xml/de/index.html)
+//! for more details on how XML is mapped to Rust types.
+//!
+//! # Implementation
+//!
+//! This module relies on [`quick-xml`](https://crates.io/crates/quick-xml) as
+//! the underlying deserializer.
+//!
+//! All deserializers in this module follow the same two-pass approach.
+//! Start by using `serde::Deserialize` to try to deserialize the target type.
+//! If it succeeds, return `Ok(value)`.
+//! If it fails, use `eserde::EDeserialize` to visit the input again and
+//! accumulate as many deserialization errors as possible.
+//! The errors are then returned as a vector in the `Err` variant.
+use quick_xml::events::Event;
+
+use crate::{
+    path::{self, Path, Segment},
+    reporter::ErrorReporter,
+    DeserializationError, DeserializationErrors, DeserializationWarning, EDeserialize,
+};
+
+/// Deserialize an instance of type `T` from a string of XML text.
+///
+/// # Example
+///
+/// ```rust
+/// #[derive(eserde::Deserialize, Debug)]
+/// struct User {
+///     #[serde(rename = "@fingerprint")]
+///     fingerprint: String,
+///     location: String,
+/// }
+///
+/// # fn main() {
+/// let x = r#"
+///     <user fingerprint="0xF9BA143B95FF6D82">
+///         <location>Menlo Park, CA</location>
+///     </user>"#;
+///
+/// let u: User = eserde::xml::from_str(x).unwrap();
+/// println!("{:#?}", u);
+/// # }
+/// ```
+pub fn from_str<'a, T>(s: &'a str) -> Result<T, DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let mut de = quick_xml::de::Deserializer::from_str(s);
+    let error = match T::deserialize(&mut de) {
+        Ok(v) => {
+            return Ok(v);
+        }
+        Err(e) => e,
+    };
+    let _guard = ErrorReporter::start_deserialization();
+
+    let mut de = quick_xml::de::Deserializer::from_str(s);
+    let de = path::Deserializer::new(&mut de);
+
+    let errors = match T::deserialize_for_errors(de) {
+        Ok(_) => vec![],
+        Err(_) => ErrorReporter::take_errors(),
+    };
+    let errors = if errors.is_empty() {
+        vec![DeserializationError::formatted(None, error.to_string())]
+    } else {
+        let root = root_element(s);
+        errors
+            .into_iter()
+            .map(|mut e| {
+                e.path = xml_path(root.as_deref(), e.path.take());
+                e
+            })
+            .collect()
+    };
+
+    Err(DeserializationErrors::from(errors))
+}
+
+/// Deserialize an instance of type `T` from a string of XML text, collecting the
+/// [warnings](DeserializationWarning) reported along the way—e.g. unknown elements
+/// or attributes that were ignored.
+///
+/// It behaves like [`from_str`], but on success it visits the input a second time.
+/// Check out [`json::from_str_with_warnings`](crate::json::from_str_with_warnings)
+/// for more details.
+/// Warning paths start with the name of the root element, like error paths.
+pub fn from_str_with_warnings<'a, T>(
+    s: &'a str,
+) -> Result<(T, Vec<DeserializationWarning>), DeserializationErrors>
+where
+    T: EDeserialize<'a>,
+{
+    let value = from_str(s)?;
+    let _guard = ErrorReporter::start_deserialization();
+
+    let mut de = quick_xml::de::Deserializer::from_str(s);
+    let de = path::Deserializer::new(&mut de);
+    // The input is known to be valid: we're only interested in warnings.
+    let _ = T::deserialize_for_errors(de);
+
+    let root = root_element(s);
+    let warnings = ErrorReporter::take_warnings()
+        .into_iter()
+        .map(|mut w| {
+            w.path = xml_path(root.as_deref(), w.path.take());
+            w
+        })
+        .collect();
+    Ok((value, warnings))
+}
+
+/// The name of the root element, if the input contains one.
+fn root_element(s: &str) -> Option<String> {
+    let mut reader = quick_xml::Reader::from_str(s);
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(e) | Event::Empty(e) => {
+                return Some(String::from_utf8_lossy(e.name().as_ref()).into_owned());
+            }
+            Event::Eof => return None,
+            _ => {}
+        }
+    }
+}
+
+/// Prepend the name of the root element to the path and
+/// drop the `$text`/`$value` segments.
+fn xml_path(root: Option<&str>, path: Option<Path>) -> Option<Path> {
+    let segments = root
+        .map(|root| Segment::Map {
+            key: root.to_owned(),
+        })
+        .into_iter()
+        .chain(path.iter().flat_map(|path| path.iter().cloned()))
+        .filter(
+            |segment| !matches!(segment, Segment::Map { key } if key == "$text" || key == "$value"),
+        )
+        .collect::<Vec<_>>();
+    (!segments.is_empty()).then(|| Path::from(segments))
+}
@@ -0,0 +1,104 @@
+use eserde::reporter::ErrorReporter;
+
+#[derive(eserde::Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+#[allow(dead_code)]
+struct Account {
+    id: u32,
+    #[eserde(code = "invalid_age")]
+    age: u8,
+    email: String,
+    role: Role,
+    tags: [String; 2],
+    #[eserde(with = "password")]
+    password: String,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+enum Role {
+    Admin,
+    Member,
+}
+
+/// At least 8 characters, with at least one digit.
+mod password {
+    use super::*;
+    use serde::{de::Error, Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        if s.len() < 8 || !s.chars().any(|c| c.is_ascii_digit()) {
+            return Err(D::Error::custom("the password is too weak"));
+        }
+        Ok(s)
+    }
+
+    pub fn deserialize_for_errors<'de, D: Deserializer<'de>>(deserializer: D) -> Result<(), ()> {
+        let s = String::deserialize(deserializer).map_err(ErrorReporter::report)?;
+        let mut result = Ok(());
+        if s.len() < 8 {
+            ErrorReporter::report_with_code("password_too_short", "the password is too short");
+            result = Err(());
+        }
+        if !s.chars().any(|c| c.is_ascii_digit()) {
+            ErrorReporter::report_with_code("password_no_digit", "the password has no digits");
+            result = Err(());
+        }
+        result
+    }
+}
+
+fn codes(input: &str) -> Vec<String> {
+    let errors = eserde::json::from_str::<Account>(input).unwrap_err();
+    errors
+        .iter()
+        .map(|e| {
+            format!(
+                "{}: {}",
+                e.path()
+                    .filter(|p| !p.is_empty())
+                    .map(|p| p.to_string())
+                    .unwrap_or_default(),
+                e.code()
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_builtin_codes() {
+    insta::assert_debug_snapshot!(codes(
+        r#"{"id": 5000000000, "email": 7, "role": "Owner", "tags": ["a"], "extra": 1, "password": "s3cret-pass"}"#
+    ), @r#"
+    [
+        "id: out_of_range",
+        "email: invalid_type",
+        "role: unknown_variant",
+        "tags: invalid_length",
+        "extra: unknown_field",
+    ]
+    "#);
+}
+
+#[test]
+fn test_custom_codes() {
+    insta::assert_debug_snapshot!(codes(
+        r#"{"id": 1, "age": -1, "email": "a@b.c", "role": "Admin", "tags": ["a", "b"], "password": "short"}"#
+    ), @r#"
+    [
+        "age: invalid_age",
+        "password: password_too_short",
+        "password: password_no_digit",
+    ]
+    "#);
+}
+
+#[test]
+fn test_syntax_error() {
+    insta::assert_debug_snapshot!(codes(r#"{"id": 1,"#), @r#"
+    [
+        ": invalid",
+    ]
+    "#);
+}
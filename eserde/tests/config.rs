@@ -0,0 +1,113 @@
+#![cfg(all(feature = "config", feature = "json", feature = "toml"))]
+use eserde::config::ConfigBuilder;
+use eserde::env::Options;
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Config {
+    port: u16,
+    #[serde(default)]
+    hosts: Vec<String>,
+    db: Db,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Db {
+    url: String,
+    pool_size: u32,
+    #[serde(default)]
+    timeout: Option<u64>,
+}
+
+const DEFAULTS: &str = r#"
+port = 8080
+hosts = ["localhost"]
+
+[db]
+url = "postgres://localhost/app"
+pool_size = 10
+"#;
+
+fn options() -> Options {
+    Options::new().prefix("APP_").separator("__")
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        Config {
+            port: 9000,
+            hosts: vec!["a.example.com".to_owned(), "b.example.com".to_owned()],
+            db: Db {
+                url: "postgres://db.example.com/app".to_owned(),
+                pool_size: 20,
+                timeout: Some(5),
+            },
+        },
+        ConfigBuilder::new()
+            .add_toml("defaults.toml", DEFAULTS)
+            .add_json(
+                "production.json",
+                r#"{ "db": { "url": "postgres://db.example.com/app", "timeout": 5 } }"#
+            )
+            .add_env_vars(
+                &options(),
+                [
+                    ("APP_HOSTS", "a.example.com,b.example.com"),
+                    ("APP_DB__POOL_SIZE", "20"),
+                ]
+            )
+            .set_override("port", "9000")
+            .deserialize()
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = ConfigBuilder::new()
+        .add_toml("defaults.toml", DEFAULTS)
+        .add_json(
+            "production.json",
+            r#"{ "hosts": ["a.example.com", 42], "db": { "timeout": -5 } }"#,
+        )
+        .add_env_vars(&options(), [("APP_DB__POOL_SIZE", "ten")])
+        .set_override("port", "http")
+        .deserialize::<Config>();
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - db.pool_size: invalid value: string "ten", expected u32 (from env)
+    - db.timeout: invalid value: integer `-5`, expected u64 (from production.json)
+    - hosts[1]: invalid type: integer `42`, expected a string (from production.json)
+    - port: invalid value: string "http", expected u16 (from override)
+    "#);
+}
+
+#[test]
+fn test_fail_missing() {
+    let x = ConfigBuilder::new()
+        .add_toml("defaults.toml", "[db]\nurl = \"postgres://localhost/app\"")
+        .deserialize::<Config>();
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - db: missing field `pool_size` (from defaults.toml)
+    - missing field `port`
+    "#);
+}
+
+#[test]
+fn test_fail_unparsable_source() {
+    let x = ConfigBuilder::new()
+        .add_toml("defaults.toml", DEFAULTS)
+        .add_json("production.json", r#"{ "port": 80 "#)
+        .deserialize::<Config>();
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - EOF while parsing an object at line 1 column 13 (from production.json)
+    "#);
+}
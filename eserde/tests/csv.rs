@@ -0,0 +1,113 @@
+#![cfg(feature = "csv")]
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Customer {
+    name: String,
+    age: u8,
+    #[serde(default)]
+    email: Option<String>,
+    tier: Tier,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+enum Tier {
+    Free,
+    Pro,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Row(String, u8);
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        vec![
+            Customer {
+                name: "Jane".to_owned(),
+                age: 34,
+                email: Some("jane@example.com".to_owned()),
+                tier: Tier::Pro,
+            },
+            Customer {
+                name: "John, Jr.".to_owned(),
+                age: 27,
+                email: None,
+                tier: Tier::Free,
+            },
+        ],
+        eserde::csv::from_str(
+            "name,age,email,tier\nJane,34,jane@example.com,Pro\n\"John, Jr.\",27,,Free\n"
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_happy_no_headers() {
+    let reader = csv::ReaderBuilder::new()
+        .delimiter(b';')
+        .has_headers(false)
+        .from_reader("Jane;34\nJohn;27\n".as_bytes());
+    assert_eq!(
+        vec![Row("Jane".to_owned(), 34), Row("John".to_owned(), 27)],
+        eserde::csv::from_reader::<Row, _>(reader).unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::csv::from_str::<Customer>(
+        "name,age,email,tier\n\
+         Jane,34,jane@example.com,Pro\n\
+         John,abc,,Gold\n\
+         Mary,300,mary@example.com,Free\n\
+         Bob,40\n",
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - row 3, column `age`: invalid value: string "abc", expected u8
+    - row 3, column `tier`: unknown variant `Gold`, expected `Free` or `Pro`
+    - row 4, column `age`: invalid value: string "300", expected u8
+    - row 5: CSV error: record 4 (line: 5, byte: 95): found record with 2 fields, but the previous record has 4 fields
+    "#);
+
+    let cells = errs
+        .iter()
+        .map(|e| (e.row(), e.column(), e.column_index(), e.cell()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        cells,
+        vec![
+            (3, Some("age"), Some(1), Some("abc")),
+            (3, Some("tier"), Some(3), Some("Gold")),
+            (4, Some("age"), Some(1), Some("300")),
+            (5, None, None, None),
+        ]
+    );
+}
+
+#[test]
+fn test_fail_missing_column() {
+    let x = eserde::csv::from_str::<Customer>("name,email,tier\nJane,jane@example.com,Pro\n");
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - row 2: missing field `age`
+    "#);
+}
+
+#[test]
+fn test_fail_no_headers() {
+    let reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .from_reader("Jane,34\nJohn,-1\n".as_bytes());
+    let x = eserde::csv::from_reader::<Row, _>(reader);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - row 2, column 2: invalid value: string "-1", expected u8
+    "#);
+}
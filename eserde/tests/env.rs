@@ -0,0 +1,143 @@
+#![cfg(feature = "env")]
+use eserde::env::Options;
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Config {
+    database_url: String,
+    port: u16,
+    #[serde(default)]
+    debug: bool,
+    #[serde(default)]
+    hosts: Vec<String>,
+    db: Db,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Db {
+    pool_size: u32,
+    timeout: Option<u64>,
+}
+
+fn options() -> Options {
+    Options::new().prefix("APP_").separator("__")
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        Config {
+            database_url: "postgres://localhost/app".to_owned(),
+            port: 8080,
+            debug: true,
+            hosts: vec!["a.example.com".to_owned(), "b.example.com".to_owned()],
+            db: Db {
+                pool_size: 10,
+                timeout: None,
+            },
+        },
+        options()
+            .from_iter([
+                ("APP_DATABASE_URL", "postgres://localhost/app"),
+                ("APP_PORT", "8080"),
+                ("APP_DEBUG", "true"),
+                ("APP_HOSTS", "a.example.com,b.example.com"),
+                ("APP_DB__POOL_SIZE", "10"),
+                ("OTHER_PORT", "not a number"),
+            ])
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = options().from_iter::<Config, _, _, _>([
+        ("APP_PORT", "80800"),
+        ("APP_DEBUG", "yes"),
+        ("APP_DB__POOL_SIZE", "ten"),
+        ("APP_DB__TIMEOUT", "-5"),
+    ]);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - APP_DB__POOL_SIZE: invalid value: string "ten", expected u32
+    - APP_DB__TIMEOUT: invalid value: string "-5", expected u64
+    - APP_DEBUG: invalid value: string "yes", expected a boolean
+    - APP_PORT: invalid value: string "80800", expected u16
+    - APP_DATABASE_URL: missing environment variable
+    "#);
+}
+
+#[test]
+fn test_fail_nested_missing() {
+    let x = options().from_iter::<Config, _, _, _>([
+        ("APP_DATABASE_URL", "postgres://localhost/app"),
+        ("APP_PORT", "8080"),
+        ("APP_HOSTS", "a.example.com"),
+        ("APP_DB__TIMEOUT", "5"),
+    ]);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - APP_DB__POOL_SIZE: missing environment variable
+    "#);
+}
+
+#[test]
+fn test_no_prefix() {
+    #[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+    struct Flat {
+        port: u16,
+        ids: Vec<u8>,
+    }
+
+    let x = eserde::env::from_iter::<Flat, _, _, _>([("PORT", "http"), ("IDS", "1,2,x,4,300")]);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - IDS[2]: invalid value: string "x", expected u8
+    - IDS[4]: invalid value: string "300", expected u8
+    - PORT: invalid value: string "http", expected u16
+    "#);
+}
+
+#[test]
+fn test_fail_missing_section() {
+    #[derive(eserde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct App {
+        #[eserde(missing = "Please provide a database URL")]
+        database_url: String,
+        db: Db,
+        cache: std::collections::BTreeMap<String, String>,
+    }
+
+    let x = options().from_iter::<App, _, _, _>([("APP_PORT", "8080")]);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - APP_DATABASE_URL: Please provide a database URL
+    - APP_DB__: missing environment variables
+    - APP_CACHE__: missing environment variables
+    "#);
+}
+
+#[test]
+fn test_fail_missing_with_formatter() {
+    let formatter = |error: &eserde::messages::ErrorDescription<'_>, _: &str| {
+        Some(format!("`{}` è obbligatorio", error.field()?))
+    };
+    let x = eserde::messages::with_formatter(std::sync::Arc::new(formatter), "it", || {
+        options().from_iter::<Config, _, _, _>([("APP_PORT", "8080"), ("APP_DB__TIMEOUT", "5")])
+    });
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - APP_DB__POOL_SIZE: `pool_size` è obbligatorio
+    - APP_DATABASE_URL: `database_url` è obbligatorio
+    "#);
+}
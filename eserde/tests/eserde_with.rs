@@ -0,0 +1,174 @@
+use std::fmt;
+
+use eserde::{reporter::ErrorReporter, EDeserialize};
+use serde::de::{value::SeqAccessDeserializer, Deserialize, Deserializer, SeqAccess, Visitor};
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Server {
+    host: String,
+    #[eserde(with = "ports")]
+    ports: Vec<u16>,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+enum Listener {
+    Tcp {
+        #[eserde(with = "ports")]
+        ports: Vec<u16>,
+    },
+    Unix(String),
+}
+
+/// Ports, either as a list (`[80, 443]`) or as a comma-separated string (`"80,443"`).
+mod ports {
+    use super::*;
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Vec<u16>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PortsVisitor;
+
+        impl<'de> Visitor<'de> for PortsVisitor {
+            type Value = Vec<u16>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a list of ports or a comma-separated string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.split(',')
+                    .map(|part| {
+                        part.trim()
+                            .parse()
+                            .map_err(|_| E::custom(format!("invalid port `{part}`")))
+                    })
+                    .collect()
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                Vec::deserialize(SeqAccessDeserializer::new(seq))
+            }
+        }
+
+        deserializer.deserialize_any(PortsVisitor)
+    }
+
+    pub fn deserialize_for_errors<'de, D>(deserializer: D) -> Result<(), ()>
+    where
+        D: Deserializer<'de>,
+    {
+        struct PortsErrorsVisitor;
+
+        impl<'de> Visitor<'de> for PortsErrorsVisitor {
+            type Value = Result<(), ()>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a list of ports or a comma-separated string")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                // Report every invalid port, not just the first one.
+                let mut result = Ok(());
+                for part in v.split(',').map(str::trim) {
+                    if part.parse::<u16>().is_err() {
+                        ErrorReporter::report(format!("invalid port `{part}`"));
+                        result = Err(());
+                    }
+                }
+                Ok(result)
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, seq: A) -> Result<Self::Value, A::Error> {
+                Ok(Vec::<u16>::deserialize_for_errors(
+                    SeqAccessDeserializer::new(seq),
+                ))
+            }
+        }
+
+        deserializer
+            .deserialize_any(PortsErrorsVisitor)
+            .map_err(ErrorReporter::report)?
+    }
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        vec![
+            Server {
+                host: "a".to_owned(),
+                ports: vec![80, 443],
+            },
+            Server {
+                host: "b".to_owned(),
+                ports: vec![8080, 8081],
+            },
+        ],
+        eserde::json::from_str::<Vec<Server>>(
+            r#"[
+                {"host": "a", "ports": [80, 443]},
+                {"host": "b", "ports": "8080, 8081"}
+            ]"#
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        Listener::Tcp { ports: vec![22] },
+        eserde::json::from_str::<Listener>(r#"{"Tcp": {"ports": "22"}}"#).unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::json::from_str::<Vec<Server>>(
+        r#"[
+            {"host": "a", "ports": [80, -1, 70000]},
+            {"host": "b", "ports": "http, 8080, https"},
+            {"host": 3, "ports": true},
+            {"host": "d"}
+        ]"#,
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - [0].ports[1]: invalid value: integer `-1`, expected u16 at line 2 column 42
+    - [0].ports[2]: invalid value: integer `70000`, expected u16 at line 2 column 49
+    - [1].ports: invalid port `http`
+    - [1].ports: invalid port `https`
+    - [2].host: invalid type: integer `3`, expected a string at line 4 column 22
+    - [2].ports: invalid type: boolean `true`, expected a list of ports or a comma-separated string at line 4 column 37
+    - [3]: missing field `ports`
+    "#);
+}
+
+#[test]
+fn test_fail_enum() {
+    let x = eserde::json::from_str::<Listener>(r#"{"Tcp": {"ports": "22,ssh"}}"#);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - Tcp.ports: invalid port `ssh`
+    "#);
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Firewall {
+    #[eserde(with = "ports")]
+    r#in: Vec<u16>,
+}
+
+#[test]
+fn test_raw_identifier() {
+    assert_eq!(
+        Firewall { r#in: vec![22, 80] },
+        eserde::json::from_str::<Firewall>(r#"{"in": "22,80"}"#).unwrap()
+    );
+    let errs = eserde::json::from_str::<Firewall>(r#"{"in": "22,ssh"}"#).unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - in: invalid port `ssh`
+    "#);
+}
@@ -0,0 +1,126 @@
+//! Hand-written `EDeserialize` implementations, built with `eserde::de`.
+use eserde::{
+    de::{deserialize_struct_for_errors, EDeserializeStruct},
+    EDeserialize,
+};
+use serde::{Deserialize, Deserializer};
+
+#[derive(Debug, PartialEq, Eq)]
+struct Rgb {
+    red: u8,
+    green: u8,
+    blue: u8,
+}
+
+impl<'de> Deserialize<'de> for Rgb {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(rename = "Rgb", deny_unknown_fields)]
+        struct Raw {
+            r: u8,
+            g: u8,
+            #[serde(default)]
+            b: u8,
+        }
+        let raw = Raw::deserialize(deserializer)?;
+        Ok(Rgb {
+            red: raw.r,
+            green: raw.g,
+            blue: raw.b,
+        })
+    }
+}
+
+impl<'de> EDeserialize<'de> for Rgb {
+    fn deserialize_for_errors<D>(deserializer: D) -> Result<(), ()>
+    where
+        D: Deserializer<'de>,
+    {
+        deserialize_struct_for_errors::<Self, D>(deserializer)
+    }
+}
+
+impl<'de> EDeserializeStruct<'de> for Rgb {
+    const NAME: &'static str = "Rgb";
+    const FIELDS: &'static [&'static str] = &["r", "g", "b"];
+    const DENY_UNKNOWN_FIELDS: bool = true;
+
+    fn is_required(field: &'static str) -> bool {
+        field != "b"
+    }
+
+    fn field_for_errors<D>(_field: &'static str, deserializer: D) -> Result<(), ()>
+    where
+        D: Deserializer<'de>,
+    {
+        u8::deserialize_for_errors(deserializer)
+    }
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Theme {
+    name: String,
+    colors: Vec<Rgb>,
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        Theme {
+            name: "dusk".to_owned(),
+            colors: vec![
+                Rgb {
+                    red: 1,
+                    green: 2,
+                    blue: 3
+                },
+                Rgb {
+                    red: 4,
+                    green: 5,
+                    blue: 0
+                },
+            ],
+        },
+        eserde::json::from_str::<Theme>(
+            r#"{"name": "dusk", "colors": [{"r": 1, "g": 2, "b": 3}, {"g": 5, "r": 4}]}"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::json::from_str::<Theme>(
+        r#"{
+            "name": "dusk",
+            "colors": [
+                {"r": 256, "g": -1, "b": 3},
+                {"r": 1, "r": 2, "alpha": 0.5},
+                "white"
+            ]
+        }"#,
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - colors[0].r: invalid value: integer `256`, expected u8 at line 4 column 25
+    - colors[0].g: invalid value: integer `-1`, expected u8 at line 4 column 34
+    - colors[1].r: duplicate field `r`
+    - colors[1].alpha: unknown field `alpha`, expected one of `r`, `g`, `b`
+    - colors[1]: missing field `g`
+    - colors[2]: invalid type: string "white", expected struct Rgb at line 6 column 23
+    "#);
+}
+
+#[test]
+fn test_fail_seq() {
+    // Formats that aren't self-describing visit structs as sequences.
+    let x = eserde::postcard::from_bytes::<Rgb>(&[7]);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - g: Hit the end of buffer, expected more data at byte offset 1
+    "#);
+}
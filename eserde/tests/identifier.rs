@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Config {
+    retries: u8,
+    /// Settings, keyed by a `field_identifier` enum.
+    settings: BTreeMap<Setting, String>,
+    /// A list of `variant_identifier` enums.
+    levels: Vec<Level>,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(field_identifier, rename_all = "kebab-case")]
+enum Setting {
+    LogFile,
+    #[serde(alias = "colour")]
+    Color,
+    Timeout,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+#[serde(variant_identifier)]
+enum Level {
+    Debug,
+    Info,
+    Warn,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum Key<'a> {
+    Name,
+    Other(&'a str),
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        Config {
+            retries: 3,
+            settings: BTreeMap::from([
+                (Setting::LogFile, "out.log".to_owned()),
+                (Setting::Color, "auto".to_owned()),
+            ]),
+            levels: vec![Level::Info, Level::Warn],
+        },
+        eserde::json::from_str::<Config>(
+            r#"{
+                "retries": 3,
+                "settings": {"log-file": "out.log", "colour": "auto"},
+                "levels": ["Info", "Warn"]
+            }"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_catch_all() {
+    assert_eq!(
+        vec![Key::Name, Key::Other("email")],
+        eserde::json::from_str::<Vec<Key>>(r#"["name", "email"]"#).unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::json::from_str::<Config>(
+        r#"{
+            "retries": 300,
+            "settings": {"log_file": "out.log", "timeout": 30},
+            "levels": ["Info", "Trace", 7]
+        }"#,
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - retries: invalid value: integer `300`, expected u8 at line 2 column 26
+    - settings: unknown field `log_file`, expected one of `log-file`, `color`, `colour`, `timeout`
+    - settings.timeout: invalid type: integer `30`, expected a string at line 3 column 61
+    - levels[1]: unknown variant `Trace`, expected one of `Debug`, `Info`, `Warn` at line 4 column 38
+    - levels[2]: invalid type: integer `7`, expected variant identifier at line 4 column 41
+    "#);
+}
@@ -17,7 +17,15 @@ fn struct_deny_unknown_fields() {
                             segments: [],
                         },
                     ),
-                    details: "missing field `write_only`",
+                    details: "missing field `WRITE-ONLY`",
+                    origin: None,
+                    offset: None,
+                    code: Some(
+                        "missing_field",
+                    ),
+                    field: Some(
+                        "WRITE-ONLY",
+                    ),
                 },
             ],
         ),
@@ -40,7 +48,15 @@ fn struct_allow_unknown_fields() {
                             segments: [],
                         },
                     ),
-                    details: "missing field `write_only`",
+                    details: "missing field `WRITE-ONLY`",
+                    origin: None,
+                    offset: None,
+                    code: Some(
+                        "missing_field",
+                    ),
+                    field: Some(
+                        "WRITE-ONLY",
+                    ),
                 },
                 DeserializationError {
                     path: Some(
@@ -48,7 +64,15 @@ fn struct_allow_unknown_fields() {
                             segments: [],
                         },
                     ),
-                    details: "missing field `skip_serializing_if`",
+                    details: "missing field `SKIP-SERIALIZING-IF`",
+                    origin: None,
+                    offset: None,
+                    code: Some(
+                        "missing_field",
+                    ),
+                    field: Some(
+                        "SKIP-SERIALIZING-IF",
+                    ),
                 },
             ],
         ),
@@ -56,6 +80,25 @@ fn struct_allow_unknown_fields() {
     "#);
 }
 
+/// Missing fields are named after `rename_all`, like `serde` itself does:
+/// the expectations above must match the errors reported by `serde_json`.
+#[test]
+fn missing_field_names_match_serde() {
+    let error = serde_json::from_str::<StructDenyUnknownFields>(
+        r#"{"DEFAULT":true,"SKIP-SERIALIZING-IF":true,"renamed":false,"OPTION":false}"#,
+    )
+    .unwrap_err();
+    assert!(error.to_string().starts_with("missing field `WRITE-ONLY`"));
+
+    let error = serde_json::from_str::<StructAllowUnknownFields>(
+        r#"{"DEFAULT":false,"WRITE-ONLY":false,"renamed":false,"OPTION":null}"#,
+    )
+    .unwrap_err();
+    assert!(error
+        .to_string()
+        .starts_with("missing field `SKIP-SERIALIZING-IF`"));
+}
+
 #[test]
 fn tuple_struct() {
     let test =
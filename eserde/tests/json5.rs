@@ -0,0 +1,108 @@
+#![cfg(feature = "json5")]
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Config {
+    name: String,
+    port: u16,
+    tags: Vec<String>,
+    db: Db,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Db {
+    url: String,
+    pool_size: u32,
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        Config {
+            name: "api".to_owned(),
+            port: 8080,
+            tags: vec!["web".to_owned(), "public".to_owned()],
+            db: Db {
+                url: "postgres://localhost/app".to_owned(),
+                pool_size: 10,
+            },
+        },
+        eserde::json5::from_str(
+            r#"
+            // The service configuration.
+            {
+                name: "api",
+                port: 8080, /* Must be above 1024. */
+                tags: ['web', 'public',],
+                "db": {
+                    url: "postgres://localhost/app",
+                    pool_size: 10,
+                },
+            }"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::json5::from_str::<Config>(
+        r#"
+        {
+            // Out of range.
+            port: 80800,
+            tags: ['web', 42,],
+            db: {
+                pool_size: "ten",
+            },
+        }"#,
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - port: invalid value: integer `80800`, expected u16
+    - tags[1]: invalid type: integer `42`, expected a string
+    - db.pool_size: invalid type: string "ten", expected u32
+    - db: missing field `url`
+    - missing field `name`
+    "#);
+}
+
+#[test]
+fn test_fail_syntax() {
+    let x = eserde::json5::from_str::<Config>("{ name: 'api', port: }");
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - --> 1:22
+      |
+    1 | { name: 'api', port: }
+      |                      ^---
+      |
+      = expected array, boolean, null, number, object, or string
+    "#);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn test_dynamic_values() {
+    #[derive(eserde::Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Plugin {
+        name: String,
+        settings: serde_json::Value,
+    }
+
+    let x = eserde::json5::from_str::<Vec<Plugin>>(
+        r#"[
+            { name: "cache", settings: { ttl: 60, }, },
+            { name: 42, settings: [1, 2,], },
+        ]"#,
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - [1].name: invalid type: integer `42`, expected a string
+    "#);
+}
@@ -0,0 +1,106 @@
+#![cfg(feature = "json")]
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Record {
+    id: u64,
+    name: String,
+}
+
+const INPUT: &str = r#"{ "id": 1, "name": "foo" }
+{ "id": -2, "name": "bar" }
+
+{ "id": 3 }
+{ "id": 4, "name": "qux" }
+{ "id": "5", "name": 5 }
+"#;
+
+#[test]
+fn test_happy() {
+    let input = "{ \"id\": 1, \"name\": \"foo\" }\r\n\n{ \"id\": 2, \"name\": \"bar\" }\n";
+    assert_eq!(
+        vec![
+            Record {
+                id: 1,
+                name: "foo".to_owned()
+            },
+            Record {
+                id: 2,
+                name: "bar".to_owned()
+            },
+        ],
+        eserde::json::lines(input).collect_all(10).unwrap()
+    );
+}
+
+#[test]
+fn test_per_line() {
+    let results = eserde::json::lines::<Record>(INPUT).collect::<Vec<_>>();
+    assert_eq!(results.len(), 5);
+    assert!(results[0].is_ok());
+    assert!(results[3].is_ok());
+    let errs = results.into_iter().nth(1).unwrap().unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - [1].id: invalid value: integer `-2`, expected u64 at line 2 column 10
+    "#);
+}
+
+#[test]
+fn test_collect_all() {
+    let x = eserde::json::lines::<Record>(INPUT).collect_all(10);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - [1].id: invalid value: integer `-2`, expected u64 at line 2 column 10
+    - [3]: missing field `name`
+    - [5].id: invalid type: string "5", expected u64 at line 6 column 11
+    - [5].name: invalid type: integer `5`, expected a string at line 6 column 22
+    "#);
+}
+
+#[test]
+fn test_collect_all_capped() {
+    let x = eserde::json::lines::<Record>(INPUT).collect_all(2);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - [1].id: invalid value: integer `-2`, expected u64 at line 2 column 10
+    - [3]: missing field `name`
+    - too many errors, stopped after the first 2
+    "#);
+}
+
+#[test]
+fn test_collect_all_zero_is_clamped() {
+    let x = eserde::json::lines::<Record>(INPUT).collect_all(0);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - [1].id: invalid value: integer `-2`, expected u64 at line 2 column 10
+    - too many errors, stopped after the first 1
+    "#);
+}
+
+#[test]
+fn test_line_numbers_with_formatter() {
+    // The formatter sees the actual line number, and its output is left untouched.
+    let formatter = |error: &eserde::messages::ErrorDescription<'_>, _: &str| {
+        Some(format!(
+            "{} (at line 1 column 1 of the batch)",
+            error.message()
+        ))
+    };
+    let errs = eserde::messages::with_formatter(std::sync::Arc::new(formatter), "en", || {
+        eserde::json::lines::<Record>(INPUT).collect_all(10)
+    })
+    .unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - [1].id: invalid value: integer `-2`, expected u64 at line 2 column 10 (at line 1 column 1 of the batch)
+    - [3]: missing field `name` (at line 1 column 1 of the batch)
+    - [5].id: invalid type: string "5", expected u64 at line 6 column 11 (at line 1 column 1 of the batch)
+    - [5].name: invalid type: integer `5`, expected a string at line 6 column 22 (at line 1 column 1 of the batch)
+    "#);
+}
@@ -0,0 +1,67 @@
+#![cfg(feature = "json")]
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Order {
+    id: u64,
+    items: Vec<Item>,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Item {
+    sku: String,
+    quantity: u16,
+}
+
+const INPUT: &str = r#"{
+    "id": -1,
+    "items": [
+        { "sku": "A", "quantity": -1 },
+        { "sku": 2, "quantity": 70000 },
+        { "quantity": 1 }
+    ]
+}"#;
+
+#[test]
+fn test_under_budget() {
+    let x = eserde::json::from_slice_with_max_errors::<Order>(INPUT.as_bytes(), 10);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - id: invalid value: integer `-1`, expected u64 at line 2 column 12
+    - items[0].quantity: invalid value: integer `-1`, expected u16 at line 4 column 36
+    - items[1].sku: invalid type: integer `2`, expected a string at line 5 column 18
+    - items[1].quantity: invalid value: integer `70000`, expected u16 at line 5 column 37
+    - items[2]: missing field `sku`
+    "#);
+}
+
+#[test]
+fn test_over_budget() {
+    let x = eserde::json::from_slice_with_max_errors::<Order>(INPUT.as_bytes(), 2);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - id: invalid value: integer `-1`, expected u64 at line 2 column 12
+    - items[0].quantity: invalid value: integer `-1`, expected u16 at line 4 column 36
+    - too many errors, stopped after the first 2
+    "#);
+}
+
+#[test]
+fn test_unbounded_budget() {
+    let x = eserde::json::from_slice_with_max_errors::<Order>(INPUT.as_bytes(), usize::MAX);
+    let errs = x.unwrap_err();
+    assert_eq!(errs.len(), 5);
+}
+
+#[test]
+fn test_str_over_budget() {
+    let x = eserde::json::from_str_with_max_errors::<Order>(INPUT, 1);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - id: invalid value: integer `-1`, expected u64 at line 2 column 12
+    - too many errors, stopped after the first 1
+    "#);
+}
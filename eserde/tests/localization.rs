@@ -0,0 +1,155 @@
+use std::sync::Arc;
+
+use eserde::messages::{with_formatter, ErrorDescription};
+
+#[derive(eserde::Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+#[allow(dead_code)]
+struct Order {
+    id: u32,
+    quantity: u8,
+    items: Vec<String>,
+    coords: [f64; 2],
+    status: Status,
+    #[eserde(message = "Please provide a valid note")]
+    note: String,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+enum Status {
+    Open,
+    Closed,
+}
+
+/// Describe each error, rather than translating it, to check what the formatter receives.
+fn describe(error: &ErrorDescription<'_>, locale: &str) -> Option<String> {
+    Some(format!(
+        "[{locale}] {} field={:?} expected={:?} unexpected={:?}",
+        error.code(),
+        error.field(),
+        error.expected(),
+        error.unexpected(),
+    ))
+}
+
+fn messages(input: &str) -> Vec<String> {
+    let errors = with_formatter(Arc::new(describe), "fr", || {
+        eserde::json::from_str::<Order>(input)
+    })
+    .unwrap_err();
+    errors.iter().map(|e| e.to_string()).collect()
+}
+
+#[test]
+fn test_error_descriptions() {
+    insta::assert_debug_snapshot!(messages(
+        r#"{"id": "1", "quantity": 300, "items": [1], "coords": [1.0], "status": "Lost", "note": 2, "extra": 1}"#
+    ), @r#"
+    [
+        "id: [fr] invalid_type field=Some(\"id\") expected=Some(\"u32\") unexpected=Some(\"string \\\"1\\\"\")",
+        "quantity: [fr] out_of_range field=Some(\"quantity\") expected=Some(\"u8\") unexpected=Some(\"integer `300`\")",
+        "items[0]: [fr] invalid_type field=None expected=Some(\"a string\") unexpected=Some(\"integer `1`\")",
+        "coords: [fr] invalid_length field=Some(\"coords\") expected=Some(\"sequence of 2 elements\") unexpected=Some(\"1\")",
+        "status: [fr] unknown_variant field=Some(\"status\") expected=Some(\"`Open` or `Closed`\") unexpected=Some(\"Lost\")",
+        "note: Please provide a valid note",
+        "extra: [fr] unknown_field field=Some(\"extra\") expected=Some(\"one of `id`, `quantity`, `items`, `coords`, `status`, `note`\") unexpected=None",
+    ]
+    "#);
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Segment {
+    #[eserde(compat)]
+    ends: (u8, u8),
+}
+
+#[test]
+fn test_invalid_length_description() {
+    // Tuples are deserialized by `serde`, with its own wording for length errors.
+    let errors = with_formatter(Arc::new(describe), "fr", || {
+        eserde::json::from_str::<Segment>(r#"{"ends": [1]}"#)
+    })
+    .unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    insta::assert_debug_snapshot!(messages, @r#"
+    [
+        "ends: [fr] invalid_length field=Some(\"ends\") expected=Some(\"a tuple of size 2\") unexpected=Some(\"1\")",
+    ]
+    "#);
+}
+
+#[test]
+fn test_missing_and_duplicate_fields() {
+    insta::assert_debug_snapshot!(messages(r#"{"id": 1, "id": 2}"#), @r#"
+    [
+        "id: [fr] duplicate_field field=Some(\"id\") expected=None unexpected=None",
+    ]
+    "#);
+    insta::assert_debug_snapshot!(messages(r#"{"id": 1}"#), @r#"
+    [
+        "[fr] missing_field field=Some(\"quantity\") expected=None unexpected=None",
+        "[fr] missing_field field=Some(\"items\") expected=None unexpected=None",
+        "[fr] missing_field field=Some(\"coords\") expected=None unexpected=None",
+        "[fr] missing_field field=Some(\"status\") expected=None unexpected=None",
+        "Please provide a valid note",
+    ]
+    "#);
+}
+
+#[test]
+fn test_formatter_can_keep_the_original_message() {
+    let formatter = |error: &ErrorDescription<'_>, locale: &str| {
+        (locale == "it" && error.code() == "missing_field")
+            .then(|| format!("manca il campo `{}`", error.field().unwrap()))
+    };
+    let errors = with_formatter(Arc::new(formatter), "it", || {
+        eserde::json::from_str::<Order>(r#"{"id": "1"}"#)
+    })
+    .unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    insta::assert_debug_snapshot!(messages, @r#"
+    [
+        "id: invalid type: string \"1\", expected u32 at line 1 column 10",
+        "manca il campo `quantity`",
+        "manca il campo `items`",
+        "manca il campo `coords`",
+        "manca il campo `status`",
+        "Please provide a valid note",
+    ]
+    "#);
+}
+
+#[test]
+fn test_formatter_is_scoped() {
+    let _ = with_formatter(Arc::new(describe), "fr", || {
+        eserde::json::from_str::<Order>(r#"{}"#)
+    });
+    let errors = eserde::json::from_str::<Order>(
+        r#"{"id": 1, "quantity": 1, "items": [], "coords": [0, 0], "status": "Open"}"#,
+    )
+    .unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    insta::assert_debug_snapshot!(messages, @r#"
+    [
+        "Please provide a valid note",
+    ]
+    "#);
+}
+
+#[test]
+#[cfg(feature = "toml")]
+fn test_formatter_applies_to_format_errors() {
+    // Syntax errors are raised by the data format, rather than reported while visiting the input.
+    let errors = with_formatter(Arc::new(describe), "fr", || {
+        eserde::toml::from_str::<Order>("id = ")
+    })
+    .unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    insta::assert_debug_snapshot!(messages, @r#"
+    [
+        "[fr] invalid field=None expected=None unexpected=None",
+    ]
+    "#);
+}
@@ -0,0 +1,85 @@
+#[derive(eserde::Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+struct Signup {
+    #[eserde(message = "Age must be a whole number")]
+    age: u32,
+    #[eserde(
+        missing = "Please tell us your {field}",
+        invalid = "`{field}` must be a {expected}: {message}"
+    )]
+    display_name: String,
+    #[serde(default)]
+    #[eserde(invalid = "Invalid tags")]
+    tags: Vec<String>,
+    #[eserde(compat, invalid = "Bad referrer: {message}")]
+    referrer: Option<String>,
+    plan: Plan,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+enum Plan {
+    Free,
+    Paid {
+        #[eserde(missing = "Paid plans need {field}")]
+        seats: u16,
+    },
+}
+
+#[test]
+fn test_happy() {
+    let signup = eserde::json::from_str::<Signup>(
+        r#"{"age": 30, "displayName": "Ada", "referrer": null, "plan": "Free"}"#,
+    )
+    .unwrap();
+    assert_eq!(signup.display_name, "Ada");
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::json::from_str::<Signup>(
+        r#"{"age": "2", "tags": [1, "a", 2], "referrer": 3, "plan": {"Paid": {}}}"#,
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - age: Age must be a whole number
+    - tags[0]: Invalid tags
+    - tags[2]: Invalid tags
+    - referrer: Bad referrer: invalid type: integer `3`, expected a string at line 1 column 47
+    - plan: Paid plans need seats
+    - Please tell us your displayName
+    "#);
+}
+
+#[test]
+fn test_fail_invalid() {
+    let x = eserde::json::from_str::<Signup>(r#"{"age": -1, "displayName": 7, "plan": "Free"}"#);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - age: Age must be a whole number
+    - displayName: `displayName` must be a String: invalid type: integer `7`, expected a string at line 1 column 28
+    "#);
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Account {
+    #[eserde(invalid = "`{field}` must be a {expected}")]
+    r#type: u8,
+}
+
+#[test]
+fn test_raw_identifier() {
+    let x = eserde::json::from_str::<Account>(r#"{"type": "admin"}"#);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - type: `type` must be a u8
+    "#);
+}
@@ -0,0 +1,157 @@
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+struct Request {
+    request_id: String,
+    #[serde(rename = "X-Retry")]
+    retry: u8,
+    r#type: String,
+    trace_parent: Option<String>,
+    body: Body,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+#[serde(rename_all_fields = "camelCase")]
+enum Body {
+    Text {
+        content_type: String,
+        max_length: Option<u32>,
+    },
+    #[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+    Binary { content_type: String },
+}
+
+#[test]
+fn test_optional_fields_can_be_missing() {
+    assert_eq!(
+        Request {
+            request_id: "a".to_owned(),
+            retry: 1,
+            r#type: "ping".to_owned(),
+            trace_parent: None,
+            body: Body::Text {
+                content_type: "text/plain".to_owned(),
+                max_length: None,
+            },
+        },
+        eserde::json::from_str(
+            r#"{"request-id": "a", "X-Retry": 1, "type": "ping", "body": {"Text": {"contentType": "text/plain"}}}"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_missing_fields_use_serde_names() {
+    let x = eserde::json::from_str::<Request>(r#"{"retry": "1", "body": {"Text": {}}}"#);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - body: missing field `contentType`
+    - missing field `request-id`
+    - missing field `X-Retry`
+    - missing field `type`
+    "#);
+
+    let x = eserde::json::from_str::<Request>(r#"{"body": {"Binary": {}}}"#);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - body: missing field `CONTENT_TYPE`
+    - missing field `request-id`
+    - missing field `X-Retry`
+    - missing field `type`
+    "#);
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[serde(rename_all(serialize = "UPPERCASE", deserialize = "PascalCase"))]
+#[allow(dead_code)]
+struct Directional {
+    user_name: String,
+    #[serde(rename(serialize = "mail", deserialize = "e-mail"))]
+    email: String,
+    #[serde(rename(deserialize = "Zip"), default)]
+    zip_code: Option<String>,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[serde(rename_all = "lowercase")]
+#[allow(dead_code)]
+struct Lowercase {
+    user_name: String,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[serde(rename_all(serialize = "UPPERCASE"))]
+#[allow(dead_code)]
+struct SerializeOnly {
+    user_name: String,
+}
+
+/// The message of the first missing field error reported by `serde` itself.
+fn serde_missing_field<T: serde::de::DeserializeOwned + std::fmt::Debug>(input: &str) -> String {
+    let error = serde_json::from_str::<T>(input).unwrap_err().to_string();
+    error.split(" at line ").next().unwrap().to_owned()
+}
+
+fn eserde_missing_fields<T: for<'de> eserde::EDeserialize<'de> + std::fmt::Debug>(
+    input: &str,
+) -> Vec<String> {
+    let errors = eserde::json::from_str::<T>(input).unwrap_err();
+    errors.iter().map(|e| e.message().to_owned()).collect()
+}
+
+#[test]
+fn test_missing_fields_honor_deserialize_renames() {
+    insta::assert_debug_snapshot!(eserde_missing_fields::<Directional>("{}"), @r#"
+    [
+        "missing field `UserName`",
+        "missing field `e-mail`",
+    ]
+    "#);
+    insta::assert_debug_snapshot!(eserde_missing_fields::<Lowercase>("{}"), @r#"
+    [
+        "missing field `user_name`",
+    ]
+    "#);
+    insta::assert_debug_snapshot!(eserde_missing_fields::<SerializeOnly>("{}"), @r#"
+    [
+        "missing field `user_name`",
+    ]
+    "#);
+}
+
+#[test]
+fn test_missing_fields_match_serde() {
+    let cases = [
+        (
+            serde_missing_field::<Directional>(r#"{"e-mail": "a"}"#),
+            eserde_missing_fields::<Directional>(r#"{"e-mail": "a"}"#),
+        ),
+        (
+            serde_missing_field::<Directional>(r#"{"UserName": "a"}"#),
+            eserde_missing_fields::<Directional>(r#"{"UserName": "a"}"#),
+        ),
+        (
+            serde_missing_field::<Lowercase>("{}"),
+            eserde_missing_fields::<Lowercase>("{}"),
+        ),
+        (
+            serde_missing_field::<SerializeOnly>("{}"),
+            eserde_missing_fields::<SerializeOnly>("{}"),
+        ),
+        (
+            serde_missing_field::<Request>(
+                r#"{"request-id": "a", "type": "ping", "body": {"Text": {"contentType": "a"}}}"#,
+            ),
+            eserde_missing_fields::<Request>(
+                r#"{"request-id": "a", "type": "ping", "body": {"Text": {"contentType": "a"}}}"#,
+            ),
+        ),
+    ];
+    for (serde, eserde) in cases {
+        assert_eq!(vec![serde], eserde);
+    }
+}
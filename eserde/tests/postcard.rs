@@ -0,0 +1,157 @@
+#![cfg(feature = "postcard")]
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Order {
+    id: u32,
+    customer: String,
+    items: Vec<Item>,
+    status: Status,
+    #[serde(default)]
+    note: Option<String>,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Item {
+    sku: String,
+    quantity: u16,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+enum Status {
+    Pending,
+    Shipped { tracking: String },
+}
+
+// The same shape as `Order`, but more permissive, to produce invalid payloads.
+#[derive(serde::Serialize)]
+struct RawOrder {
+    id: u32,
+    customer: String,
+    items: Vec<RawItem>,
+    status: RawStatus,
+    note: Option<String>,
+}
+
+#[derive(serde::Serialize)]
+struct RawItem {
+    sku: String,
+    quantity: u64,
+}
+
+#[derive(serde::Serialize)]
+enum RawStatus {
+    #[allow(dead_code)]
+    Pending,
+    Shipped {
+        tracking: String,
+    },
+    Lost,
+}
+
+fn item(sku: &str, quantity: u64) -> RawItem {
+    RawItem {
+        sku: sku.to_owned(),
+        quantity,
+    }
+}
+
+fn encode(items: Vec<RawItem>, status: RawStatus) -> Vec<u8> {
+    postcard::to_allocvec(&RawOrder {
+        id: 7,
+        customer: "ACME".to_owned(),
+        items,
+        status,
+        note: Some("fragile".to_owned()),
+    })
+    .unwrap()
+}
+
+#[test]
+fn test_happy() {
+    let bytes = encode(
+        vec![item("A", 2), item("B", 1)],
+        RawStatus::Shipped {
+            tracking: "XY".to_owned(),
+        },
+    );
+    assert_eq!(
+        Order {
+            id: 7,
+            customer: "ACME".to_owned(),
+            items: vec![
+                Item {
+                    sku: "A".to_owned(),
+                    quantity: 2
+                },
+                Item {
+                    sku: "B".to_owned(),
+                    quantity: 1
+                },
+            ],
+            status: Status::Shipped {
+                tracking: "XY".to_owned()
+            },
+            note: Some("fragile".to_owned()),
+        },
+        eserde::postcard::from_bytes(&bytes).unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    // Only the first error is reported: the second item would be out of range as well.
+    let bytes = encode(
+        vec![item("A", 2), item("B", 70_000), item("C", 80_000)],
+        RawStatus::Lost,
+    );
+    let x = eserde::postcard::from_bytes::<Order>(&bytes);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - items[1].quantity: Found a varint that didn't terminate. Is the usize too big for this platform? at byte offset 15
+    "#);
+}
+
+#[test]
+fn test_fail_unknown_variant() {
+    let bytes = encode(vec![item("A", 2)], RawStatus::Lost);
+    let x = eserde::postcard::from_bytes::<Order>(&bytes);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - status: Serde Deserialization Error at byte offset 11
+    "#);
+}
+
+#[test]
+fn test_fail_truncated() {
+    let bytes = encode(vec![item("A", 2)], RawStatus::Pending);
+    let x = eserde::postcard::from_bytes::<Order>(&bytes[..8]);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - items[0].sku: Hit the end of buffer, expected more data at byte offset 8
+    "#);
+}
+
+#[test]
+fn test_fail_with_formatter() {
+    // The byte offset is kept apart from the message, which the formatter replaces entirely.
+    let formatter = |error: &eserde::messages::ErrorDescription<'_>, _: &str| {
+        Some(format!("valore non valido ({})", error.code()))
+    };
+    let bytes = encode(vec![item("A", 2), item("B", 70_000)], RawStatus::Lost);
+    let errs = eserde::messages::with_formatter(std::sync::Arc::new(formatter), "it", || {
+        eserde::postcard::from_bytes::<Order>(&bytes)
+    })
+    .unwrap_err();
+    let error = errs.iter().next().unwrap();
+    assert_eq!(error.message(), "valore non valido (invalid)");
+    assert_eq!(error.offset(), Some(15));
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - items[1].quantity: valore non valido (invalid) at byte offset 15
+    "#);
+}
@@ -0,0 +1,102 @@
+#![cfg(feature = "ron")]
+#[derive(eserde::Deserialize, Debug, PartialEq)]
+struct Scene {
+    name: String,
+    shapes: Vec<Shape>,
+    #[serde(default)]
+    background: Option<Color>,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq)]
+enum Shape {
+    Circle { radius: f32 },
+    Rectangle(u32, u32),
+    Label(String),
+    Empty,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq)]
+struct Color(u8, u8, u8);
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        Scene {
+            name: "intro".to_owned(),
+            shapes: vec![
+                Shape::Circle { radius: 1.5 },
+                Shape::Rectangle(4, 3),
+                Shape::Label("hello".to_owned()),
+                Shape::Empty,
+            ],
+            background: Some(Color(255, 0, 128)),
+        },
+        eserde::ron::from_str(
+            r#"
+            // A scene.
+            Scene(
+                name: "intro",
+                shapes: [
+                    Circle(radius: 1.5),
+                    Rectangle(4, 3),
+                    Label("hello"),
+                    Empty,
+                ],
+                background: Some((255, 0, 128)),
+            )"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::ron::from_str::<Scene>(
+        r#"
+        Scene(
+            shapes: [
+                Circle(radius: "big"),
+                Rectangle(4, -3),
+                Label(42),
+                Triangle,
+                Circle(),
+            ],
+            background: Some((256, 0, 0)),
+        )"#,
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - shapes[0].Circle.radius: Expected f32 but found the string "big" instead
+    - shapes[1].Rectangle[1]: Expected u32 but found the signed integer `-3` instead
+    - shapes[2].Label: Expected a string but found the unsigned integer `42` instead
+    - shapes[3]: Unexpected variant named `Triangle` in enum `Shape`, expected one of `Circle`, `Rectangle`, `Label`, or `Empty` instead
+    - shapes[4]: missing field `radius`
+    - background[0]: Expected u8 but found the unsigned integer `256` instead
+    - missing field `name`
+    "#);
+}
+
+#[test]
+fn test_fail_syntax() {
+    let x = eserde::ron::from_str::<Scene>(r#"Scene(name: "intro", shapes: [)"#);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - shapes[0]: Expected identifier
+    - shapes[1]: Expected comma
+    "#);
+}
+
+#[test]
+fn test_fail_trailing_characters() {
+    let x = eserde::ron::from_str::<Scene>(r#"Scene(name: "intro", shapes: []) Scene()"#);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - 1:33-1:34: Non-whitespace trailing characters
+    "#);
+}
@@ -0,0 +1,109 @@
+use eserde::{reporter::ErrorReporter, EDeserialize};
+use serde::{
+    de::{self, value, Visitor},
+    forward_to_deserialize_any, Deserializer,
+};
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[serde(rename = "Coordinates")]
+#[allow(dead_code)]
+struct Renamed {
+    x: i32,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[serde(rename(serialize = "Output"))]
+#[allow(dead_code)]
+struct SerializeRenamed {
+    x: i32,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct r#Raw {
+    x: i32,
+}
+
+/// A deserializer that fails with the name of the struct it's asked to deserialize.
+struct StructName;
+
+impl<'de> Deserializer<'de> for StructName {
+    type Error = value::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom("not a struct"))
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        _fields: &'static [&'static str],
+        _visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        Err(de::Error::custom(name))
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf option unit unit_struct newtype_struct seq tuple
+        tuple_struct map enum identifier ignored_any
+    }
+}
+
+/// The struct name seen by the deserializer in both passes.
+fn names<T>() -> (String, Vec<String>)
+where
+    T: for<'de> EDeserialize<'de> + std::fmt::Debug,
+{
+    let first_pass = T::deserialize(StructName).unwrap_err().to_string();
+    let _guard = ErrorReporter::start_deserialization();
+    let _ = T::deserialize_for_errors(StructName);
+    let second_pass = ErrorReporter::take_errors()
+        .iter()
+        .map(|e| e.message().to_owned())
+        .collect();
+    (first_pass, second_pass)
+}
+
+#[test]
+fn test_generated_types_keep_the_original_name() {
+    insta::assert_debug_snapshot!(names::<Point>(), @r#"
+    (
+        "Point",
+        [
+            "Point",
+        ],
+    )
+    "#);
+    insta::assert_debug_snapshot!(names::<Renamed>(), @r#"
+    (
+        "Coordinates",
+        [
+            "Coordinates",
+        ],
+    )
+    "#);
+    insta::assert_debug_snapshot!(names::<SerializeRenamed>(), @r#"
+    (
+        "SerializeRenamed",
+        [
+            "SerializeRenamed",
+        ],
+    )
+    "#);
+    insta::assert_debug_snapshot!(names::<Raw>(), @r#"
+    (
+        "Raw",
+        [
+            "Raw",
+        ],
+    )
+    "#);
+}
@@ -0,0 +1,194 @@
+use std::collections::HashMap;
+
+use eserde::{config::ConfigBuilder, env::Options, DeserializationWarning};
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Config {
+    name: String,
+    #[serde(default)]
+    servers: Vec<Server>,
+    #[serde(default)]
+    features: HashMap<String, bool>,
+    #[serde(default)]
+    storage: Option<Storage>,
+    #[serde(default)]
+    logging: Option<Logging>,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Server {
+    host: String,
+    #[serde(rename = "listen-port", default)]
+    port: u16,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+enum Storage {
+    Disk { path: String },
+    Memory,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Logging {
+    level: String,
+    // Unknown keys are forwarded to the flattened map, so they can't be detected.
+    #[serde(flatten)]
+    extra: HashMap<String, String>,
+}
+
+fn unknown_fields(warnings: Vec<DeserializationWarning>) -> Vec<String> {
+    warnings
+        .iter()
+        .filter(|w| w.code() == "unknown_field")
+        .map(|w| w.to_string())
+        .collect()
+}
+
+#[test]
+fn test_toml() {
+    let input = r#"
+        name = "app"
+        version = 2
+
+        [[servers]]
+        host = "a"
+        listen-port = 80
+        port = 8080
+
+        [features]
+        beta = true
+
+        [storage.Disk]
+        path = "/data"
+
+        [logging]
+        level = "info"
+        format = "json"
+    "#;
+    let (_, warnings) = eserde::toml::from_str_with_warnings::<Config>(input).unwrap();
+    insta::assert_debug_snapshot!(unknown_fields(warnings), @r#"
+    [
+        "version: unknown field `version` was ignored",
+        "servers[0].port: unknown field `port` was ignored",
+    ]
+    "#);
+}
+
+#[test]
+fn test_json5() {
+    let input = r#"{ name: "app", servers: [{ host: "a" }, { host: "b", tls: true }] }"#;
+    let (_, warnings) = eserde::json5::from_str_with_warnings::<Config>(input).unwrap();
+    insta::assert_debug_snapshot!(unknown_fields(warnings), @r#"
+    [
+        "servers[1].tls: unknown field `tls` was ignored",
+    ]
+    "#);
+}
+
+#[test]
+fn test_ron() {
+    let input = r#"Config(name: "app", servers: [Server(host: "a", tls: true)], debug: true)"#;
+    let (_, warnings) = eserde::ron::from_str_with_warnings::<Config>(input).unwrap();
+    insta::assert_debug_snapshot!(unknown_fields(warnings), @r#"
+    [
+        "servers[0].tls: unknown field `tls` was ignored",
+        "debug: unknown field `debug` was ignored",
+    ]
+    "#);
+}
+
+#[test]
+fn test_json_slice() {
+    let input = br#"{ "name": "app", "servers": [{ "host": "a", "tls": true }] }"#;
+    let (_, warnings) = eserde::json::from_slice_with_warnings::<Config>(input).unwrap();
+    insta::assert_debug_snapshot!(unknown_fields(warnings), @r#"
+    [
+        "servers[0].tls: unknown field `tls` was ignored",
+    ]
+    "#);
+}
+
+#[test]
+fn test_urlencoded() {
+    let (_, warnings) =
+        eserde::urlencoded::from_str_with_warnings::<Config>("name=app&debug=true").unwrap();
+    insta::assert_debug_snapshot!(unknown_fields(warnings), @r#"
+    [
+        "debug: unknown field `debug` was ignored",
+    ]
+    "#);
+}
+
+#[test]
+fn test_xml() {
+    let input = r#"<config version="2">
+        <name>app</name>
+        <servers><host>a</host><tls>true</tls></servers>
+    </config>"#;
+    let (_, warnings) = eserde::xml::from_str_with_warnings::<Config>(input).unwrap();
+    insta::assert_debug_snapshot!(unknown_fields(warnings), @r#"
+    [
+        "config.@version: unknown field `@version` was ignored",
+        "config.servers[0].tls: unknown field `tls` was ignored",
+    ]
+    "#);
+}
+
+#[test]
+fn test_env() {
+    let (_, warnings) = Options::new()
+        .prefix("APP_")
+        .separator("__")
+        .from_iter_with_warnings::<Config, _, _, _>([
+            ("APP_NAME", "app"),
+            ("APP_DEBUG", "true"),
+            ("APP_LOGGING__LEVEL", "info"),
+        ])
+        .unwrap();
+    insta::assert_debug_snapshot!(unknown_fields(warnings), @r#"
+    [
+        "APP_DEBUG: unknown field `debug` was ignored",
+    ]
+    "#);
+}
+
+#[test]
+fn test_config() {
+    let (_, warnings) = ConfigBuilder::new()
+        .add_toml("defaults.toml", "name = \"app\"\nversion = 2")
+        .add_json(
+            "production.json",
+            r#"{ "servers": [{ "host": "a", "tls": true }] }"#,
+        )
+        .add_env_vars(
+            &Options::new().prefix("APP_").separator("__"),
+            [("APP_NAME", "prod"), ("APP_DEBUG", "true")],
+        )
+        .deserialize_with_warnings::<Config>()
+        .unwrap();
+    insta::assert_debug_snapshot!(unknown_fields(warnings), @r#"
+    [
+        "debug: unknown field `debug` was ignored (from env)",
+        "servers[0].tls: unknown field `tls` was ignored (from production.json)",
+        "version: unknown field `version` was ignored (from defaults.toml)",
+    ]
+    "#);
+}
+
+#[test]
+fn test_errors_are_reported_as_usual() {
+    let errors =
+        eserde::toml::from_str_with_warnings::<Config>("version = 2\nservers = [{ port = 1 }]")
+            .unwrap_err();
+    let errors: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    insta::assert_debug_snapshot!(errors, @r#"
+    [
+        "servers[0]: missing field `host`",
+        "missing field `name`",
+    ]
+    "#);
+}
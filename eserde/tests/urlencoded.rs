@@ -0,0 +1,52 @@
+#![cfg(feature = "urlencoded")]
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Search {
+    q: String,
+    page: u32,
+    #[serde(default)]
+    tag: Vec<u32>,
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        Search {
+            q: "rust serde".to_owned(),
+            page: 2,
+            tag: vec![],
+        },
+        eserde::urlencoded::from_str("q=rust+serde&page=2").unwrap()
+    );
+
+    assert_eq!(
+        Search {
+            q: "rust".to_owned(),
+            page: 1,
+            tag: vec![7],
+        },
+        eserde::urlencoded::from_str("q=rust&page=1&tag=7").unwrap()
+    );
+
+    assert_eq!(
+        Search {
+            q: "rust".to_owned(),
+            page: 1,
+            tag: vec![7, 8, 9],
+        },
+        eserde::urlencoded::from_bytes(b"tag=7&q=rust&tag=8&page=1&tag=9").unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::urlencoded::from_str::<Search>("page=two&tag=1&tag=x&tag=3&tag=-4");
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - page: invalid digit found in string
+    - tag[1]: invalid digit found in string
+    - tag[3]: invalid digit found in string
+    - missing field `q`
+    "#);
+}
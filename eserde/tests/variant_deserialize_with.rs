@@ -0,0 +1,121 @@
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Envelope {
+    id: u32,
+    proof: Proof,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+enum Proof {
+    #[serde(deserialize_with = "hex")]
+    Signature(Vec<u8>),
+    #[serde(with = "range")]
+    Range {
+        start: u32,
+        end: u32,
+    },
+    None,
+}
+
+/// Decode a hex-encoded byte string.
+fn hex<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let s: &str = serde::Deserialize::deserialize(deserializer)?;
+    if !s.len().is_multiple_of(2) {
+        return Err(serde::de::Error::custom("odd number of hex digits"));
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16))
+        .collect::<Result<_, _>>()
+        .map_err(serde::de::Error::custom)
+}
+
+/// Parse a `start..end` range.
+mod range {
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<(u32, u32), D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s: &str = serde::Deserialize::deserialize(deserializer)?;
+        s.split_once("..")
+            .and_then(|(start, end)| Some((start.parse().ok()?, end.parse().ok()?)))
+            .ok_or_else(|| serde::de::Error::custom(format!("invalid range `{s}`")))
+    }
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        vec![
+            Envelope {
+                id: 1,
+                proof: Proof::Signature(vec![0xca, 0xfe]),
+            },
+            Envelope {
+                id: 2,
+                proof: Proof::Range { start: 3, end: 7 },
+            },
+            Envelope {
+                id: 3,
+                proof: Proof::None,
+            },
+        ],
+        eserde::json::from_str::<Vec<Envelope>>(
+            r#"[
+                {"id": 1, "proof": {"Signature": "cafe"}},
+                {"id": 2, "proof": {"Range": "3..7"}},
+                {"id": 3, "proof": "None"}
+            ]"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::json::from_str::<Vec<Envelope>>(
+        r#"[
+            {"id": "1", "proof": {"Signature": "caf"}},
+            {"id": 2, "proof": {"Range": "3-7"}},
+            {"id": -3, "proof": {"Signature": "zz"}}
+        ]"#,
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - [0].id: invalid type: string "1", expected u32 at line 2 column 22
+    - [0].proof.Signature: odd number of hex digits
+    - [1].proof.Range: invalid range `3-7`
+    - [2].id: invalid value: integer `-3`, expected u32 at line 4 column 21
+    - [2].proof.Signature: invalid digit found in string
+    "#);
+}
+
+mod raw {
+    // Lints aren't forwarded to the types generated by the derive.
+    #![allow(non_camel_case_types)]
+
+    #[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+    pub enum Keyword {
+        #[serde(deserialize_with = "super::hex")]
+        r#type(Vec<u8>),
+    }
+}
+
+#[test]
+fn test_raw_identifier() {
+    use raw::Keyword;
+
+    assert_eq!(
+        Keyword::r#type(vec![0xca, 0xfe]),
+        eserde::json::from_str::<Keyword>(r#"{"type": "cafe"}"#).unwrap()
+    );
+    let errs = eserde::json::from_str::<Keyword>(r#"{"type": "caf"}"#).unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - type: odd number of hex digits
+    "#);
+}
@@ -0,0 +1,145 @@
+use std::collections::BTreeMap;
+
+use eserde::reporter::ErrorReporter;
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Config {
+    #[serde(alias = "host_name")]
+    host: String,
+    #[eserde(deprecated = "use `workers` instead")]
+    #[serde(default)]
+    threads: Option<u32>,
+    #[serde(default)]
+    workers: Option<u32>,
+    #[eserde(with = "percentage")]
+    #[serde(default)]
+    sampling: u8,
+    #[serde(default)]
+    backends: Vec<Backend>,
+    #[serde(default)]
+    labels: BTreeMap<String, String>,
+    #[serde(default)]
+    strict: Option<Strict>,
+    #[serde(default)]
+    auth: Option<Auth>,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Backend {
+    url: String,
+    #[eserde(deprecated)]
+    #[serde(default)]
+    weight: Option<u32>,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+#[allow(dead_code)]
+struct Strict {
+    enabled: bool,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+enum Auth {
+    Basic { user: String },
+}
+
+/// Values above 100 are clamped, with a warning.
+mod percentage {
+    use super::*;
+    use serde::{Deserialize, Deserializer};
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+        u8::deserialize(deserializer).map(|n| n.min(100))
+    }
+
+    pub fn deserialize_for_errors<'de, D: Deserializer<'de>>(deserializer: D) -> Result<(), ()> {
+        let n = u8::deserialize(deserializer).map_err(ErrorReporter::report)?;
+        if n > 100 {
+            ErrorReporter::report_warning("clamped", format!("{n} was clamped to 100"));
+        }
+        Ok(())
+    }
+}
+
+fn warnings(input: &str) -> Vec<String> {
+    let (_, warnings) = eserde::json::from_str_with_warnings::<Config>(input).unwrap();
+    warnings
+        .iter()
+        .map(|w| format!("{} [{}]", w, w.code()))
+        .collect()
+}
+
+#[test]
+fn test_no_warnings() {
+    insta::assert_debug_snapshot!(warnings(r#"{"host_name": "localhost", "labels": {"env": "prod"}}"#), @r#"
+    []
+    "#);
+}
+
+#[test]
+fn test_warnings() {
+    insta::assert_debug_snapshot!(warnings(
+        r#"{
+            "host": "localhost",
+            "port": 8080,
+            "threads": 4,
+            "sampling": 250,
+            "backends": [{"url": "a"}, {"url": "b", "weight": 2, "tls": true}],
+            "auth": {"Basic": {"user": "admin", "password": "hunter2"}}
+        }"#
+    ), @r#"
+    [
+        "port: unknown field `port` was ignored [unknown_field]",
+        "threads: this field is deprecated: use `workers` instead [deprecated_field]",
+        "sampling: 250 was clamped to 100 [clamped]",
+        "backends[1].weight: this field is deprecated [deprecated_field]",
+        "backends[1].tls: unknown field `tls` was ignored [unknown_field]",
+        "auth.Basic.password: unknown field `password` was ignored [unknown_field]",
+    ]
+    "#);
+}
+
+#[test]
+fn test_errors_take_precedence() {
+    let errors = eserde::json::from_str_with_warnings::<Config>(
+        r#"{"port": 8080, "threads": "4", "strict": {"enabled": true, "extra": 1}}"#,
+    )
+    .unwrap_err();
+    let errors: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    insta::assert_debug_snapshot!(errors, @r#"
+    [
+        "threads: invalid type: string \"4\", expected u32 at line 1 column 29",
+        "strict.extra: unknown field `extra`, expected `enabled` at line 1 column 66",
+        "strict: expected `,` or `}` at line 1 column 67",
+    ]
+    "#);
+}
+
+#[test]
+fn test_warnings_outside_of_deserialization_are_discarded() {
+    ErrorReporter::report_warning("clamped", "this is dropped");
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Loop {
+    #[eserde(deprecated = "use `until` instead")]
+    #[serde(default)]
+    r#while: Option<String>,
+}
+
+#[test]
+fn test_raw_identifier() {
+    let (_, warnings) =
+        eserde::json::from_str_with_warnings::<Loop>(r#"{"while": "done"}"#).unwrap();
+    let warnings: Vec<_> = warnings.iter().map(|w| w.to_string()).collect();
+    insta::assert_debug_snapshot!(warnings, @r#"
+    [
+        "while: this field is deprecated: use `until` instead",
+    ]
+    "#);
+}
@@ -0,0 +1,101 @@
+#![cfg(feature = "xml")]
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Order {
+    #[serde(rename = "@id")]
+    id: u64,
+    customer: String,
+    item: Vec<Item>,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Item {
+    #[serde(rename = "@sku")]
+    sku: String,
+    quantity: Quantity,
+}
+
+#[derive(eserde::Deserialize, Debug, PartialEq, Eq)]
+struct Quantity {
+    #[serde(rename = "@unit", default)]
+    unit: Option<String>,
+    #[serde(rename = "$text")]
+    value: u32,
+}
+
+#[test]
+fn test_happy() {
+    assert_eq!(
+        Order {
+            id: 42,
+            customer: "ACME".to_owned(),
+            item: vec![
+                Item {
+                    sku: "A-1".to_owned(),
+                    quantity: Quantity {
+                        unit: Some("kg".to_owned()),
+                        value: 2,
+                    },
+                },
+                Item {
+                    sku: "B-7".to_owned(),
+                    quantity: Quantity {
+                        unit: None,
+                        value: 1,
+                    },
+                },
+            ],
+        },
+        eserde::xml::from_str(
+            r#"
+            <?xml version="1.0" encoding="UTF-8"?>
+            <order id="42">
+                <customer>ACME</customer>
+                <item sku="A-1"><quantity unit="kg">2</quantity></item>
+                <item sku="B-7"><quantity>1</quantity></item>
+            </order>"#
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_fail() {
+    let x = eserde::xml::from_str::<Order>(
+        r#"
+        <order id="forty-two">
+            <item sku="A-1"><quantity>two</quantity></item>
+            <item><quantity>1</quantity></item>
+            <item sku="C-3"><quantity unit="kg">-5</quantity></item>
+        </order>"#,
+    );
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - order.@id: invalid type: string "forty-two", expected u64
+    - order.item[0].quantity: invalid type: string "two", expected u32
+    - order.item[1]: missing field `@sku`
+    - order.item[2].quantity: invalid type: string "-5", expected u32
+    - order: missing field `customer`
+    "#);
+}
+
+#[test]
+fn test_fail_syntax() {
+    let x = eserde::xml::from_str::<Order>(r#"<order id="1"><customer>ACME</order>"#);
+    assert!(x.is_err(), "Expected Err: {:?}", x);
+    let errs = x.unwrap_err();
+    insta::assert_snapshot!(errs, @r#"
+    Something went wrong during deserialization:
+    - order.customer: ill-formed document: expected `</customer>`, but `</order>` was found
+    - order: ill-formed document: start tag not closed: `</order>` not found before end of input
+    "#);
+}
+
+/// Missing attributes are named after their `@`-prefixed rename, like `quick-xml` does.
+#[test]
+fn test_missing_attribute_name_matches_quick_xml() {
+    let error =
+        quick_xml::de::from_str::<Item>(r#"<item><quantity>1</quantity></item>"#).unwrap_err();
+    assert_eq!(error.to_string(), "missing field `@sku`");
+}
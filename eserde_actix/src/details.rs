@@ -0,0 +1,89 @@
+//! `actix-web` glue for the problem detail types provided by [`eserde_problem`].
+//!
+//! See [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html) for more details.
+use actix_web::{
+    http::{header::CONTENT_TYPE, StatusCode},
+    HttpResponse,
+};
+
+pub(crate) use eserde_problem::ProblemDetails;
+#[cfg(any(feature = "json", feature = "urlencoded"))]
+pub(crate) use eserde_problem::{Source, ValidationError, ValidationErrors};
+
+/// Serialize a problem details object into an `application/problem+json` response.
+pub(crate) fn problem_response<Extension>(details: &ProblemDetails<Extension>) -> HttpResponse
+where
+    Extension: serde::Serialize,
+{
+    match serde_json::to_vec(details) {
+        Ok(body) => {
+            let status =
+                StatusCode::from_u16(details.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            HttpResponse::build(status)
+                .insert_header((CONTENT_TYPE, eserde_problem::APPLICATION_PROBLEM_JSON))
+                .body(body)
+        }
+        Err(_) => internal_server_error(),
+    }
+}
+
+pub(crate) fn internal_server_error() -> HttpResponse {
+    HttpResponse::InternalServerError()
+        .insert_header((CONTENT_TYPE, eserde_problem::APPLICATION_PROBLEM_JSON))
+        .body(eserde_problem::INTERNAL_SERVER_ERROR_PROBLEM)
+}
+
+/// Problem details for a request body that couldn't be buffered
+/// (e.g. because it exceeds the configured size limit).
+#[cfg(any(feature = "json", feature = "urlencoded"))]
+pub(crate) fn payload_error_response(error: &actix_web::Error) -> HttpResponse {
+    let status = error.as_response_error().status_code();
+    let details = if status == StatusCode::PAYLOAD_TOO_LARGE {
+        ProblemDetails::new(status.as_u16())
+            .type_("content_too_large")
+            .title("The content is too large")
+    } else {
+        ProblemDetails::new(status.as_u16())
+            .type_("body_buffering_error")
+            .title("Failed to buffer the body")
+    };
+    let details = details.detail(error.to_string());
+    problem_response(&details)
+}
+
+/// Problem details for a request whose `Content-Type` header is missing
+/// (`missing_header`) or doesn't match what the extractor expects (`invalid_content_type`).
+#[cfg(any(feature = "json", feature = "urlencoded"))]
+pub(crate) fn content_type_response(code: &'static str, detail: String) -> HttpResponse {
+    let error = ValidationError {
+        detail,
+        code: code.into(),
+        source: Source::Header {
+            name: CONTENT_TYPE.as_str().into(),
+        },
+    };
+    problem_response(&ProblemDetails::invalid_request(ValidationErrors {
+        errors: vec![error],
+    }))
+}
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        target = $target:literal,
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: $target,
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+#[cfg(any(feature = "json", feature = "urlencoded"))]
+pub(crate) use __log_rejection;
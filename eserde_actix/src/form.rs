@@ -0,0 +1,326 @@
+//! Supporting types for the [`Form`] extractor.
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+
+use actix_web::body::BoxBody;
+use actix_web::dev::Payload;
+use actix_web::http::header::{self, HeaderMap};
+use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+use eserde::{DeserializationErrors, EDeserialize};
+use serde::de::DeserializeOwned;
+
+use crate::details::{
+    __log_rejection, content_type_response, payload_error_response, problem_response,
+    ProblemDetails, ValidationErrors,
+};
+
+/// URL-encoded form extractor.
+///
+/// It deserializes `application/x-www-form-urlencoded` request bodies into some type that
+/// implements [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`].
+/// The request will be rejected (and a [`FormRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: application/x-www-form-urlencoded` header.
+/// - The body couldn't be deserialized into the target type.
+/// - Buffering the request body fails (e.g. because it exceeds the limit set via
+///   [`PayloadConfig`](actix_web::web::PayloadConfig)).
+///
+/// Repeated keys (e.g. `tag=a&tag=b`) can be deserialized into a `Vec<T>`.
+///
+/// See [`FormRejection`] for more details.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use actix_web::{web, App};
+/// use eserde_actix::Form;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct SignUp {
+///     email: String,
+///     password: String,
+/// }
+///
+/// async fn sign_up(Form(payload): Form<SignUp>) -> &'static str {
+///     // payload is a `SignUp`
+///     "Welcome!"
+/// }
+///
+/// let app = App::new().route("/sign-up", web::post().to(sign_up));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+#[must_use]
+pub struct Form<T>(pub T);
+
+impl<T> FromRequest for Form<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    T: 'static,
+{
+    type Error = FormRejection;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let content_type = check_form_content_type(req.headers());
+        let bytes = Bytes::from_request(req, payload);
+        Box::pin(async move {
+            content_type?;
+            let bytes = bytes.await.map_err(FormRejection::PayloadError)?;
+            Self::from_bytes(&bytes)
+        })
+    }
+}
+
+/// Check that the `Content-Type` header is set to `application/x-www-form-urlencoded`.
+///
+/// Return an error otherwise.
+fn check_form_content_type(headers: &HeaderMap) -> Result<(), FormRejection> {
+    let Some(content_type) = headers.get(header::CONTENT_TYPE) else {
+        return Err(MissingFormContentType.into());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(MissingFormContentType.into());
+    };
+
+    let is_form_content_type = content_type
+        .parse::<mime::Mime>()
+        .is_ok_and(|mime| mime.essence_str() == mime::APPLICATION_WWW_FORM_URLENCODED.as_ref());
+    if !is_form_content_type {
+        return Err(FormContentTypeMismatch {
+            actual: content_type.to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+impl<T> Deref for Form<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Form<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Form<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+impl<T> Form<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Construct a `Form<T>` from a byte slice. Most users should prefer to use the `FromRequest` impl
+    /// but special cases may require first extracting the body into `Bytes` then optionally
+    /// constructing a `Form<T>`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, FormRejection> {
+        match eserde::urlencoded::from_bytes(bytes) {
+            Ok(value) => Ok(Form(value)),
+            Err(errors) => Err(FormError(errors).into()),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+/// Rejection type for [`Form`].
+///
+/// This rejection is used if the request body couldn't be deserialized
+/// into the target type.
+pub struct FormError(pub(crate) DeserializationErrors);
+
+impl ResponseError for FormError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        __log_rejection!(
+            target = "eserde_actix::form::rejection",
+            rejection_type = FormError,
+            status = self.status_code(),
+        );
+        problem_response(&ProblemDetails::invalid_request(
+            ValidationErrors::from_body(&self.0),
+        ))
+    }
+}
+
+impl std::fmt::Display for FormError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to deserialize the request form body into the target schema:\n")?;
+        for e in self.0.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for FormError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Debug, Default)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+/// Rejection type for [`Form`] used if the `Content-Type`
+/// header is missing.
+pub struct MissingFormContentType;
+
+impl ResponseError for MissingFormContentType {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        __log_rejection!(
+            target = "eserde_actix::form::rejection",
+            rejection_type = MissingFormContentType,
+            status = self.status_code(),
+        );
+        content_type_response(
+            "missing_header",
+            "Expected request with `Content-Type: application/x-www-form-urlencoded`, but no `Content-Type` header was found".into(),
+        )
+    }
+}
+
+impl std::fmt::Display for MissingFormContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/x-www-form-urlencoded`"
+        )
+    }
+}
+
+impl std::error::Error for MissingFormContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+/// Rejection type for [`Form`] used if the `Content-Type`
+/// header has an incorrect value.
+pub struct FormContentTypeMismatch {
+    pub(crate) actual: String,
+}
+
+impl ResponseError for FormContentTypeMismatch {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        __log_rejection!(
+            target = "eserde_actix::form::rejection",
+            rejection_type = FormContentTypeMismatch,
+            status = self.status_code(),
+        );
+        content_type_response("invalid_content_type", self.to_string())
+    }
+}
+
+impl std::fmt::Display for FormContentTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/x-www-form-urlencoded`, but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for FormContentTypeMismatch {}
+
+/// Rejection used for [`Form`].
+///
+/// Contains one variant for each way the [`Form`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+pub enum FormRejection {
+    #[allow(missing_docs)]
+    FormError(FormError),
+    #[allow(missing_docs)]
+    MissingFormContentType(MissingFormContentType),
+    #[allow(missing_docs)]
+    FormContentTypeMismatch(FormContentTypeMismatch),
+    /// The request body couldn't be buffered.
+    PayloadError(actix_web::Error),
+}
+
+impl ResponseError for FormRejection {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::FormError(inner) => inner.status_code(),
+            Self::MissingFormContentType(inner) => inner.status_code(),
+            Self::FormContentTypeMismatch(inner) => inner.status_code(),
+            Self::PayloadError(inner) => inner.as_response_error().status_code(),
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        match self {
+            Self::FormError(inner) => inner.error_response(),
+            Self::MissingFormContentType(inner) => inner.error_response(),
+            Self::FormContentTypeMismatch(inner) => inner.error_response(),
+            Self::PayloadError(inner) => payload_error_response(inner),
+        }
+    }
+}
+
+impl From<FormError> for FormRejection {
+    fn from(inner: FormError) -> Self {
+        Self::FormError(inner)
+    }
+}
+impl From<MissingFormContentType> for FormRejection {
+    fn from(inner: MissingFormContentType) -> Self {
+        Self::MissingFormContentType(inner)
+    }
+}
+impl From<FormContentTypeMismatch> for FormRejection {
+    fn from(inner: FormContentTypeMismatch) -> Self {
+        Self::FormContentTypeMismatch(inner)
+    }
+}
+impl std::fmt::Display for FormRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::FormError(inner) => write!(f, "{inner}"),
+            Self::MissingFormContentType(inner) => write!(f, "{inner}"),
+            Self::FormContentTypeMismatch(inner) => write!(f, "{inner}"),
+            Self::PayloadError(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for FormRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::FormError(inner) => inner.source(),
+            Self::MissingFormContentType(inner) => inner.source(),
+            Self::FormContentTypeMismatch(inner) => inner.source(),
+            Self::PayloadError(inner) => Some(inner),
+        }
+    }
+}
@@ -0,0 +1,351 @@
+//! Supporting types for the [`Json`] extractor.
+use std::future::Future;
+use std::ops::{Deref, DerefMut};
+use std::pin::Pin;
+
+use actix_web::body::BoxBody;
+use actix_web::dev::Payload;
+use actix_web::http::header::{self, HeaderMap};
+use actix_web::http::StatusCode;
+use actix_web::web::Bytes;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, Responder, ResponseError};
+use eserde::{DeserializationErrors, EDeserialize};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::details::{
+    __log_rejection, content_type_response, internal_server_error, payload_error_response,
+    problem_response, ProblemDetails, ValidationErrors,
+};
+
+/// JSON Extractor / Response.
+///
+/// When used as an extractor, it can deserialize request bodies into some type that
+/// implements [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`].
+/// The request will be rejected (and a [`JsonRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: application/json` (or similar) header.
+/// - The body doesn't contain syntactically valid JSON or it couldn't be deserialized into the target type.
+/// - Buffering the request body fails (e.g. because it exceeds the limit set via
+///   [`PayloadConfig`](actix_web::web::PayloadConfig)).
+///
+/// See [`JsonRejection`] for more details.
+///
+/// # Extractor example
+///
+/// ```rust,no_run
+/// use actix_web::{web, App};
+/// use eserde_actix::Json;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreateUser {
+///     email: String,
+///     password: String,
+/// }
+///
+/// async fn create_user(Json(payload): Json<CreateUser>) -> &'static str {
+///     // payload is a `CreateUser`
+///     "Created"
+/// }
+///
+/// let app = App::new().route("/users", web::post().to(create_user));
+/// ```
+///
+/// When used as a response, it can serialize any type that implements [`serde::Serialize`] to
+/// `JSON`, and will automatically set `Content-Type: application/json` header.
+///
+/// If the [`Serialize`] implementation decides to fail
+/// or if a map with non-string keys is used,
+/// a 500 response will be issued.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+#[must_use]
+pub struct Json<T>(pub T);
+
+impl<T> FromRequest for Json<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    T: 'static,
+{
+    type Error = JsonRejection;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+
+    fn from_request(req: &HttpRequest, payload: &mut Payload) -> Self::Future {
+        let content_type = check_json_content_type(req.headers());
+        let bytes = Bytes::from_request(req, payload);
+        Box::pin(async move {
+            content_type?;
+            let bytes = bytes.await.map_err(JsonRejection::PayloadError)?;
+            Self::from_bytes(&bytes)
+        })
+    }
+}
+
+/// Check that the `Content-Type` header is set to `application/json`, or another
+/// `application/*+json` MIME type.
+///
+/// Return an error otherwise.
+fn check_json_content_type(headers: &HeaderMap) -> Result<(), JsonRejection> {
+    let Some(content_type) = headers.get(header::CONTENT_TYPE) else {
+        return Err(MissingJsonContentType.into());
+    };
+    let Ok(content_type) = content_type.to_str() else {
+        return Err(MissingJsonContentType.into());
+    };
+
+    let Ok(mime) = content_type.parse::<mime::Mime>() else {
+        return Err(JsonContentTypeMismatch {
+            actual: content_type.to_string(),
+        }
+        .into());
+    };
+
+    let is_json_content_type = mime.type_() == "application"
+        && (mime.subtype() == "json" || mime.suffix().is_some_and(|name| name == "json"));
+    if !is_json_content_type {
+        return Err(JsonContentTypeMismatch {
+            actual: content_type.to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+impl<T> Deref for Json<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Json<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Json<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
+
+impl<T> Json<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Construct a `Json<T>` from a byte slice. Most users should prefer to use the `FromRequest` impl
+    /// but special cases may require first extracting the body into `Bytes` then optionally
+    /// constructing a `Json<T>`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, JsonRejection> {
+        match eserde::json::from_slice(bytes) {
+            Ok(value) => Ok(Json(value)),
+            Err(errors) => Err(JsonError(errors).into()),
+        }
+    }
+}
+
+impl<T> Responder for Json<T>
+where
+    T: Serialize,
+{
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        match serde_json::to_vec(&self.0) {
+            Ok(body) => HttpResponse::Ok()
+                .insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON.as_ref()))
+                .body(body),
+            Err(_) => internal_server_error(),
+        }
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`].
+///
+/// This rejection is used if the request body couldn't be deserialized
+/// into the target type.
+pub struct JsonError(pub(crate) DeserializationErrors);
+
+impl ResponseError for JsonError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        __log_rejection!(
+            target = "eserde_actix::json::rejection",
+            rejection_type = JsonError,
+            status = self.status_code(),
+        );
+        problem_response(&ProblemDetails::invalid_request(
+            ValidationErrors::from_body(&self.0),
+        ))
+    }
+}
+
+impl std::fmt::Display for JsonError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to deserialize the request JSON body into the target schema:\n")?;
+        for e in self.0.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for JsonError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+#[derive(Debug, Default)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`] used if the `Content-Type`
+/// header is missing.
+pub struct MissingJsonContentType;
+
+impl ResponseError for MissingJsonContentType {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        __log_rejection!(
+            target = "eserde_actix::json::rejection",
+            rejection_type = MissingJsonContentType,
+            status = self.status_code(),
+        );
+        content_type_response(
+            "missing_header",
+            "Expected request with `Content-Type: application/json`, but no `Content-Type` header was found".into(),
+        )
+    }
+}
+
+impl std::fmt::Display for MissingJsonContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Expected request with `Content-Type: application/json`")
+    }
+}
+
+impl std::error::Error for MissingJsonContentType {}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+/// Rejection type for [`Json`] used if the `Content-Type`
+/// header has an incorrect value.
+pub struct JsonContentTypeMismatch {
+    pub(crate) actual: String,
+}
+
+impl ResponseError for JsonContentTypeMismatch {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        __log_rejection!(
+            target = "eserde_actix::json::rejection",
+            rejection_type = JsonContentTypeMismatch,
+            status = self.status_code(),
+        );
+        content_type_response("invalid_content_type", self.to_string())
+    }
+}
+
+impl std::fmt::Display for JsonContentTypeMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: application/json` or `application/*+json`, but found `{}`",
+            self.actual
+        )
+    }
+}
+
+impl std::error::Error for JsonContentTypeMismatch {}
+
+/// Rejection used for [`Json`].
+///
+/// Contains one variant for each way the [`Json`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub enum JsonRejection {
+    #[allow(missing_docs)]
+    JsonError(JsonError),
+    #[allow(missing_docs)]
+    MissingJsonContentType(MissingJsonContentType),
+    #[allow(missing_docs)]
+    JsonContentTypeMismatch(JsonContentTypeMismatch),
+    /// The request body couldn't be buffered.
+    PayloadError(actix_web::Error),
+}
+
+impl ResponseError for JsonRejection {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::JsonError(inner) => inner.status_code(),
+            Self::MissingJsonContentType(inner) => inner.status_code(),
+            Self::JsonContentTypeMismatch(inner) => inner.status_code(),
+            Self::PayloadError(inner) => inner.as_response_error().status_code(),
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        match self {
+            Self::JsonError(inner) => inner.error_response(),
+            Self::MissingJsonContentType(inner) => inner.error_response(),
+            Self::JsonContentTypeMismatch(inner) => inner.error_response(),
+            Self::PayloadError(inner) => payload_error_response(inner),
+        }
+    }
+}
+
+impl From<JsonError> for JsonRejection {
+    fn from(inner: JsonError) -> Self {
+        Self::JsonError(inner)
+    }
+}
+impl From<MissingJsonContentType> for JsonRejection {
+    fn from(inner: MissingJsonContentType) -> Self {
+        Self::MissingJsonContentType(inner)
+    }
+}
+impl From<JsonContentTypeMismatch> for JsonRejection {
+    fn from(inner: JsonContentTypeMismatch) -> Self {
+        Self::JsonContentTypeMismatch(inner)
+    }
+}
+impl std::fmt::Display for JsonRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::JsonError(inner) => write!(f, "{inner}"),
+            Self::MissingJsonContentType(inner) => write!(f, "{inner}"),
+            Self::JsonContentTypeMismatch(inner) => write!(f, "{inner}"),
+            Self::PayloadError(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for JsonRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::JsonError(inner) => inner.source(),
+            Self::MissingJsonContentType(inner) => inner.source(),
+            Self::JsonContentTypeMismatch(inner) => inner.source(),
+            Self::PayloadError(inner) => Some(inner),
+        }
+    }
+}
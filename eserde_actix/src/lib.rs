@@ -0,0 +1,95 @@
+//! # eserde_actix
+//!
+//! A collection of [`actix-web`] extractors built on top of [`eserde`] to
+//! provide exhaustive error reports when deserialization fails.
+//! They are designed to be drop-in replacement for their official [`actix-web`]
+//! counterpart.
+//!
+//! Check out [`Json`] for working with JSON payloads, [`Query`] for query strings
+//! and [`Form`] for URL-encoded form bodies.
+//!
+//! Error responses follow the problem details specification,
+//! as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+//! They are built using [`eserde_problem`], so they have the same shape as
+//! the ones returned by [`eserde_axum`].
+//! The [`problem`] module exposes them, so that your own extractors can
+//! return the same shape.
+//!
+//! # Error responses
+//!
+//! ```rust
+//! use actix_web::{test::TestRequest, FromRequest, ResponseError};
+//! use eserde_actix::Query;
+//!
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct Pagination {
+//!     page: u32,
+//!     per_page: u32,
+//! }
+//!
+//! # actix_rt::System::new().block_on(async {
+//! let request = TestRequest::get().uri("/users?page=first").to_http_request();
+//! let rejection = Query::<Pagination>::extract(&request).await.unwrap_err();
+//! let response = rejection.error_response();
+//! assert_eq!(response.status(), 400);
+//!
+//! let body = actix_web::body::to_bytes(response.into_body()).await.unwrap();
+//! assert_eq!(
+//!     serde_json::from_slice::<serde_json::Value>(&body).unwrap(),
+//!     serde_json::json!({
+//!         "type": "invalid_request",
+//!         "status": 400,
+//!         "title": "The request is invalid",
+//!         "detail": "The request is either malformed or doesn't match the expected schema",
+//!         "errors": [
+//!             {
+//!                 "detail": "invalid digit found in string",
+//!                 "code": "invalid",
+//!                 "source": "query",
+//!                 "parameter": "page"
+//!             },
+//!             {
+//!                 "detail": "missing field `per_page`",
+//!                 "code": "missing_field",
+//!                 "source": "query",
+//!                 "parameter": "per_page"
+//!             }
+//!         ]
+//!     })
+//! );
+//! # });
+//! ```
+//!
+//! [`actix-web`]: https://docs.rs/actix-web
+//! [`eserde`]: https://docs.rs/eserde
+//! [`eserde_axum`]: https://docs.rs/eserde_axum
+#![deny(missing_docs)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub mod json;
+
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub use json::Json;
+
+#[cfg(feature = "urlencoded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+pub mod query;
+
+#[cfg(feature = "urlencoded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+pub use query::Query;
+
+#[cfg(feature = "urlencoded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+pub mod form;
+
+#[cfg(feature = "urlencoded")]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+pub use form::Form;
+
+pub mod problem;
+
+pub(crate) mod details;
@@ -0,0 +1,73 @@
+//! Problem details responses, as returned by this crate's extractors.
+//!
+//! The problem details types live in [`eserde_problem`], a framework-agnostic
+//! crate, and are re-exported here for convenience.
+//! Wrap them in a [`Problem`] to turn them into an `actix-web` error—e.g. to
+//! reject requests in your own extractors with the same response shape.
+//!
+//! See [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html) for more details.
+pub use eserde_problem::{
+    ProblemDetails, Source, ValidationError, ValidationErrors, APPLICATION_PROBLEM_JSON,
+};
+
+use actix_web::{body::BoxBody, http::StatusCode, HttpResponse, ResponseError};
+
+use crate::details::problem_response;
+
+/// A problem details response.
+///
+/// It serializes the wrapped [`ProblemDetails`] as `application/problem+json`,
+/// using its `status` as the response status code.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use actix_web::{web, App};
+/// use eserde_actix::problem::{Problem, ProblemDetails};
+///
+/// async fn get_balance() -> Result<String, Problem> {
+///     Err(ProblemDetails::new(403)
+///         .type_("https://example.com/probs/out-of-credit")
+///         .title("You do not have enough credit")
+///         .into())
+/// }
+///
+/// let app = App::new().route("/balance", web::get().to(get_balance));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Problem<Extension = ()>(pub ProblemDetails<Extension>);
+
+impl<Extension> From<ProblemDetails<Extension>> for Problem<Extension> {
+    fn from(details: ProblemDetails<Extension>) -> Self {
+        Self(details)
+    }
+}
+
+impl From<eserde::DeserializationErrors> for Problem<ValidationErrors> {
+    fn from(errors: eserde::DeserializationErrors) -> Self {
+        Self(errors.into())
+    }
+}
+
+impl<Extension> std::fmt::Display for Problem<Extension> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0.title)?;
+        if !self.0.detail.is_empty() {
+            write!(f, ": {}", self.0.detail)?;
+        }
+        Ok(())
+    }
+}
+
+impl<Extension> ResponseError for Problem<Extension>
+where
+    Extension: serde::Serialize + std::fmt::Debug,
+{
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.0.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        problem_response(&self.0)
+    }
+}
@@ -0,0 +1,175 @@
+//! Supporting types for the [`Query`] extractor.
+use std::future::{ready, Ready};
+use std::ops::{Deref, DerefMut};
+
+use actix_web::body::BoxBody;
+use actix_web::dev::Payload;
+use actix_web::http::StatusCode;
+use actix_web::{FromRequest, HttpRequest, HttpResponse, ResponseError};
+use eserde::{DeserializationErrors, EDeserialize};
+use serde::de::DeserializeOwned;
+
+use crate::details::{__log_rejection, problem_response, ProblemDetails, ValidationErrors};
+
+/// Query string extractor.
+///
+/// It deserializes the query string of the request into some type that
+/// implements [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`].
+/// The request will be rejected (and a [`QueryRejection`] will be returned) if the query
+/// string couldn't be deserialized into the target type.
+///
+/// Repeated keys (e.g. `tag=a&tag=b`) can be deserialized into a `Vec<T>`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use actix_web::{web, App};
+/// use eserde_actix::Query;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct Pagination {
+///     page: u32,
+///     per_page: u32,
+/// }
+///
+/// async fn list_users(Query(pagination): Query<Pagination>) -> String {
+///     format!("Page {} ({} per page)", pagination.page, pagination.per_page)
+/// }
+///
+/// let app = App::new().route("/users", web::get().to(list_users));
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+#[must_use]
+pub struct Query<T>(pub T);
+
+impl<T> FromRequest for Query<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    type Error = QueryRejection;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(Self::from_query(req.query_string()))
+    }
+}
+
+impl<T> Query<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Construct a `Query<T>` from a query string (without the leading `?`).
+    pub fn from_query(query: &str) -> Result<Self, QueryRejection> {
+        match eserde::urlencoded::from_str(query) {
+            Ok(value) => Ok(Query(value)),
+            Err(errors) => Err(QueryError(errors).into()),
+        }
+    }
+}
+
+impl<T> Deref for Query<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Query<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+/// Rejection type for [`Query`].
+///
+/// This rejection is used if the query string couldn't be deserialized
+/// into the target type.
+pub struct QueryError(pub(crate) DeserializationErrors);
+
+impl ResponseError for QueryError {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        __log_rejection!(
+            target = "eserde_actix::query::rejection",
+            rejection_type = QueryError,
+            status = self.status_code(),
+        );
+        problem_response(&ProblemDetails::invalid_request(
+            ValidationErrors::from_query(&self.0),
+        ))
+    }
+}
+
+impl std::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to deserialize the query string into the target schema:\n")?;
+        for e in self.0.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for QueryError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.0)
+    }
+}
+
+/// Rejection used for [`Query`].
+///
+/// Contains one variant for each way the [`Query`] extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "urlencoded")))]
+pub enum QueryRejection {
+    #[allow(missing_docs)]
+    QueryError(QueryError),
+}
+
+impl ResponseError for QueryRejection {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::QueryError(inner) => inner.status_code(),
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse<BoxBody> {
+        match self {
+            Self::QueryError(inner) => inner.error_response(),
+        }
+    }
+}
+
+impl From<QueryError> for QueryRejection {
+    fn from(inner: QueryError) -> Self {
+        Self::QueryError(inner)
+    }
+}
+impl std::fmt::Display for QueryRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::QueryError(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for QueryRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::QueryError(inner) => inner.source(),
+        }
+    }
+}
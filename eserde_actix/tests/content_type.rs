@@ -0,0 +1,112 @@
+#![cfg(all(feature = "json", feature = "urlencoded"))]
+use actix_web::{
+    http::header::CONTENT_TYPE, test::TestRequest, FromRequest, HttpResponse, ResponseError,
+};
+use eserde_actix::{Form, Json};
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Signup {
+    name: String,
+}
+
+/// The status code and the pretty-printed validation errors of a response.
+async fn into_parts(response: HttpResponse) -> (u16, String) {
+    let status = response.status().as_u16();
+    let body = actix_web::body::to_bytes(response.into_body())
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    (
+        status,
+        serde_json::to_string_pretty(&body["errors"]).unwrap(),
+    )
+}
+
+#[actix_rt::test]
+async fn test_json_missing_content_type() {
+    let (request, mut payload) = TestRequest::post()
+        .set_payload(r#"{"name": "Ada"}"#)
+        .to_http_parts();
+    let rejection = Json::<Signup>::from_request(&request, &mut payload)
+        .await
+        .unwrap_err();
+    let (status, errors) = into_parts(rejection.error_response()).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "missing_header",
+        "detail": "Expected request with `Content-Type: application/json`, but no `Content-Type` header was found",
+        "name": "content-type",
+        "source": "header"
+      }
+    ]
+    "#);
+}
+
+#[actix_rt::test]
+async fn test_json_invalid_content_type() {
+    let (request, mut payload) = TestRequest::post()
+        .insert_header((CONTENT_TYPE, "text/plain"))
+        .set_payload(r#"{"name": "Ada"}"#)
+        .to_http_parts();
+    let rejection = Json::<Signup>::from_request(&request, &mut payload)
+        .await
+        .unwrap_err();
+    let (status, errors) = into_parts(rejection.error_response()).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "invalid_content_type",
+        "detail": "Expected request with `Content-Type: application/json` or `application/*+json`, but found `text/plain`",
+        "name": "content-type",
+        "source": "header"
+      }
+    ]
+    "#);
+}
+
+#[actix_rt::test]
+async fn test_form_missing_content_type() {
+    let (request, mut payload) = TestRequest::post().set_payload("name=Ada").to_http_parts();
+    let rejection = Form::<Signup>::from_request(&request, &mut payload)
+        .await
+        .unwrap_err();
+    let (status, errors) = into_parts(rejection.error_response()).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "missing_header",
+        "detail": "Expected request with `Content-Type: application/x-www-form-urlencoded`, but no `Content-Type` header was found",
+        "name": "content-type",
+        "source": "header"
+      }
+    ]
+    "#);
+}
+
+#[actix_rt::test]
+async fn test_form_invalid_content_type() {
+    let (request, mut payload) = TestRequest::post()
+        .insert_header((CONTENT_TYPE, "application/json"))
+        .set_payload("name=Ada")
+        .to_http_parts();
+    let rejection = Form::<Signup>::from_request(&request, &mut payload)
+        .await
+        .unwrap_err();
+    let (status, errors) = into_parts(rejection.error_response()).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "invalid_content_type",
+        "detail": "Expected request with `Content-Type: application/x-www-form-urlencoded`, but found `application/json`",
+        "name": "content-type",
+        "source": "header"
+      }
+    ]
+    "#);
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "urlencoded")]
+use actix_web::{http::header::CONTENT_TYPE, test, web, App};
+use eserde_actix::Form;
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct SignUp {
+    email: String,
+    age: u8,
+    #[serde(default)]
+    newsletter: bool,
+}
+
+async fn sign_up(Form(_): Form<SignUp>) -> &'static str {
+    "welcome"
+}
+
+/// The status code and the pretty-printed validation errors of the response.
+async fn respond(body: &'static str) -> (u16, String) {
+    let app = test::init_service(App::new().route("/", web::post().to(sign_up))).await;
+    let request = test::TestRequest::post()
+        .uri("/")
+        .insert_header((CONTENT_TYPE, "application/x-www-form-urlencoded"))
+        .set_payload(body)
+        .to_request();
+    let response = test::call_service(&app, request).await;
+    let status = response.status().as_u16();
+    if status != 400 {
+        return (status, String::new());
+    }
+    let body: serde_json::Value = test::read_body_json(response).await;
+    (
+        status,
+        serde_json::to_string_pretty(&body["errors"]).unwrap(),
+    )
+}
+
+#[actix_rt::test]
+async fn test_valid() {
+    let (status, _) = respond("email=ada%40example.com&age=36&newsletter=true").await;
+    assert_eq!(status, 200);
+}
+
+#[actix_rt::test]
+async fn test_invalid_form() {
+    let (status, errors) = respond("age=300&newsletter=maybe").await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "invalid",
+        "detail": "number too large to fit in target type",
+        "pointer": "/age",
+        "source": "body"
+      },
+      {
+        "code": "invalid",
+        "detail": "provided string was not `true` or `false`",
+        "pointer": "/newsletter",
+        "source": "body"
+      },
+      {
+        "code": "missing_field",
+        "detail": "missing field `email`",
+        "pointer": "",
+        "source": "body"
+      }
+    ]
+    "#);
+}
@@ -0,0 +1,98 @@
+#![cfg(feature = "json")]
+use actix_web::{http::header::CONTENT_TYPE, test, web, App};
+use eserde_actix::Json;
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct CreateOrder {
+    id: u32,
+    items: Vec<Item>,
+}
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Item {
+    sku: String,
+    quantity: u8,
+}
+
+async fn create_order(Json(_): Json<CreateOrder>) -> &'static str {
+    "created"
+}
+
+/// The status code and the pretty-printed validation errors of the response.
+async fn respond(body: &'static str) -> (u16, String) {
+    let app = test::init_service(App::new().route("/", web::post().to(create_order))).await;
+    let request = test::TestRequest::post()
+        .uri("/")
+        .insert_header((CONTENT_TYPE, "application/json"))
+        .set_payload(body)
+        .to_request();
+    let response = test::call_service(&app, request).await;
+    let status = response.status().as_u16();
+    if status != 400 {
+        return (status, String::new());
+    }
+    let body: serde_json::Value = test::read_body_json(response).await;
+    (
+        status,
+        serde_json::to_string_pretty(&body["errors"]).unwrap(),
+    )
+}
+
+#[actix_rt::test]
+async fn test_valid() {
+    let (status, _) = respond(r#"{"id": 1, "items": [{"sku": "A", "quantity": 2}]}"#).await;
+    assert_eq!(status, 200);
+}
+
+#[actix_rt::test]
+async fn test_invalid_body() {
+    let (status, errors) =
+        respond(r#"{"id": -1, "items": [{"sku": 1, "quantity": 300}, {"quantity": 1}]}"#).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "out_of_range",
+        "detail": "invalid value: integer `-1`, expected u32 at line 1 column 9",
+        "pointer": "/id",
+        "source": "body"
+      },
+      {
+        "code": "invalid_type",
+        "detail": "invalid type: integer `1`, expected a string at line 1 column 30",
+        "pointer": "/items/0/sku",
+        "source": "body"
+      },
+      {
+        "code": "out_of_range",
+        "detail": "invalid value: integer `300`, expected u8 at line 1 column 47",
+        "pointer": "/items/0/quantity",
+        "source": "body"
+      },
+      {
+        "code": "missing_field",
+        "detail": "missing field `sku`",
+        "pointer": "/items/1",
+        "source": "body"
+      }
+    ]
+    "#);
+}
+
+#[actix_rt::test]
+async fn test_syntax_error() {
+    let (status, errors) = respond(r#"{"id": 1,"#).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "invalid",
+        "detail": "EOF while parsing a value at line 1 column 9",
+        "pointer": "",
+        "source": "body"
+      }
+    ]
+    "#);
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "urlencoded")]
+use actix_web::{test, web, App};
+use eserde_actix::Query;
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct Pagination {
+    page: u32,
+    per_page: u32,
+    #[serde(default)]
+    tag: Vec<String>,
+}
+
+async fn list_users(Query(_): Query<Pagination>) -> &'static str {
+    "users"
+}
+
+/// The status code and the pretty-printed validation errors of the response.
+async fn respond(uri: &str) -> (u16, String) {
+    let app = test::init_service(App::new().route("/users", web::get().to(list_users))).await;
+    let request = test::TestRequest::get().uri(uri).to_request();
+    let response = test::call_service(&app, request).await;
+    let status = response.status().as_u16();
+    if status != 400 {
+        return (status, String::new());
+    }
+    let body: serde_json::Value = test::read_body_json(response).await;
+    (
+        status,
+        serde_json::to_string_pretty(&body["errors"]).unwrap(),
+    )
+}
+
+#[actix_rt::test]
+async fn test_valid() {
+    let (status, _) = respond("/users?page=1&per_page=10&tag=a&tag=b").await;
+    assert_eq!(status, 200);
+}
+
+#[actix_rt::test]
+async fn test_invalid_parameters() {
+    let (status, errors) = respond("/users?page=first&per_page=-1").await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "invalid",
+        "detail": "invalid digit found in string",
+        "parameter": "page",
+        "source": "query"
+      },
+      {
+        "code": "invalid",
+        "detail": "invalid digit found in string",
+        "parameter": "per_page",
+        "source": "query"
+      }
+    ]
+    "#);
+}
+
+#[actix_rt::test]
+async fn test_missing_parameters() {
+    let (status, errors) = respond("/users?tag=a").await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "missing_field",
+        "detail": "missing field `page`",
+        "parameter": "page",
+        "source": "query"
+      },
+      {
+        "code": "missing_field",
+        "detail": "missing field `per_page`",
+        "parameter": "per_page",
+        "source": "query"
+      }
+    ]
+    "#);
+}
@@ -0,0 +1,201 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use super::*;
+use axum_core::extract::{FromRequest, Request};
+use bytes::Bytes;
+use eserde::{DeserializationErrors, EDeserialize};
+use serde::de::DeserializeOwned;
+
+/// Content-negotiated body Extractor.
+///
+/// It deserializes request bodies into some type that implements
+/// [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`], using
+/// the format specified by the `Content-Type` header:
+///
+/// | Format     | `Content-Type`                                    | Feature |
+/// |------------|---------------------------------------------------|---------|
+/// | JSON       | `application/json`, `application/*+json`          | `body`  |
+/// | URL-encoded form | `application/x-www-form-urlencoded`         | `body`  |
+/// | TOML       | `application/toml`                                | `toml`  |
+/// | XML        | `application/xml`, `text/xml`, `application/*+xml` | `xml`  |
+///
+/// The request will be rejected (and a [`BodyRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type` header, or it doesn't match any of
+///   the supported formats.
+/// - The body couldn't be deserialized into the target type.
+/// - Buffering the request body fails.
+///
+/// ⚠️ Since deserializing the body requires consuming it, the `Body` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::Body;
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreateUser {
+///     email: String,
+///     password: String,
+/// }
+///
+/// // Accepts both JSON payloads and HTML form submissions.
+/// async fn create_user(Body(payload): Body<CreateUser>) {
+///     // payload is a `CreateUser`
+/// }
+///
+/// let app = Router::new().route("/users", post(create_user));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "body")))]
+pub struct Body<T>(pub T);
+
+impl<T, S> FromRequest<S> for Body<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = BodyRejection;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let content_type = req
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .map(|content_type| String::from_utf8_lossy(content_type.as_bytes()).into_owned());
+        let format = Format::negotiate(content_type.as_deref())?;
+        let bytes = Bytes::from_request(req, state).await?;
+        format.deserialize(&bytes)
+    }
+}
+
+impl<T> Body<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Construct a `Body<T>` from a byte slice, using the format specified by `content_type`.
+    /// Most users should prefer to use the `FromRequest` impl but special cases may require
+    /// first extracting a `Request` into `Bytes` then optionally constructing a `Body<T>`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use eserde_axum::{body::BodyRejection, Body};
+    ///
+    /// #[derive(eserde::Deserialize, Debug)]
+    /// struct Pagination {
+    ///     page: u32,
+    /// }
+    ///
+    /// let Body(p) = Body::<Pagination>::from_bytes("application/json", br#"{"page": 2}"#).unwrap();
+    /// assert_eq!(p.page, 2);
+    /// let Body(p) = Body::<Pagination>::from_bytes("application/x-www-form-urlencoded", b"page=3").unwrap();
+    /// assert_eq!(p.page, 3);
+    ///
+    /// let rejection = Body::<Pagination>::from_bytes("text/plain", b"page=3").unwrap_err();
+    /// let BodyRejection::UnsupportedContentType(rejection) = rejection else {
+    ///     panic!("Unexpected rejection: {rejection}");
+    /// };
+    /// assert_eq!(rejection.actual(), Some("text/plain"));
+    /// assert!(rejection.acceptable().contains(&"application/json"));
+    /// ```
+    pub fn from_bytes(content_type: &str, bytes: &[u8]) -> Result<Self, BodyRejection> {
+        Format::negotiate(Some(content_type))?.deserialize(bytes)
+    }
+}
+
+/// The formats supported by [`Body`].
+#[derive(Debug, Clone, Copy)]
+enum Format {
+    Json,
+    Form,
+    #[cfg(feature = "toml")]
+    Toml,
+    #[cfg(feature = "xml")]
+    Xml,
+}
+
+impl Format {
+    /// Pick the format matching the `Content-Type` of the request.
+    fn negotiate(content_type: Option<&str>) -> Result<Self, UnsupportedContentType> {
+        let unsupported = || UnsupportedContentType {
+            actual: content_type.map(str::to_owned),
+        };
+        let mime: mime::Mime = content_type
+            .ok_or_else(unsupported)?
+            .parse()
+            .map_err(|_| unsupported())?;
+        let (type_, subtype, suffix) = (
+            mime.type_().as_str(),
+            mime.subtype().as_str(),
+            mime.suffix().map(|suffix| suffix.as_str()),
+        );
+        let format = match (type_, subtype, suffix) {
+            ("application", "json", _) | ("application", _, Some("json")) => Format::Json,
+            ("application", "x-www-form-urlencoded", _) => Format::Form,
+            #[cfg(feature = "toml")]
+            ("application", "toml", _) => Format::Toml,
+            #[cfg(feature = "xml")]
+            ("application" | "text", "xml", _) | ("application", _, Some("xml")) => Format::Xml,
+            _ => return Err(unsupported()),
+        };
+        Ok(format)
+    }
+
+    fn deserialize<T>(self, bytes: &[u8]) -> Result<Body<T>, BodyRejection>
+    where
+        T: DeserializeOwned,
+        T: for<'de> EDeserialize<'de>,
+    {
+        let outcome: Result<T, DeserializationErrors> = match self {
+            Format::Json => eserde::json::from_slice(bytes),
+            Format::Form => eserde::urlencoded::from_bytes(bytes),
+            #[cfg(feature = "toml")]
+            Format::Toml => eserde::toml::from_str(std::str::from_utf8(bytes)?),
+            #[cfg(feature = "xml")]
+            Format::Xml => eserde::xml::from_str(std::str::from_utf8(bytes)?),
+        };
+        match outcome {
+            Ok(value) => Ok(Body(value)),
+            Err(errors) => Err(BodyError::new(errors).into()),
+        }
+    }
+}
+
+/// The `Content-Type`s accepted by [`Body`], in order of preference.
+pub(crate) const ACCEPTABLE: &[&str] = &[
+    "application/json",
+    "application/x-www-form-urlencoded",
+    #[cfg(feature = "toml")]
+    "application/toml",
+    #[cfg(feature = "xml")]
+    "application/xml",
+];
+
+impl<T> Deref for Body<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Body<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Body<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
@@ -0,0 +1,7 @@
+//! Supporting types for the [`Body`] extractor.
+mod body_;
+mod rejections;
+
+#[doc(hidden)]
+pub use body_::Body;
+pub use rejections::*;
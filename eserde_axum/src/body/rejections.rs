@@ -0,0 +1,257 @@
+use axum_core::extract::rejection::BytesRejection;
+use eserde::DeserializationErrors;
+use http::{header::CONTENT_TYPE, StatusCode};
+
+use super::body_::ACCEPTABLE;
+use crate::details::{
+    bytes_rejection_response, problem_response, InvalidRequest, ProblemDetails, Source,
+    ValidationError, ValidationErrors,
+};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::body::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "body")))]
+/// Rejection type for [`Body`](super::Body).
+///
+/// This rejection is used if the request body couldn't be deserialized
+/// into the target type.
+pub struct BodyError {
+    errors: DeserializationErrors,
+}
+
+impl BodyError {
+    pub(crate) fn new(errors: DeserializationErrors) -> Self {
+        Self { errors }
+    }
+
+    /// The errors that occurred while deserializing the request body.
+    pub fn errors(&self) -> &DeserializationErrors {
+        &self.errors
+    }
+}
+
+impl axum_core::response::IntoResponse for BodyError {
+    fn into_response(self) -> axum_core::response::Response {
+        let response = InvalidRequest::new(ValidationErrors::from_body(&self.errors));
+        __log_rejection!(
+            rejection_type = BodyError,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for BodyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to deserialize the request body into the target schema:\n")?;
+        for e in self.errors.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for BodyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.errors)
+    }
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "body")))]
+/// Rejection type for [`Body`](super::Body) used if the `Content-Type`
+/// header is missing, or it doesn't match any of the supported formats.
+///
+/// The response has status `415 Unsupported Media Type`, and it lists the
+/// acceptable content types in the `acceptable` member.
+pub struct UnsupportedContentType {
+    pub(crate) actual: Option<String>,
+}
+
+impl UnsupportedContentType {
+    /// The value of the `Content-Type` header, if there was one.
+    pub fn actual(&self) -> Option<&str> {
+        self.actual.as_deref()
+    }
+
+    /// The content types accepted by the [`Body`](super::Body) extractor.
+    ///
+    /// They depend on the enabled features.
+    pub fn acceptable(&self) -> &'static [&'static str] {
+        ACCEPTABLE
+    }
+
+    fn detail(&self) -> String {
+        let acceptable = ACCEPTABLE
+            .iter()
+            .map(|content_type| format!("`{content_type}`"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        match &self.actual {
+            Some(actual) => format!(
+                "Expected request with `Content-Type` set to one of {acceptable}, but found `{actual}`"
+            ),
+            None => format!(
+                "Expected request with `Content-Type` set to one of {acceptable}, but no `Content-Type` header was found"
+            ),
+        }
+    }
+}
+
+impl axum_core::response::IntoResponse for UnsupportedContentType {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = StatusCode::UNSUPPORTED_MEDIA_TYPE;
+        let detail = self.detail();
+        let details = ProblemDetails::new(status.as_u16())
+            .type_("unsupported_media_type")
+            .title("The content type is not supported")
+            .detail(detail.clone())
+            .member("acceptable", ACCEPTABLE.into())
+            .extensions(ValidationErrors {
+                errors: vec![ValidationError {
+                    detail,
+                    code: "unsupported_media_type".into(),
+                    source: Source::Header {
+                        name: CONTENT_TYPE.as_str().into(),
+                    },
+                }],
+            });
+        __log_rejection!(rejection_type = UnsupportedContentType, status = status,);
+        problem_response(details)
+    }
+}
+
+impl std::fmt::Display for UnsupportedContentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.detail())
+    }
+}
+
+impl std::error::Error for UnsupportedContentType {}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "body")))]
+/// Rejection type for [`Body`](super::Body) used if the body of a request
+/// in a text-based format isn't valid UTF-8.
+pub struct InvalidUtf8 {
+    pub(crate) inner: std::str::Utf8Error,
+}
+
+impl axum_core::response::IntoResponse for InvalidUtf8 {
+    fn into_response(self) -> axum_core::response::Response {
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![ValidationError {
+                detail: self.to_string(),
+                code: "invalid_utf8".into(),
+                source: Source::Body { pointer: None },
+            }],
+        });
+        __log_rejection!(
+            rejection_type = InvalidUtf8,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "The request body is not valid UTF-8: {}", self.inner)
+    }
+}
+
+impl std::error::Error for InvalidUtf8 {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.inner)
+    }
+}
+
+/// Rejection used for [`Body`](super::Body).
+///
+/// Contains one variant for each way the [`Body`](super::Body) extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "body")))]
+pub enum BodyRejection {
+    #[allow(missing_docs)]
+    BodyError(BodyError),
+    #[allow(missing_docs)]
+    UnsupportedContentType(UnsupportedContentType),
+    #[allow(missing_docs)]
+    InvalidUtf8(InvalidUtf8),
+    #[allow(missing_docs)]
+    BytesRejection(BytesRejection),
+}
+
+impl axum_core::response::IntoResponse for BodyRejection {
+    fn into_response(self) -> axum_core::response::Response {
+        match self {
+            Self::BodyError(inner) => inner.into_response(),
+            Self::UnsupportedContentType(inner) => inner.into_response(),
+            Self::InvalidUtf8(inner) => inner.into_response(),
+            Self::BytesRejection(inner) => bytes_rejection_response(inner),
+        }
+    }
+}
+
+impl From<BodyError> for BodyRejection {
+    fn from(inner: BodyError) -> Self {
+        Self::BodyError(inner)
+    }
+}
+impl From<UnsupportedContentType> for BodyRejection {
+    fn from(inner: UnsupportedContentType) -> Self {
+        Self::UnsupportedContentType(inner)
+    }
+}
+impl From<std::str::Utf8Error> for BodyRejection {
+    fn from(inner: std::str::Utf8Error) -> Self {
+        Self::InvalidUtf8(InvalidUtf8 { inner })
+    }
+}
+impl From<BytesRejection> for BodyRejection {
+    fn from(inner: BytesRejection) -> Self {
+        Self::BytesRejection(inner)
+    }
+}
+impl std::fmt::Display for BodyRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::BodyError(inner) => write!(f, "{inner}"),
+            Self::UnsupportedContentType(inner) => write!(f, "{inner}"),
+            Self::InvalidUtf8(inner) => write!(f, "{inner}"),
+            Self::BytesRejection(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for BodyRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::BodyError(inner) => inner.source(),
+            Self::UnsupportedContentType(inner) => inner.source(),
+            Self::InvalidUtf8(inner) => inner.source(),
+            Self::BytesRejection(inner) => inner.source(),
+        }
+    }
+}
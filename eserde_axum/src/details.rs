@@ -1,96 +1,71 @@
-//! Types to represent a problem detail error response.
+//! `axum` glue for the problem detail types provided by [`eserde_problem`].
 //!
 //! See [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html) for more details.
-use std::borrow::Cow;
-
 use bytes::{BufMut, BytesMut};
 use http::{header::CONTENT_TYPE, HeaderName, HeaderValue, StatusCode};
 
-#[derive(serde::Serialize)]
-pub(crate) struct ProblemDetails<Extension> {
-    #[serde(rename = "type")]
-    pub(crate) type_: Cow<'static, str>,
-    pub(crate) status: u16,
-    pub(crate) title: Cow<'static, str>,
-    pub(crate) detail: Cow<'static, str>,
-    #[serde(flatten)]
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) extensions: Option<Extension>,
-}
-
-#[derive(serde::Serialize)]
-pub(crate) struct ValidationErrors {
-    pub(crate) errors: Vec<ValidationError>,
-}
-
-#[derive(serde::Serialize)]
-pub(crate) struct ValidationError {
-    pub(crate) detail: String,
-    #[serde(flatten)]
-    pub(crate) source: Source,
-}
-
-/// The request part where the problem occurred.
-#[derive(serde::Serialize)]
-#[serde(tag = "source", rename_all = "snake_case")]
-pub(crate) enum Source {
-    Body {
-        /// A [JSON pointer](https://www.rfc-editor.org/info/rfc6901) targeted
-        /// at the problematic body property.
-        pointer: Option<String>,
-    },
-    Header {
-        /// The name of the problematic header.
-        name: Cow<'static, str>,
-    },
-}
+pub(crate) use eserde_problem::ProblemDetails;
+#[cfg(any(
+    feature = "json",
+    feature = "body",
+    feature = "headers",
+    feature = "multipart"
+))]
+pub(crate) use eserde_problem::{Source, ValidationError, ValidationErrors};
 
-impl<Extension> axum_core::response::IntoResponse for ProblemDetails<Extension>
+/// Serialize a problem details object into an `application/problem+json` response.
+pub(crate) fn problem_response<Extension>(
+    details: ProblemDetails<Extension>,
+) -> axum_core::response::Response
 where
     Extension: serde::Serialize,
 {
-    fn into_response(self) -> axum_core::response::Response {
-        // Use a small initial capacity of 128 bytes like serde_json::to_vec
-        // https://docs.rs/serde_json/1.0.82/src/serde_json/ser.rs.html#2189
-        let mut buf = BytesMut::with_capacity(128).writer();
-        match serde_json::to_writer(&mut buf, &self) {
-            Ok(()) => (
+    use axum_core::response::IntoResponse;
+
+    // Use a small initial capacity of 128 bytes like serde_json::to_vec
+    // https://docs.rs/serde_json/1.0.82/src/serde_json/ser.rs.html#2189
+    let mut buf = BytesMut::with_capacity(128).writer();
+    match serde_json::to_writer(&mut buf, &details) {
+        Ok(()) => {
+            let status =
+                StatusCode::from_u16(details.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+            (
+                status,
                 [(CONTENT_TYPE, APPLICATION_PROBLEM_JSON)],
                 buf.into_inner().freeze(),
             )
-                .into_response(),
-            Err(_) => INTERNAL_SERVER_ERROR.into_response(),
+                .into_response()
         }
+        Err(_) => INTERNAL_SERVER_ERROR.into_response(),
     }
 }
 
 pub(crate) const APPLICATION_PROBLEM_JSON: HeaderValue =
-    HeaderValue::from_static("application/problem+json");
+    HeaderValue::from_static(eserde_problem::APPLICATION_PROBLEM_JSON);
 
 pub(crate) const INTERNAL_SERVER_ERROR: (StatusCode, [(HeaderName, HeaderValue); 1], &[u8]) = (
     StatusCode::INTERNAL_SERVER_ERROR,
     [(CONTENT_TYPE, APPLICATION_PROBLEM_JSON)],
-    INTERNAL_SERVER_ERROR_PROBLEM,
+    eserde_problem::INTERNAL_SERVER_ERROR_PROBLEM,
 );
 
-pub(crate) const INTERNAL_SERVER_ERROR_PROBLEM: &[u8] = br#"{
-    "type": "internal_server_error",
-    "title": "Internal Server Error",
-    "detail": "Something went wrong when processing your request. Please try again later."
-    "status": 500
-}"#;
-
+#[cfg(any(
+    feature = "json",
+    feature = "body",
+    feature = "headers",
+    feature = "multipart"
+))]
 pub(crate) struct InvalidRequest(ProblemDetails<ValidationErrors>);
 
+#[cfg(any(
+    feature = "json",
+    feature = "body",
+    feature = "headers",
+    feature = "multipart"
+))]
 impl InvalidRequest {
     pub(crate) fn new(errors: ValidationErrors) -> Self {
-        Self(ProblemDetails {
-            type_: "invalid_request".into(),
-            status: Self::status().as_u16(),
-            title: "The request is invalid".into(),
-            extensions: Some(errors),
-            detail: "The request is either malformed or doesn't match the expected schema".into(),
-        })
+        Self(ProblemDetails::invalid_request(errors))
     }
 
     pub(crate) fn status() -> StatusCode {
@@ -102,8 +77,77 @@ impl InvalidRequest {
     }
 }
 
+#[cfg(any(
+    feature = "json",
+    feature = "body",
+    feature = "headers",
+    feature = "multipart"
+))]
 impl axum_core::response::IntoResponse for InvalidRequest {
     fn into_response(self) -> axum_core::response::Response {
-        self.into_inner().into_response()
+        problem_response(self.into_inner())
+    }
+}
+
+/// The preferred locale of the client, according to the `Accept-Language` header—e.g. `it`
+/// for `it;q=0.9, en;q=0.8`.
+///
+/// Returns `None` if the header is missing or only contains wildcards.
+#[cfg(feature = "json")]
+pub(crate) fn preferred_locale(headers: &http::HeaderMap) -> Option<String> {
+    let mut preferred: Option<(&str, f32)> = None;
+    for value in headers.get_all(http::header::ACCEPT_LANGUAGE) {
+        let Ok(value) = value.to_str() else {
+            continue;
+        };
+        for entry in value.split(',') {
+            let mut parts = entry.split(';').map(str::trim);
+            let tag = parts.next().unwrap_or_default();
+            if tag.is_empty() || tag == "*" {
+                continue;
+            }
+            let quality = parts
+                .find_map(|param| param.strip_prefix("q="))
+                .map_or(Some(1.0), |q| q.parse::<f32>().ok())
+                .unwrap_or(0.0);
+            // Ties are broken in favour of the first entry.
+            if quality > 0.0 && preferred.is_none_or(|(_, best)| quality > best) {
+                preferred = Some((tag, quality));
+            }
+        }
+    }
+    preferred.map(|(tag, _)| tag.to_owned())
+}
+
+/// Convert a failure to buffer the request body into a problem details response.
+#[cfg(any(feature = "json", feature = "body"))]
+pub(crate) fn bytes_rejection_response(
+    rejection: axum_core::extract::rejection::BytesRejection,
+) -> axum_core::response::Response {
+    use axum_core::extract::rejection::{BytesRejection, FailedToBufferBody};
+    use axum_core::response::IntoResponse;
+
+    let mut response = None;
+    #[allow(clippy::single_match)]
+    match rejection {
+        BytesRejection::FailedToBufferBody(failed_to_buffer_body) => match failed_to_buffer_body {
+            FailedToBufferBody::LengthLimitError(length_limit_error) => {
+                let details = ProblemDetails::new(length_limit_error.status().as_u16())
+                    .type_("content_too_large")
+                    .title("The content is too large")
+                    .detail(length_limit_error.body_text());
+                response = Some(problem_response(details));
+            }
+            FailedToBufferBody::UnknownBodyError(unknown_body_error) => {
+                let details = ProblemDetails::new(unknown_body_error.status().as_u16())
+                    .type_("body_buffering_error")
+                    .title("Failed to buffer the body")
+                    .detail(unknown_body_error.body_text());
+                response = Some(problem_response(details));
+            }
+            _ => {}
+        },
+        _ => {}
     }
+    response.unwrap_or_else(|| INTERNAL_SERVER_ERROR.into_response())
 }
@@ -0,0 +1,116 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use super::*;
+use axum_core::extract::FromRequestParts;
+use eserde::EDeserialize;
+use http::{request::Parts, HeaderMap};
+use serde::de::DeserializeOwned;
+
+/// Headers Extractor.
+///
+/// It deserializes the request headers into some type that implements
+/// [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`].
+/// Each field of the target type maps to a header with the same name.
+///
+/// The request will be rejected (and a [`HeadersRejection`] will be returned) if
+/// any of the headers is missing or can't be deserialized into the type of the
+/// corresponding field.
+/// All problematic headers are reported at once.
+///
+/// # Naming
+///
+/// Header names are case-insensitive, but they are always exposed in lowercase.
+/// Use `#[serde(rename_all = "kebab-case")]` (or `#[serde(rename = "...")]` on
+/// individual fields) to map field names to header names—e.g. `x_request_id`
+/// to `x-request-id`.
+///
+/// # Values
+///
+/// Header values are parsed into numbers, booleans and characters when the
+/// target type requires it.
+/// Use a sequence (e.g. `Vec<T>`) for headers that can be sent multiple times:
+/// each occurrence of the header becomes an element.
+/// Use `Option<T>` for optional headers.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use eserde_axum::Headers;
+///
+/// #[derive(eserde::Deserialize)]
+/// #[serde(rename_all = "kebab-case")]
+/// struct Tracing {
+///     x_request_id: String,
+///     x_retry_attempt: Option<u8>,
+/// }
+///
+/// async fn list_users(Headers(tracing): Headers<Tracing>) {
+///     // tracing is a `Tracing`
+/// }
+///
+/// let app = Router::new().route("/users", get(list_users));
+/// # let _: Router = app;
+/// ```
+///
+/// See [`HeadersRejection`] for more details on the error response.
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "headers")))]
+pub struct Headers<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for Headers<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = HeadersRejection;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Self::from_headers(&parts.headers)
+    }
+}
+
+impl<T> Headers<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+{
+    /// Construct a `Headers<T>` from a header map. Most users should prefer to use the
+    /// `FromRequestParts` impl, but special cases may require deserializing headers
+    /// that don't come from the incoming request.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Self, HeadersRejection> {
+        // Headers are mapped to a URL-encoded form, to reuse its handling of
+        // scalar values and repeated keys.
+        let mut form = form_urlencoded::Serializer::new(String::new());
+        for (name, value) in headers {
+            form.append_pair(name.as_str(), &String::from_utf8_lossy(value.as_bytes()));
+        }
+        let form = form.finish();
+        match eserde::urlencoded::from_str(&form) {
+            Ok(value) => Ok(Headers(value)),
+            Err(errors) => Err(HeadersRejection::new(errors)),
+        }
+    }
+}
+
+impl<T> Deref for Headers<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Headers<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Headers<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
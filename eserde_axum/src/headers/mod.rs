@@ -0,0 +1,7 @@
+//! Supporting types for the [`Headers`] extractor.
+mod headers_;
+mod rejections;
+
+#[doc(hidden)]
+pub use headers_::Headers;
+pub use rejections::*;
@@ -0,0 +1,148 @@
+use eserde::{path::Segment, DeserializationError, DeserializationErrors};
+
+use crate::details::{
+    InvalidRequest, Source, ValidationError, ValidationErrors, INTERNAL_SERVER_ERROR,
+};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::headers::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "headers")))]
+/// Rejection type for [`Headers`](super::Headers).
+///
+/// This rejection is used if the request headers couldn't be deserialized
+/// into the target type—e.g. a header is missing or its value is malformed.
+///
+/// The response is a problem details object, as outlined in
+/// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html), with one
+/// `"source": "header"` entry for each problematic header.
+///
+/// Errors that can't be traced back to a specific header (e.g. because the
+/// target type isn't a struct) point at a mistake on the server side: they
+/// result in a `500 Internal Server Error` response.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::http::HeaderMap;
+/// use eserde_axum::Headers;
+///
+/// #[derive(eserde::Deserialize, Debug)]
+/// #[serde(rename_all = "kebab-case")]
+/// struct Tracing {
+///     x_request_id: String,
+///     x_retry_attempt: Option<u8>,
+/// }
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("x-retry-attempt", "many".parse().unwrap());
+///
+/// let rejection = Headers::<Tracing>::from_headers(&headers).unwrap_err();
+/// assert_eq!(
+///     rejection.to_string(),
+///     "Failed to deserialize the request headers into the target schema:
+/// - x-retry-attempt: invalid digit found in string
+/// - missing field `x-request-id`
+/// "
+/// );
+/// ```
+pub struct HeadersRejection {
+    errors: DeserializationErrors,
+}
+
+impl HeadersRejection {
+    pub(crate) fn new(errors: DeserializationErrors) -> Self {
+        Self { errors }
+    }
+
+    /// The errors that occurred while deserializing the request headers.
+    pub fn errors(&self) -> &DeserializationErrors {
+        &self.errors
+    }
+}
+
+impl axum_core::response::IntoResponse for HeadersRejection {
+    fn into_response(self) -> axum_core::response::Response {
+        let errors: Option<Vec<_>> = self.errors.iter().map(header_error).collect();
+        let Some(errors) = errors else {
+            tracing::error!(
+                target: "eserde_axum::headers::rejection",
+                error = %self,
+                "the request headers can't be deserialized into the target type",
+            );
+            return INTERNAL_SERVER_ERROR.into_response();
+        };
+        __log_rejection!(
+            rejection_type = HeadersRejection,
+            status = InvalidRequest::status(),
+        );
+        InvalidRequest::new(ValidationErrors { errors }).into_response()
+    }
+}
+
+/// Determine which header the error is about.
+///
+/// Returns `None` if the error can't be attributed to a specific header.
+fn header_error(error: &DeserializationError) -> Option<ValidationError> {
+    let first = error.path().and_then(|path| path.iter().next());
+    let (name, detail, code) = match first {
+        Some(Segment::Map { key }) => (
+            key.clone(),
+            error.message().to_owned(),
+            error.code().to_owned().into(),
+        ),
+        Some(_) => return None,
+        // Missing fields are reported against their parent, the header map.
+        None => {
+            let name = error.field()?;
+            // Keep custom messages and codes, replace the generic ones.
+            let detail = if error.message() == format!("missing field `{name}`") {
+                "missing header".to_owned()
+            } else {
+                error.message().to_owned()
+            };
+            let code = match error.code() {
+                "missing_field" => "missing_header".into(),
+                code => code.to_owned().into(),
+            };
+            (name.to_owned(), detail, code)
+        }
+    };
+    Some(ValidationError {
+        detail,
+        code,
+        source: Source::Header { name: name.into() },
+    })
+}
+
+impl std::fmt::Display for HeadersRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to deserialize the request headers into the target schema:\n")?;
+        for e in self.errors.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for HeadersRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.errors)
+    }
+}
@@ -0,0 +1,225 @@
+use std::sync::Arc;
+
+use axum_core::response::{IntoResponse, Response};
+use eserde::messages::MessageFormatter;
+
+use super::JsonRejection;
+
+/// Configuration for the [`Json`](super::Json) extractor.
+///
+/// Add it to your router as an [`Extension`](https://docs.rs/axum/latest/axum/struct.Extension.html)
+/// to customize the behaviour of all [`Json`](super::Json) extractors
+/// in the routes it applies to.
+/// If no configuration is found in the request extensions, the defaults are used.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{routing::post, Extension, Router};
+/// use eserde_axum::json::{JsonConfig, ProblemDetailsFormatter};
+///
+/// # async fn create_user() {}
+/// let app = Router::new()
+///     .route("/users", post(create_user))
+///     .layer(Extension(
+///         JsonConfig::new()
+///             .limit(64 * 1024)
+///             .max_errors(20)
+///             .rejection_formatter(ProblemDetailsFormatter),
+///     ));
+/// # let _: Router = app;
+/// ```
+#[derive(Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub struct JsonConfig {
+    pub(crate) limit: Option<usize>,
+    pub(crate) max_errors: Option<usize>,
+    pub(crate) formatter: Option<Arc<dyn JsonRejectionFormatter>>,
+    pub(crate) message_formatter: Option<Arc<dyn MessageFormatter>>,
+}
+
+impl JsonConfig {
+    /// The default configuration.
+    pub fn new() -> Self {
+        Self {
+            limit: None,
+            max_errors: None,
+            formatter: None,
+            message_formatter: None,
+        }
+    }
+
+    /// Reject request bodies larger than `limit` bytes, with a
+    /// `413 Content Too Large` response.
+    ///
+    /// The body is buffered incrementally: the request is rejected as soon as
+    /// the limit is exceeded, without reading the rest of the body.
+    ///
+    /// `axum`'s [`DefaultBodyLimit`](https://docs.rs/axum/latest/axum/extract/struct.DefaultBodyLimit.html)
+    /// still applies: this limit can only be used to lower it.
+    pub fn limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Stop collecting deserialization errors once `max_errors` have been found.
+    ///
+    /// `eserde` visits invalid payloads twice, the second time to collect all errors.
+    /// The budget bounds the amount of work performed on payloads with many errors
+    /// (e.g. attacker-controlled ones).
+    /// If the budget is exceeded, the response has type `too_many_errors` and
+    /// lists the first `max_errors` errors.
+    ///
+    /// `max_errors` is clamped to be at least 1.
+    pub fn max_errors(mut self, max_errors: usize) -> Self {
+        self.max_errors = Some(max_errors.max(1));
+        self
+    }
+
+    /// Use a custom formatter to build the response for rejected requests.
+    ///
+    /// See [`JsonRejectionFormatter`] for more details.
+    pub fn rejection_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: JsonRejectionFormatter,
+    {
+        self.formatter = Some(Arc::new(formatter));
+        self
+    }
+
+    /// Localize the messages of deserialization errors via `formatter`.
+    ///
+    /// The locale is the language the client prefers, according to the
+    /// `Accept-Language` header—e.g. `it` for `Accept-Language: it, en;q=0.8`.
+    /// If the header is missing, the original messages are kept.
+    ///
+    /// See [`MessageFormatter`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use axum::{routing::post, Extension, Router};
+    /// use eserde::messages::ErrorDescription;
+    /// use eserde_axum::json::JsonConfig;
+    ///
+    /// fn translate(error: &ErrorDescription<'_>, locale: &str) -> Option<String> {
+    ///     match (locale, error.code()) {
+    ///         ("it", "missing_field") => Some(format!("il campo `{}` è obbligatorio", error.field()?)),
+    ///         _ => None,
+    ///     }
+    /// }
+    ///
+    /// # async fn create_user() {}
+    /// let app = Router::new()
+    ///     .route("/users", post(create_user))
+    ///     .layer(Extension(JsonConfig::new().message_formatter(translate)));
+    /// # let _: Router = app;
+    /// ```
+    pub fn message_formatter<F>(mut self, formatter: F) -> Self
+    where
+        F: MessageFormatter,
+    {
+        self.message_formatter = Some(Arc::new(formatter));
+        self
+    }
+}
+
+impl Default for JsonConfig {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for JsonConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonConfig")
+            .field("limit", &self.limit)
+            .field("max_errors", &self.max_errors)
+            .field("formatter", &self.formatter.as_ref().map(|_| ".."))
+            .field(
+                "message_formatter",
+                &self.message_formatter.as_ref().map(|_| ".."),
+            )
+            .finish()
+    }
+}
+
+/// Build the response returned when the [`Json`](super::Json) extractor
+/// rejects a request.
+///
+/// By default, rejections are turned into problem details responses
+/// (see [`ProblemDetailsFormatter`]).
+/// Implement this trait, and register it via [`JsonConfig::rejection_formatter`],
+/// to use a different error envelope or different status codes.
+///
+/// # Example
+///
+/// A formatter that returns `{"code", "errors": [{"field", "message"}]}`, with
+/// `422 Unprocessable Entity` for schema errors.
+///
+/// ```rust
+/// use axum::{
+///     http::StatusCode,
+///     response::{IntoResponse, Response},
+///     routing::post,
+///     Extension, Router,
+/// };
+/// use eserde_axum::json::{JsonConfig, JsonRejection, JsonRejectionFormatter};
+///
+/// struct HouseFormatter;
+///
+/// impl JsonRejectionFormatter for HouseFormatter {
+///     fn format(&self, rejection: JsonRejection) -> Response {
+///         let JsonRejection::JsonError(error) = rejection else {
+///             // Keep the default response for everything else.
+///             return rejection.into_response();
+///         };
+///         let errors: Vec<_> = error
+///             .errors()
+///             .iter()
+///             .map(|e| {
+///                 serde_json::json!({
+///                     "field": e.path().map(|p| p.to_string()),
+///                     "message": e.message(),
+///                 })
+///             })
+///             .collect();
+///         let body = serde_json::json!({ "code": "invalid_payload", "errors": errors });
+///         (StatusCode::UNPROCESSABLE_ENTITY, axum::Json(body)).into_response()
+///     }
+/// }
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreateUser {
+///     email: String,
+/// }
+///
+/// # async fn create_user() {}
+/// let app = Router::new()
+///     .route("/users", post(create_user))
+///     .layer(Extension(JsonConfig::new().rejection_formatter(HouseFormatter)));
+/// # let _: Router = app;
+///
+/// let rejection = eserde_axum::Json::<CreateUser>::from_bytes(b"{}").err().unwrap();
+/// let response = HouseFormatter.format(rejection);
+/// assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub trait JsonRejectionFormatter: Send + Sync + 'static {
+    /// Build the response for a rejected request.
+    fn format(&self, rejection: JsonRejection) -> Response;
+}
+
+/// The default [`JsonRejectionFormatter`].
+///
+/// It returns problem details responses, as outlined in
+/// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub struct ProblemDetailsFormatter;
+
+impl JsonRejectionFormatter for ProblemDetailsFormatter {
+    fn format(&self, rejection: JsonRejection) -> Response {
+        rejection.into_response()
+    }
+}
@@ -1,15 +1,17 @@
 use std::ops::Deref;
 use std::ops::DerefMut;
 
-use crate::details::INTERNAL_SERVER_ERROR;
+use crate::details::{preferred_locale, INTERNAL_SERVER_ERROR};
 
 use super::*;
+use axum_core::body::Body;
 use axum_core::extract::FromRequest;
 use axum_core::extract::Request;
 use axum_core::response::{IntoResponse, Response};
 use bytes::{BufMut, Bytes, BytesMut};
-use eserde::EDeserialize;
+use eserde::{messages::with_formatter, EDeserialize};
 use http::header::{self, HeaderMap, HeaderValue};
+use http_body_util::Limited;
 use serde::{de::DeserializeOwned, Serialize};
 
 /// JSON Extractor / Response.
@@ -20,7 +22,8 @@ use serde::{de::DeserializeOwned, Serialize};
 ///
 /// - The request doesn't have a `Content-Type: application/json` (or similar) header.
 /// - The body doesn't contain syntactically valid JSON or it couldn't be deserialized into the target type.
-/// - Buffering the request body fails.
+/// - Buffering the request body fails, e.g. because it exceeds the
+///   [configured limit](JsonConfig::limit).
 ///
 /// ⚠️ Since parsing JSON requires consuming the request body, the `Json` extractor must be
 /// *last* if there are multiple extractors in a handler.
@@ -29,6 +32,8 @@ use serde::{de::DeserializeOwned, Serialize};
 /// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
 ///
 /// See [`JsonRejection`] for more details.
+/// The body size limit, the error budget, the language of error messages and
+/// the response returned for rejected requests can be customized via [`JsonConfig`].
 ///
 /// # Extractor example
 ///
@@ -102,9 +107,30 @@ where
     type Rejection = JsonRejection;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
-        check_json_content_type(req.headers())?;
-        let bytes = Bytes::from_request(req, state).await?;
-        Self::from_bytes(&bytes)
+        let config = req.extensions().get::<JsonConfig>().cloned();
+        let outcome = async {
+            check_json_content_type(req.headers())?;
+            let formatter = config.as_ref().and_then(|config| {
+                let formatter = config.message_formatter.clone()?;
+                Some((formatter, preferred_locale(req.headers())?))
+            });
+            let limit = config.as_ref().and_then(|config| config.limit);
+            let req = match limit {
+                Some(limit) => req.map(|body| Body::new(Limited::new(body, limit))),
+                None => req,
+            };
+            let bytes = Bytes::from_request(req, state).await?;
+            let max_errors = config.as_ref().and_then(|config| config.max_errors);
+            match formatter {
+                Some((formatter, locale)) => with_formatter(formatter, &locale, || {
+                    Self::from_bytes_with_max_errors(&bytes, max_errors)
+                }),
+                None => Self::from_bytes_with_max_errors(&bytes, max_errors),
+            }
+        };
+        outcome
+            .await
+            .map_err(|rejection| FormattedJsonRejection::wrap(rejection, config.as_ref()))
     }
 }
 
@@ -167,9 +193,20 @@ where
     /// but special cases may require first extracting a `Request` into `Bytes` then optionally
     /// constructing a `Json<T>`.
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, JsonRejection> {
-        match eserde::json::from_slice(bytes) {
+        Self::from_bytes_with_max_errors(bytes, None)
+    }
+
+    fn from_bytes_with_max_errors(
+        bytes: &[u8],
+        max_errors: Option<usize>,
+    ) -> Result<Self, JsonRejection> {
+        let outcome = match max_errors {
+            Some(max_errors) => eserde::json::from_slice_with_max_errors(bytes, max_errors),
+            None => eserde::json::from_slice(bytes),
+        };
+        match outcome {
             Ok(value) => Ok(Json(value)),
-            Err(errors) => Err(JsonError::new(errors).into()),
+            Err(errors) => Err(JsonError::new(errors, max_errors).into()),
         }
     }
 }
@@ -1,7 +1,9 @@
 //! Supporting types for the [`Json`] extractor.
+mod config;
 mod json_;
 mod rejections;
 
+pub use config::*;
 #[doc(hidden)]
 pub use json_::Json;
 pub use rejections::*;
@@ -1,10 +1,13 @@
-use axum_core::extract::rejection::{BytesRejection, FailedToBufferBody};
+use std::sync::Arc;
+
+use axum_core::extract::rejection::BytesRejection;
 use eserde::DeserializationErrors;
 use http::header::CONTENT_TYPE;
 
+use super::{JsonConfig, JsonRejectionFormatter};
 use crate::details::{
-    InvalidRequest, ProblemDetails, Source, ValidationError, ValidationErrors,
-    INTERNAL_SERVER_ERROR,
+    bytes_rejection_response, problem_response, InvalidRequest, ProblemDetails, Source,
+    ValidationError, ValidationErrors,
 };
 
 #[doc(hidden)]
@@ -31,46 +34,57 @@ macro_rules! __log_rejection {
 ///
 /// This rejection is used if the request body couldn't be deserialized
 /// into the target type.
-pub struct JsonError(pub(crate) DeserializationErrors);
+pub struct JsonError {
+    pub(crate) errors: DeserializationErrors,
+    pub(crate) max_errors: Option<usize>,
+}
 
 impl JsonError {
-    pub(crate) fn new(err: DeserializationErrors) -> Self {
-        Self(err)
+    pub(crate) fn new(errors: DeserializationErrors, max_errors: Option<usize>) -> Self {
+        Self { errors, max_errors }
+    }
+
+    /// The errors that occurred while deserializing the request body.
+    pub fn errors(&self) -> &DeserializationErrors {
+        &self.errors
+    }
+
+    /// Returns `true` if error collection stopped early, because the request body
+    /// has more errors than the budget set via [`JsonConfig::max_errors`].
+    pub fn is_truncated(&self) -> bool {
+        self.max_errors
+            .is_some_and(|max_errors| self.errors.len() > max_errors)
     }
 }
 
 impl axum_core::response::IntoResponse for JsonError {
     fn into_response(self) -> axum_core::response::Response {
-        let errors = self
-            .0
-            .iter()
-            .map(|e| {
-                let pointer = e.path().map(|path| {
-                    path.iter().fold(String::new(), |mut acc, part| {
-                        acc.push('/');
-                        acc.push_str(&part.to_string());
-                        acc
-                    })
-                });
-                ValidationError {
-                    detail: e.message().into(),
-                    source: Source::Body { pointer },
-                }
-            })
-            .collect();
-        let response = InvalidRequest::new(ValidationErrors { errors });
+        let response = match self.max_errors {
+            Some(max_errors) if self.is_truncated() => {
+                // Drop the trailing "too many errors" entry, the problem type conveys it.
+                let errors: Vec<_> = self.errors.into_iter().take(max_errors).collect();
+                ProblemDetails::new(InvalidRequest::status().as_u16())
+                    .type_("too_many_errors")
+                    .title("The request has too many errors")
+                    .detail(format!(
+                        "The request doesn't match the expected schema. Only the first {max_errors} errors are reported"
+                    ))
+                    .extensions(ValidationErrors::from(DeserializationErrors::from(errors)))
+            }
+            _ => InvalidRequest::new(ValidationErrors::from(self.errors)).into_inner(),
+        };
         __log_rejection!(
             rejection_type = JsonError,
             status = InvalidRequest::status(),
         );
-        response.into_response()
+        problem_response(response)
     }
 }
 
 impl std::fmt::Display for JsonError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str("Failed to deserialize the request JSON body into the target schema:\n")?;
-        for e in self.0.iter() {
+        for e in self.errors.iter() {
             writeln!(f, "- {}", e)?;
         }
         Ok(())
@@ -79,7 +93,7 @@ impl std::fmt::Display for JsonError {
 
 impl std::error::Error for JsonError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        Some(&self.0)
+        Some(&self.errors)
     }
 }
 
@@ -94,6 +108,7 @@ impl axum_core::response::IntoResponse for MissingJsonContentType {
     fn into_response(self) -> axum_core::response::Response {
         let error = ValidationError {
                     detail: "Expected request with `Content-Type: application/json`, but no `Content-Type` header was found".into(),
+                    code: "missing_header".into(),
                     source: Source::Header {
                         name: CONTENT_TYPE.as_str().into(),
                     },
@@ -130,6 +145,13 @@ pub struct JsonContentTypeMismatch {
     pub(crate) actual: String,
 }
 
+impl JsonContentTypeMismatch {
+    /// The value of the `Content-Type` header.
+    pub fn actual(&self) -> &str {
+        &self.actual
+    }
+}
+
 impl axum_core::response::IntoResponse for JsonContentTypeMismatch {
     fn into_response(self) -> axum_core::response::Response {
         let error = ValidationError {
@@ -137,6 +159,7 @@ impl axum_core::response::IntoResponse for JsonContentTypeMismatch {
                 "Expected request with `Content-Type: application/json` or `application/*+json`, but found `{}`",
                 self.actual
             ),
+            code: "invalid_content_type".into(),
             source: Source::Header {
                 name: CONTENT_TYPE.as_str().into(),
             },
@@ -183,6 +206,9 @@ pub enum JsonRejection {
     JsonContentTypeMismatch(JsonContentTypeMismatch),
     #[allow(missing_docs)]
     BytesRejection(BytesRejection),
+    /// A rejection that will be turned into a response by the
+    /// [`JsonRejectionFormatter`] set in [`JsonConfig`].
+    Formatted(FormattedJsonRejection),
 }
 impl axum_core::response::IntoResponse for JsonRejection {
     fn into_response(self) -> axum_core::response::Response {
@@ -190,39 +216,8 @@ impl axum_core::response::IntoResponse for JsonRejection {
             Self::JsonError(inner) => inner.into_response(),
             Self::MissingJsonContentType(inner) => inner.into_response(),
             Self::JsonContentTypeMismatch(inner) => inner.into_response(),
-            Self::BytesRejection(inner) => {
-                let mut response = None;
-                #[allow(clippy::single_match)]
-                match inner {
-                    BytesRejection::FailedToBufferBody(failed_to_buffer_body) => {
-                        match failed_to_buffer_body {
-                            FailedToBufferBody::LengthLimitError(length_limit_error) => {
-                                let details: ProblemDetails<()> = ProblemDetails {
-                                    type_: "content_too_large".into(),
-                                    status: length_limit_error.status().as_u16(),
-                                    title: "The content is too large".into(),
-                                    detail: length_limit_error.body_text().into(),
-                                    extensions: None,
-                                };
-                                response = Some(details.into_response());
-                            }
-                            FailedToBufferBody::UnknownBodyError(unknown_body_error) => {
-                                let details: ProblemDetails<()> = ProblemDetails {
-                                    type_: "body_buffering_error".into(),
-                                    status: unknown_body_error.status().as_u16(),
-                                    title: "Failed to buffer the body".into(),
-                                    detail: unknown_body_error.body_text().into(),
-                                    extensions: None,
-                                };
-                                response = Some(details.into_response());
-                            }
-                            _ => {}
-                        }
-                    }
-                    _ => {}
-                }
-                response.unwrap_or_else(|| INTERNAL_SERVER_ERROR.into_response())
-            }
+            Self::Formatted(inner) => inner.into_response(),
+            Self::BytesRejection(inner) => bytes_rejection_response(inner),
         }
     }
 }
@@ -254,6 +249,7 @@ impl std::fmt::Display for JsonRejection {
             Self::MissingJsonContentType(inner) => write!(f, "{inner}"),
             Self::JsonContentTypeMismatch(inner) => write!(f, "{inner}"),
             Self::BytesRejection(inner) => write!(f, "{inner}"),
+            Self::Formatted(inner) => write!(f, "{inner}"),
         }
     }
 }
@@ -264,6 +260,66 @@ impl std::error::Error for JsonRejection {
             Self::MissingJsonContentType(inner) => inner.source(),
             Self::JsonContentTypeMismatch(inner) => inner.source(),
             Self::BytesRejection(inner) => inner.source(),
+            Self::Formatted(inner) => inner.source(),
+        }
+    }
+}
+
+/// Rejection type for [`Json`](super::Json) used when a custom
+/// [`JsonRejectionFormatter`] has been set in [`JsonConfig`].
+///
+/// Its response is built by the formatter.
+#[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+pub struct FormattedJsonRejection {
+    rejection: Box<JsonRejection>,
+    formatter: Arc<dyn JsonRejectionFormatter>,
+}
+
+impl FormattedJsonRejection {
+    /// Apply the formatter in `config` (if any) to `rejection`.
+    pub(crate) fn wrap(rejection: JsonRejection, config: Option<&JsonConfig>) -> JsonRejection {
+        match config.and_then(|config| config.formatter.clone()) {
+            Some(formatter) => JsonRejection::Formatted(FormattedJsonRejection {
+                rejection: Box::new(rejection),
+                formatter,
+            }),
+            None => rejection,
         }
     }
+
+    /// The underlying rejection.
+    pub fn rejection(&self) -> &JsonRejection {
+        &self.rejection
+    }
+
+    /// Discard the formatter and return the underlying rejection.
+    pub fn into_rejection(self) -> JsonRejection {
+        *self.rejection
+    }
+}
+
+impl axum_core::response::IntoResponse for FormattedJsonRejection {
+    fn into_response(self) -> axum_core::response::Response {
+        self.formatter.format(*self.rejection)
+    }
+}
+
+impl std::fmt::Debug for FormattedJsonRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FormattedJsonRejection")
+            .field("rejection", &self.rejection)
+            .finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Display for FormattedJsonRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.rejection)
+    }
+}
+
+impl std::error::Error for FormattedJsonRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.rejection.source()
+    }
 }
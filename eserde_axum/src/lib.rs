@@ -6,6 +6,14 @@
 //! counterpart.
 //!
 //! Check out [`Json`] for working with JSON payloads.
+//! Enable the `headers` feature to work with request headers via `Headers`.
+//! Enable the `body` feature to work with endpoints that accept several
+//! content types (JSON, URL-encoded forms and, via the `toml` and `xml`
+//! features, TOML and XML) via `Body`.
+//! Enable the `multipart` feature to work with `multipart/form-data` payloads,
+//! including file uploads, via `Multipart`.
+//! The [`problem`] module exposes the problem details types used for error
+//! responses, so that your own extractors can return the same shape.
 //!
 //! [`axum`]: https://docs.rs/axum
 //! [`eserde`]: https://docs.rs/eserde
@@ -20,4 +28,30 @@ pub mod json;
 #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
 pub use json::Json;
 
+#[cfg(feature = "body")]
+#[cfg_attr(docsrs, doc(cfg(feature = "body")))]
+pub mod body;
+
+#[cfg(feature = "body")]
+#[cfg_attr(docsrs, doc(cfg(feature = "body")))]
+pub use body::Body;
+
+#[cfg(feature = "headers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "headers")))]
+pub mod headers;
+
+#[cfg(feature = "headers")]
+#[cfg_attr(docsrs, doc(cfg(feature = "headers")))]
+pub use headers::Headers;
+
+#[cfg(feature = "multipart")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub mod multipart;
+
+#[cfg(feature = "multipart")]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub use multipart::Multipart;
+
+pub mod problem;
+
 pub(crate) mod details;
@@ -0,0 +1,9 @@
+//! Supporting types for the [`Multipart`] extractor.
+mod multipart_;
+mod rejections;
+mod uploaded_file;
+
+#[doc(hidden)]
+pub use multipart_::Multipart;
+pub use rejections::*;
+pub use uploaded_file::UploadedFile;
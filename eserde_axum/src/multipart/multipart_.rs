@@ -0,0 +1,155 @@
+use std::ops::Deref;
+use std::ops::DerefMut;
+
+use super::uploaded_file::Uploads;
+use super::*;
+use axum_core::extract::{FromRequest, Request};
+use axum_core::RequestExt;
+use eserde::EDeserialize;
+use serde::de::DeserializeOwned;
+
+/// Multipart Extractor.
+///
+/// It deserializes `multipart/form-data` request bodies into some type that
+/// implements [`serde::de::DeserializeOwned`] and [`eserde::EDeserialize`].
+/// Each field of the target type maps to the form field with the same name.
+///
+/// - Text parts are parsed into numbers, booleans and characters when the
+///   target type requires it.
+/// - File parts (i.e. parts with a file name) must be deserialized into an
+///   [`UploadedFile`].
+///
+/// Use a sequence (e.g. `Vec<T>`) for fields that can be sent multiple times:
+/// each part becomes an element.
+/// Use `Option<T>` for optional fields.
+///
+/// The request will be rejected (and a [`MultipartRejection`] will be returned) if:
+///
+/// - The request doesn't have a `Content-Type: multipart/form-data` header, with a boundary.
+/// - The body isn't a well-formed multipart body.
+/// - Any of the parts couldn't be deserialized into the target type, or a file
+///   was uploaded for a field that isn't an [`UploadedFile`].
+///   All problematic fields are reported at once.
+///
+/// ⚠️ The whole body is buffered in memory before deserializing it.
+/// Its size is bounded by `axum`'s
+/// [`DefaultBodyLimit`](https://docs.rs/axum/latest/axum/extract/struct.DefaultBodyLimit.html).
+///
+/// ⚠️ Since parsing the form requires consuming the request body, the `Multipart` extractor must be
+/// *last* if there are multiple extractors in a handler.
+/// See ["the order of extractors"][order-of-extractors]
+///
+/// [order-of-extractors]: https://docs.rs/axum/latest/axum/extract/index.html#the-order-of-extractors
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use axum::{routing::post, Router};
+/// use eserde_axum::multipart::{Multipart, UploadedFile};
+///
+/// #[derive(eserde::Deserialize)]
+/// struct CreatePost {
+///     title: String,
+///     tags: Vec<String>,
+///     cover: Option<UploadedFile>,
+///     attachments: Vec<UploadedFile>,
+/// }
+///
+/// async fn create_post(Multipart(post): Multipart<CreatePost>) {
+///     // post is a `CreatePost`
+/// }
+///
+/// let app = Router::new().route("/posts", post(create_post));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub struct Multipart<T>(pub T);
+
+impl<T, S> FromRequest<S> for Multipart<T>
+where
+    T: DeserializeOwned,
+    T: for<'de> EDeserialize<'de>,
+    S: Send + Sync,
+{
+    type Rejection = MultipartRejection;
+
+    async fn from_request(req: Request, _state: &S) -> Result<Self, Self::Rejection> {
+        let boundary = req
+            .headers()
+            .get(http::header::CONTENT_TYPE)
+            .and_then(|content_type| content_type.to_str().ok())
+            .and_then(|content_type| multer::parse_boundary(content_type).ok())
+            .ok_or(InvalidBoundary)?;
+        let stream = req.with_limited_body().into_body().into_data_stream();
+        let mut multipart = multer::Multipart::new(stream, boundary);
+
+        let mut pairs = Vec::new();
+        let mut uploads = Uploads::new();
+        // The name of the form field each file was uploaded for.
+        let mut file_fields = Vec::new();
+        while let Some(field) = multipart
+            .next_field()
+            .await
+            .map_err(MalformedMultipart::new)?
+        {
+            let Some(name) = field.name().map(str::to_owned) else {
+                // Unnamed parts can't be mapped to any field.
+                continue;
+            };
+            match field.file_name().map(str::to_owned) {
+                Some(filename) => {
+                    let content_type = field.content_type().map(|mime| mime.to_string());
+                    let bytes = field.bytes().await.map_err(MalformedMultipart::new)?;
+                    let token = uploads.push(UploadedFile {
+                        filename,
+                        content_type,
+                        bytes,
+                    });
+                    pairs.push((name.clone(), token));
+                    file_fields.push(name);
+                }
+                None => {
+                    let text = field.text().await.map_err(MalformedMultipart::new)?;
+                    pairs.push((name, text));
+                }
+            }
+        }
+        // Parts are mapped to a URL-encoded form, to reuse its handling of
+        // scalar values and repeated keys.
+        let form = form_urlencoded::Serializer::new(String::new())
+            .extend_pairs(pairs)
+            .finish();
+
+        let (outcome, unclaimed) = uploads.scope(|| eserde::urlencoded::from_str::<T>(&form));
+        let unexpected_files: Vec<String> = unclaimed
+            .into_iter()
+            .map(|i| file_fields[i].clone())
+            .collect();
+        match outcome {
+            Ok(value) if unexpected_files.is_empty() => Ok(Multipart(value)),
+            Ok(_) => Err(MultipartError::new(Vec::new().into(), unexpected_files).into()),
+            Err(errors) => Err(MultipartError::new(errors, unexpected_files).into()),
+        }
+    }
+}
+
+impl<T> Deref for Multipart<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Multipart<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> From<T> for Multipart<T> {
+    fn from(inner: T) -> Self {
+        Self(inner)
+    }
+}
@@ -0,0 +1,272 @@
+use eserde::DeserializationErrors;
+use http::{header::CONTENT_TYPE, StatusCode};
+
+use crate::details::{
+    problem_response, InvalidRequest, ProblemDetails, Source, ValidationError, ValidationErrors,
+};
+
+#[doc(hidden)]
+macro_rules! __log_rejection {
+    (
+        rejection_type = $ty:ident,
+        status = $status:expr,
+    ) => {
+        {
+            tracing::event!(
+                target: "eserde_axum::multipart::rejection",
+                tracing::Level::TRACE,
+                status = $status.as_u16(),
+                rejection_type = ::std::any::type_name::<$ty>(),
+                "rejecting request",
+            );
+        }
+    };
+}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+/// Rejection type for [`Multipart`](super::Multipart).
+///
+/// This rejection is used if the form fields couldn't be deserialized
+/// into the target type.
+///
+/// Each error points at the problematic form field via a JSON pointer—e.g.
+/// `/title` or `/tags/2`.
+pub struct MultipartError {
+    errors: DeserializationErrors,
+    unexpected_files: Vec<String>,
+}
+
+impl MultipartError {
+    pub(crate) fn new(errors: DeserializationErrors, unexpected_files: Vec<String>) -> Self {
+        Self {
+            errors,
+            unexpected_files,
+        }
+    }
+
+    /// The errors that occurred while deserializing the form fields.
+    pub fn errors(&self) -> &DeserializationErrors {
+        &self.errors
+    }
+
+    /// The names of the form fields that received a file upload,
+    /// even though they aren't deserialized into an [`UploadedFile`](super::UploadedFile).
+    pub fn unexpected_files(&self) -> &[String] {
+        &self.unexpected_files
+    }
+}
+
+impl axum_core::response::IntoResponse for MultipartError {
+    fn into_response(self) -> axum_core::response::Response {
+        let mut errors = ValidationErrors::from_body(&self.errors);
+        for (error, validation_error) in self.errors.iter().zip(&mut errors.errors) {
+            // Missing fields are reported against the form, point at the field instead.
+            if let Source::Body { pointer } = &mut validation_error.source {
+                if pointer.as_deref().is_none_or(str::is_empty) {
+                    if let Some(field) = error.field() {
+                        *pointer = Some(format!("/{field}"));
+                    }
+                }
+            }
+        }
+        errors
+            .errors
+            .extend(self.unexpected_files.iter().map(|field| ValidationError {
+                detail: "unexpected file upload".into(),
+                code: "unexpected_file".into(),
+                source: Source::Body {
+                    pointer: Some(format!("/{field}")),
+                },
+            }));
+        __log_rejection!(
+            rejection_type = MultipartError,
+            status = InvalidRequest::status(),
+        );
+        InvalidRequest::new(errors).into_response()
+    }
+}
+
+impl std::fmt::Display for MultipartError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Failed to deserialize the multipart form into the target schema:\n")?;
+        for e in self.errors.iter() {
+            writeln!(f, "- {}", e)?;
+        }
+        for field in &self.unexpected_files {
+            writeln!(f, "- {}: unexpected file upload", field)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for MultipartError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.errors)
+    }
+}
+
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+/// Rejection type for [`Multipart`](super::Multipart) used if the `Content-Type`
+/// header is missing, isn't `multipart/form-data` or has no boundary.
+pub struct InvalidBoundary;
+
+impl axum_core::response::IntoResponse for InvalidBoundary {
+    fn into_response(self) -> axum_core::response::Response {
+        let error = ValidationError {
+            detail: "Expected request with `Content-Type: multipart/form-data` and a boundary"
+                .into(),
+            code: "invalid_content_type".into(),
+            source: Source::Header {
+                name: CONTENT_TYPE.as_str().into(),
+            },
+        };
+        let response = InvalidRequest::new(ValidationErrors {
+            errors: vec![error],
+        });
+        __log_rejection!(
+            rejection_type = InvalidBoundary,
+            status = InvalidRequest::status(),
+        );
+        response.into_response()
+    }
+}
+
+impl std::fmt::Display for InvalidBoundary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Expected request with `Content-Type: multipart/form-data` and a boundary"
+        )
+    }
+}
+
+impl std::error::Error for InvalidBoundary {}
+
+#[derive(Debug)]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+/// Rejection type for [`Multipart`](super::Multipart) used if the request body
+/// isn't a well-formed multipart body, or it couldn't be buffered.
+pub struct MalformedMultipart {
+    inner: multer::Error,
+}
+
+impl MalformedMultipart {
+    pub(crate) fn new(inner: multer::Error) -> Self {
+        Self { inner }
+    }
+
+    /// The status code of the response.
+    ///
+    /// It's `413 Content Too Large` if the body exceeds the size limit,
+    /// `400 Bad Request` otherwise.
+    pub fn status(&self) -> StatusCode {
+        let mut source: Option<&(dyn std::error::Error + 'static)> = Some(&self.inner);
+        while let Some(error) = source {
+            if error.is::<http_body_util::LengthLimitError>() {
+                return StatusCode::PAYLOAD_TOO_LARGE;
+            }
+            source = error.source();
+        }
+        match self.inner {
+            multer::Error::FieldSizeExceeded { .. } | multer::Error::StreamSizeExceeded { .. } => {
+                StatusCode::PAYLOAD_TOO_LARGE
+            }
+            _ => StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+impl axum_core::response::IntoResponse for MalformedMultipart {
+    fn into_response(self) -> axum_core::response::Response {
+        let status = self.status();
+        let details = if status == StatusCode::PAYLOAD_TOO_LARGE {
+            ProblemDetails::new(status.as_u16())
+                .type_("content_too_large")
+                .title("The content is too large")
+        } else {
+            ProblemDetails::new(status.as_u16())
+                .type_("malformed_multipart")
+                .title("The multipart body is malformed")
+        };
+        __log_rejection!(rejection_type = MalformedMultipart, status = status,);
+        problem_response(details.detail(self.inner.to_string()))
+    }
+}
+
+impl std::fmt::Display for MalformedMultipart {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to parse the multipart body: {}", self.inner)
+    }
+}
+
+impl std::error::Error for MalformedMultipart {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.inner)
+    }
+}
+
+/// Rejection used for [`Multipart`](super::Multipart).
+///
+/// Contains one variant for each way the [`Multipart`](super::Multipart) extractor
+/// can fail.
+///
+/// All error responses follow the problem details specification,
+/// as outlined in [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug)]
+#[non_exhaustive]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub enum MultipartRejection {
+    #[allow(missing_docs)]
+    MultipartError(MultipartError),
+    #[allow(missing_docs)]
+    InvalidBoundary(InvalidBoundary),
+    #[allow(missing_docs)]
+    MalformedMultipart(MalformedMultipart),
+}
+
+impl axum_core::response::IntoResponse for MultipartRejection {
+    fn into_response(self) -> axum_core::response::Response {
+        match self {
+            Self::MultipartError(inner) => inner.into_response(),
+            Self::InvalidBoundary(inner) => inner.into_response(),
+            Self::MalformedMultipart(inner) => inner.into_response(),
+        }
+    }
+}
+
+impl From<MultipartError> for MultipartRejection {
+    fn from(inner: MultipartError) -> Self {
+        Self::MultipartError(inner)
+    }
+}
+impl From<InvalidBoundary> for MultipartRejection {
+    fn from(inner: InvalidBoundary) -> Self {
+        Self::InvalidBoundary(inner)
+    }
+}
+impl From<MalformedMultipart> for MultipartRejection {
+    fn from(inner: MalformedMultipart) -> Self {
+        Self::MalformedMultipart(inner)
+    }
+}
+impl std::fmt::Display for MultipartRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MultipartError(inner) => write!(f, "{inner}"),
+            Self::InvalidBoundary(inner) => write!(f, "{inner}"),
+            Self::MalformedMultipart(inner) => write!(f, "{inner}"),
+        }
+    }
+}
+impl std::error::Error for MultipartRejection {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::MultipartError(inner) => inner.source(),
+            Self::InvalidBoundary(inner) => inner.source(),
+            Self::MalformedMultipart(inner) => inner.source(),
+        }
+    }
+}
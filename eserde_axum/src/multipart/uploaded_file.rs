@@ -0,0 +1,130 @@
+use std::{
+    cell::RefCell,
+    hash::{BuildHasher, Hasher, RandomState},
+};
+
+use bytes::Bytes;
+use serde::de::{Error, Unexpected};
+
+/// A file uploaded as part of a `multipart/form-data` request.
+///
+/// Use it as the type of a field in the target type of the
+/// [`Multipart`](super::Multipart) extractor—e.g. `avatar: UploadedFile`,
+/// `attachments: Vec<UploadedFile>` or `cover: Option<UploadedFile>`.
+///
+/// It can only be deserialized by the [`Multipart`](super::Multipart) extractor:
+/// text values, including text parts of a multipart body, are rejected.
+///
+/// ```rust
+/// use eserde_axum::multipart::UploadedFile;
+///
+/// let errors = eserde::json::from_str::<UploadedFile>(r#""avatar.png""#).unwrap_err();
+/// assert_eq!(
+///     errors.iter().next().unwrap().message(),
+///     "invalid type: text value, expected a file upload"
+/// );
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(docsrs, doc(cfg(feature = "multipart")))]
+pub struct UploadedFile {
+    /// The file name sent by the client.
+    pub filename: String,
+    /// The `Content-Type` of the part, if the client specified one.
+    pub content_type: Option<String>,
+    /// The content of the file.
+    pub bytes: Bytes,
+}
+
+thread_local! {
+    static UPLOADS: RefCell<Option<Uploads>> = const { RefCell::new(None) };
+}
+
+/// The files of the request that is being deserialized.
+///
+/// File parts are replaced with a token in the URL-encoded form that's
+/// deserialized into the target type.
+/// The token includes an unguessable nonce, to make sure that a text part
+/// can't be passed off as a file.
+pub(crate) struct Uploads {
+    nonce: u64,
+    files: Vec<UploadedFile>,
+    claimed: Vec<bool>,
+}
+
+impl Uploads {
+    pub(crate) fn new() -> Self {
+        Self {
+            nonce: RandomState::new().build_hasher().finish(),
+            files: Vec::new(),
+            claimed: Vec::new(),
+        }
+    }
+
+    /// Register a file, returning the token that stands for it in the form.
+    pub(crate) fn push(&mut self, file: UploadedFile) -> String {
+        let token = format!("eserde-upload:{:016x}:{}", self.nonce, self.files.len());
+        self.files.push(file);
+        self.claimed.push(false);
+        token
+    }
+
+    /// Make the files available to [`UploadedFile`]'s `Deserialize` implementation
+    /// while `f` runs.
+    ///
+    /// Returns the output of `f`, together with the indexes of the files that
+    /// haven't been deserialized into an [`UploadedFile`].
+    pub(crate) fn scope<R>(self, f: impl FnOnce() -> R) -> (R, Vec<usize>) {
+        struct Guard;
+        impl Drop for Guard {
+            fn drop(&mut self) {
+                UPLOADS.with(|uploads| uploads.borrow_mut().take());
+            }
+        }
+
+        UPLOADS.with(|uploads| *uploads.borrow_mut() = Some(self));
+        let guard = Guard;
+        let output = f();
+        let unclaimed = UPLOADS.with(|uploads| {
+            uploads
+                .borrow()
+                .as_ref()
+                .map(|uploads| {
+                    (0..uploads.files.len())
+                        .filter(|&i| !uploads.claimed[i])
+                        .collect()
+                })
+                .unwrap_or_default()
+        });
+        drop(guard);
+        (output, unclaimed)
+    }
+
+    fn claim(token: &str) -> Option<UploadedFile> {
+        UPLOADS.with(|uploads| {
+            let mut uploads = uploads.borrow_mut();
+            let uploads = uploads.as_mut()?;
+            let (nonce, index) = token.strip_prefix("eserde-upload:")?.split_once(':')?;
+            if u64::from_str_radix(nonce, 16).ok()? != uploads.nonce {
+                return None;
+            }
+            let index: usize = index.parse().ok()?;
+            let file = uploads.files.get(index)?.clone();
+            uploads.claimed[index] = true;
+            Some(file)
+        })
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for UploadedFile {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let token = String::deserialize(deserializer)?;
+        Uploads::claim(&token).ok_or_else(|| {
+            D::Error::invalid_type(Unexpected::Other("text value"), &"a file upload")
+        })
+    }
+}
+
+eserde::impl_edeserialize_compat!(UploadedFile);
@@ -0,0 +1,58 @@
+//! Problem details responses, as returned by this crate's extractors.
+//!
+//! The problem details types live in [`eserde_problem`], a framework-agnostic
+//! crate, and are re-exported here for convenience.
+//! Wrap them in a [`Problem`] to turn them into an `axum` response—e.g. to
+//! reject requests in your own extractors with the same response shape.
+//!
+//! See [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html) for more details.
+pub use eserde_problem::{
+    ProblemDetails, Source, ValidationError, ValidationErrors, APPLICATION_PROBLEM_JSON,
+};
+
+use crate::details::problem_response;
+
+/// A problem details response.
+///
+/// It serializes the wrapped [`ProblemDetails`] as `application/problem+json`,
+/// using its `status` as the response status code.
+///
+/// # Example
+///
+/// ```rust
+/// use axum::{routing::get, Router};
+/// use eserde_axum::problem::{Problem, ProblemDetails};
+///
+/// async fn get_balance() -> Result<String, Problem> {
+///     Err(ProblemDetails::new(403)
+///         .type_("https://example.com/probs/out-of-credit")
+///         .title("You do not have enough credit")
+///         .into())
+/// }
+///
+/// let app = Router::new().route("/balance", get(get_balance));
+/// # let _: Router = app;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Problem<Extension = ()>(pub ProblemDetails<Extension>);
+
+impl<Extension> From<ProblemDetails<Extension>> for Problem<Extension> {
+    fn from(details: ProblemDetails<Extension>) -> Self {
+        Self(details)
+    }
+}
+
+impl From<eserde::DeserializationErrors> for Problem<ValidationErrors> {
+    fn from(errors: eserde::DeserializationErrors) -> Self {
+        Self(errors.into())
+    }
+}
+
+impl<Extension> axum_core::response::IntoResponse for Problem<Extension>
+where
+    Extension: serde::Serialize,
+{
+    fn into_response(self) -> axum_core::response::Response {
+        problem_response(self.0)
+    }
+}
@@ -0,0 +1,196 @@
+#![cfg(feature = "body")]
+use axum::{
+    http::{header::CONTENT_TYPE, Request},
+    routing::post,
+    Router,
+};
+use eserde_axum::Body;
+use tower::ServiceExt;
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct CreateUser {
+    email: String,
+    age: u8,
+}
+
+async fn create_user(Body(_): Body<CreateUser>) {}
+
+/// The status code and the pretty-printed validation errors of the response.
+async fn respond(content_type: Option<&str>, body: &'static str) -> (u16, String) {
+    let mut request = Request::post("/");
+    if let Some(content_type) = content_type {
+        request = request.header(CONTENT_TYPE, content_type);
+    }
+    let response = Router::new()
+        .route("/", post(create_user))
+        .oneshot(request.body(axum::body::Body::from(body)).unwrap())
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    if status != 400 && status != 415 {
+        return (status, String::new());
+    }
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    (
+        status,
+        serde_json::to_string_pretty(&body["errors"]).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_json() {
+    let (status, _) = respond(
+        Some("application/json"),
+        r#"{"email": "ada@example.com", "age": 36}"#,
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, errors) = respond(Some("application/json"), r#"{"age": 300}"#).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "out_of_range",
+        "detail": "invalid value: integer `300`, expected u8 at line 1 column 11",
+        "pointer": "/age",
+        "source": "body"
+      },
+      {
+        "code": "missing_field",
+        "detail": "missing field `email`",
+        "pointer": "",
+        "source": "body"
+      }
+    ]
+    "#);
+}
+
+#[tokio::test]
+async fn test_form() {
+    let (status, _) = respond(
+        Some("application/x-www-form-urlencoded"),
+        "email=ada%40example.com&age=36",
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, errors) = respond(Some("application/x-www-form-urlencoded"), "age=old").await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "invalid",
+        "detail": "invalid digit found in string",
+        "pointer": "/age",
+        "source": "body"
+      },
+      {
+        "code": "missing_field",
+        "detail": "missing field `email`",
+        "pointer": "",
+        "source": "body"
+      }
+    ]
+    "#);
+}
+
+#[cfg(feature = "toml")]
+#[tokio::test]
+async fn test_toml() {
+    let (status, _) = respond(
+        Some("application/toml"),
+        "email = \"ada@example.com\"\nage = 36",
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, errors) = respond(Some("application/toml"), "age = -1").await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "out_of_range",
+        "detail": "invalid value: integer `-1`, expected u8\n",
+        "pointer": "/age",
+        "source": "body"
+      },
+      {
+        "code": "missing_field",
+        "detail": "missing field `email`",
+        "pointer": "",
+        "source": "body"
+      }
+    ]
+    "#);
+}
+
+#[cfg(feature = "xml")]
+#[tokio::test]
+async fn test_xml() {
+    let (status, _) = respond(
+        Some("application/xml"),
+        "<user><email>ada@example.com</email><age>36</age></user>",
+    )
+    .await;
+    assert_eq!(status, 200);
+
+    let (status, errors) = respond(Some("text/xml"), "<user><age>many</age></user>").await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "invalid_type",
+        "detail": "invalid type: string \"many\", expected u8",
+        "pointer": "/user/age",
+        "source": "body"
+      },
+      {
+        "code": "missing_field",
+        "detail": "missing field `email`",
+        "pointer": "/user",
+        "source": "body"
+      }
+    ]
+    "#);
+}
+
+// The detail lists the formats enabled via features.
+#[cfg(all(feature = "toml", feature = "xml"))]
+#[tokio::test]
+async fn test_missing_content_type() {
+    let (status, errors) = respond(None, r#"{"email": "ada@example.com", "age": 36}"#).await;
+    assert_eq!(status, 415);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "unsupported_media_type",
+        "detail": "Expected request with `Content-Type` set to one of `application/json`, `application/x-www-form-urlencoded`, `application/toml`, `application/xml`, but no `Content-Type` header was found",
+        "name": "content-type",
+        "source": "header"
+      }
+    ]
+    "#);
+}
+
+// The detail lists the formats enabled via features.
+#[cfg(all(feature = "toml", feature = "xml"))]
+#[tokio::test]
+async fn test_unsupported_media_type() {
+    let (status, errors) = respond(Some("text/csv"), "email,age\nada@example.com,36").await;
+    assert_eq!(status, 415);
+    insta::assert_snapshot!(errors, @r#"
+    [
+      {
+        "code": "unsupported_media_type",
+        "detail": "Expected request with `Content-Type` set to one of `application/json`, `application/x-www-form-urlencoded`, `application/toml`, `application/xml`, but found `text/csv`",
+        "name": "content-type",
+        "source": "header"
+      }
+    ]
+    "#);
+}
@@ -0,0 +1,109 @@
+#![cfg(feature = "headers")]
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{HeaderMap, Request},
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use eserde::messages::{with_formatter, ErrorDescription};
+use eserde_axum::Headers;
+use tower::ServiceExt;
+
+#[derive(eserde::Deserialize, Debug)]
+#[serde(rename_all = "kebab-case")]
+#[allow(dead_code)]
+struct Tracing {
+    x_request_id: String,
+    #[eserde(missing = "Please provide a tenant")]
+    x_tenant: String,
+    #[eserde(code = "bad_attempt")]
+    x_retry_attempt: Option<u8>,
+}
+
+async fn handler(Headers(_): Headers<Tracing>) {}
+
+async fn respond(request: Request<Body>) -> (u16, String) {
+    let response = Router::new()
+        .route("/", get(handler))
+        .oneshot(request)
+        .await
+        .unwrap();
+    into_parts(response).await
+}
+
+/// The status code and the pretty-printed validation errors of a response.
+async fn into_parts(response: Response) -> (u16, String) {
+    let status = response.status().as_u16();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    (
+        status,
+        serde_json::to_string_pretty(&body["errors"]).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_missing_headers() {
+    let request = Request::get("/")
+        .header("x-retry-attempt", "many")
+        .body(Body::empty())
+        .unwrap();
+    let (status, body) = respond(request).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(body, @r#"
+    [
+      {
+        "code": "bad_attempt",
+        "detail": "invalid digit found in string",
+        "name": "x-retry-attempt",
+        "source": "header"
+      },
+      {
+        "code": "missing_header",
+        "detail": "missing header",
+        "name": "x-request-id",
+        "source": "header"
+      },
+      {
+        "code": "missing_header",
+        "detail": "Please provide a tenant",
+        "name": "x-tenant",
+        "source": "header"
+      }
+    ]
+    "#);
+}
+
+#[tokio::test]
+async fn test_missing_header_with_formatter() {
+    let formatter = |error: &ErrorDescription<'_>, _: &str| {
+        Some(format!("l'header `{}` è obbligatorio", error.field()?))
+    };
+    let rejection = with_formatter(Arc::new(formatter), "it", || {
+        Headers::<Tracing>::from_headers(&HeaderMap::new())
+    })
+    .unwrap_err();
+    let (status, body) = into_parts(rejection.into_response()).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(body, @r#"
+    [
+      {
+        "code": "missing_header",
+        "detail": "l'header `x-request-id` è obbligatorio",
+        "name": "x-request-id",
+        "source": "header"
+      },
+      {
+        "code": "missing_header",
+        "detail": "Please provide a tenant",
+        "name": "x-tenant",
+        "source": "header"
+      }
+    ]
+    "#);
+}
@@ -0,0 +1,195 @@
+#![cfg(feature = "json")]
+use axum::{
+    http::{header::CONTENT_TYPE, Request, StatusCode},
+    response::{IntoResponse, Response},
+    routing::post,
+    Extension, Router,
+};
+use eserde_axum::{
+    json::{JsonConfig, JsonRejection, JsonRejectionFormatter},
+    Json,
+};
+use tower::ServiceExt;
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct CreateUser {
+    email: String,
+    age: u8,
+}
+
+async fn create_user(Json(_): Json<CreateUser>) {}
+
+/// A formatter with its own error envelope, rather than problem details.
+struct HouseFormatter;
+
+impl JsonRejectionFormatter for HouseFormatter {
+    fn format(&self, rejection: JsonRejection) -> Response {
+        let (status, code, errors) = match &rejection {
+            JsonRejection::JsonError(error) => {
+                let errors: Vec<_> = error
+                    .errors()
+                    .iter()
+                    .map(|e| {
+                        serde_json::json!({
+                            "field": e.path().map(|p| p.to_string()),
+                            "message": e.message(),
+                        })
+                    })
+                    .collect();
+                (StatusCode::UNPROCESSABLE_ENTITY, "invalid_payload", errors)
+            }
+            JsonRejection::MissingJsonContentType(_)
+            | JsonRejection::JsonContentTypeMismatch(_) => (
+                StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                "unsupported_media_type",
+                vec![serde_json::json!({ "message": rejection.to_string() })],
+            ),
+            _ => return rejection.into_response(),
+        };
+        let body = serde_json::json!({ "code": code, "errors": errors });
+        (status, axum::Json(body)).into_response()
+    }
+}
+
+/// The status code and the pretty-printed body of the response.
+async fn respond(
+    config: JsonConfig,
+    content_type: Option<&str>,
+    body: &'static str,
+) -> (u16, String) {
+    let mut request = Request::post("/");
+    if let Some(content_type) = content_type {
+        request = request.header(CONTENT_TYPE, content_type);
+    }
+    let response = Router::new()
+        .route("/", post(create_user))
+        .layer(Extension(config))
+        .oneshot(request.body(axum::body::Body::from(body)).unwrap())
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    (status, serde_json::to_string_pretty(&body).unwrap())
+}
+
+#[tokio::test]
+async fn test_rejection_formatter() {
+    let config = JsonConfig::new().rejection_formatter(HouseFormatter);
+    let (status, body) = respond(config, Some("application/json"), r#"{"age": 300}"#).await;
+    assert_eq!(status, 422);
+    insta::assert_snapshot!(body, @r#"
+    {
+      "code": "invalid_payload",
+      "errors": [
+        {
+          "field": "age",
+          "message": "invalid value: integer `300`, expected u8 at line 1 column 11"
+        },
+        {
+          "field": ".",
+          "message": "missing field `email`"
+        }
+      ]
+    }
+    "#);
+}
+
+#[tokio::test]
+async fn test_rejection_formatter_content_type() {
+    let config = JsonConfig::new().rejection_formatter(HouseFormatter);
+    let (status, body) = respond(config.clone(), Some("text/plain"), "{}").await;
+    assert_eq!(status, 415);
+    insta::assert_snapshot!(body, @r#"
+    {
+      "code": "unsupported_media_type",
+      "errors": [
+        {
+          "message": "Expected request with `Content-Type: application/json` or `application/*+json`, but found `text/plain`"
+        }
+      ]
+    }
+    "#);
+
+    let (status, body) = respond(config, None, "{}").await;
+    assert_eq!(status, 415);
+    insta::assert_snapshot!(body, @r#"
+    {
+      "code": "unsupported_media_type",
+      "errors": [
+        {
+          "message": "Expected request with `Content-Type: application/json`"
+        }
+      ]
+    }
+    "#);
+}
+
+#[tokio::test]
+async fn test_limit() {
+    let config = JsonConfig::new().limit(16);
+    let (status, body) = respond(
+        config,
+        Some("application/json"),
+        r#"{"email": "ada@example.com", "age": 36}"#,
+    )
+    .await;
+    assert_eq!(status, 413);
+    insta::assert_snapshot!(body, @r#"
+    {
+      "detail": "Failed to buffer the request body: length limit exceeded",
+      "status": 413,
+      "title": "The content is too large",
+      "type": "content_too_large"
+    }
+    "#);
+}
+
+#[tokio::test]
+async fn test_too_many_errors() {
+    let config = JsonConfig::new().max_errors(1);
+    let (status, body) = respond(config, Some("application/json"), r#"{"age": 300}"#).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(body, @r#"
+    {
+      "detail": "The request doesn't match the expected schema. Only the first 1 errors are reported",
+      "errors": [
+        {
+          "code": "out_of_range",
+          "detail": "invalid value: integer `300`, expected u8 at line 1 column 11",
+          "pointer": "/age",
+          "source": "body"
+        }
+      ],
+      "status": 400,
+      "title": "The request has too many errors",
+      "type": "too_many_errors"
+    }
+    "#);
+}
+
+/// A formatter that only reports whether error collection stopped early.
+struct TruncationFormatter;
+
+impl JsonRejectionFormatter for TruncationFormatter {
+    fn format(&self, rejection: JsonRejection) -> Response {
+        match rejection {
+            JsonRejection::JsonError(error) => axum::Json(error.is_truncated()).into_response(),
+            rejection => rejection.into_response(),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_is_truncated() {
+    for (max_errors, expected) in [(1, "true"), (2, "false")] {
+        let config = JsonConfig::new()
+            .max_errors(max_errors)
+            .rejection_formatter(TruncationFormatter);
+        let (_, body) = respond(config, Some("application/json"), r#"{"age": 300}"#).await;
+        assert_eq!(body, expected);
+    }
+}
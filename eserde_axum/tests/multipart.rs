@@ -0,0 +1,129 @@
+#![cfg(feature = "multipart")]
+use axum::{
+    body::Body,
+    http::{header::CONTENT_TYPE, Request},
+    routing::post,
+    Router,
+};
+use eserde_axum::multipart::{Multipart, UploadedFile};
+use tower::ServiceExt;
+
+#[derive(eserde::Deserialize, Debug)]
+#[allow(dead_code)]
+struct CreatePost {
+    title: String,
+    #[eserde(missing = "Please pick at least one category")]
+    category: String,
+    priority: u8,
+    cover: Option<UploadedFile>,
+}
+
+async fn handler(Multipart(_): Multipart<CreatePost>) {}
+
+const BOUNDARY: &str = "X-BOUNDARY";
+
+/// A text part, or a file part if `filename` is set.
+fn part(name: &str, filename: Option<&str>, value: &str) -> String {
+    let filename = filename
+        .map(|filename| format!("; filename=\"{filename}\""))
+        .unwrap_or_default();
+    format!(
+        "--{BOUNDARY}\r\nContent-Disposition: form-data; name=\"{name}\"{filename}\r\n\r\n{value}\r\n"
+    )
+}
+
+async fn respond(parts: &[String]) -> (u16, String) {
+    let body = format!("{}--{BOUNDARY}--\r\n", parts.concat());
+    let request = Request::post("/")
+        .header(
+            CONTENT_TYPE,
+            format!("multipart/form-data; boundary={BOUNDARY}"),
+        )
+        .body(Body::from(body))
+        .unwrap();
+    let response = Router::new()
+        .route("/", post(handler))
+        .oneshot(request)
+        .await
+        .unwrap();
+    let status = response.status().as_u16();
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let body: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    (
+        status,
+        serde_json::to_string_pretty(&body["errors"]).unwrap(),
+    )
+}
+
+#[tokio::test]
+async fn test_missing_parts() {
+    let (status, body) = respond(&[part("priority", None, "1")]).await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(body, @r#"
+    [
+      {
+        "code": "missing_field",
+        "detail": "missing field `title`",
+        "pointer": "/title",
+        "source": "body"
+      },
+      {
+        "code": "missing_field",
+        "detail": "Please pick at least one category",
+        "pointer": "/category",
+        "source": "body"
+      }
+    ]
+    "#);
+}
+
+#[tokio::test]
+async fn test_invalid_field_type() {
+    let (status, body) = respond(&[
+        part("title", None, "Hello"),
+        part("category", None, "news"),
+        part("priority", None, "urgent"),
+    ])
+    .await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(body, @r#"
+    [
+      {
+        "code": "invalid",
+        "detail": "invalid digit found in string",
+        "pointer": "/priority",
+        "source": "body"
+      }
+    ]
+    "#);
+}
+
+#[tokio::test]
+async fn test_bad_files() {
+    let (status, body) = respond(&[
+        part("title", Some("title.txt"), "Hello"),
+        part("category", None, "news"),
+        part("priority", None, "1"),
+        part("cover", None, "not a file"),
+    ])
+    .await;
+    assert_eq!(status, 400);
+    insta::assert_snapshot!(body, @r#"
+    [
+      {
+        "code": "invalid_type",
+        "detail": "invalid type: text value, expected a file upload",
+        "pointer": "/cover",
+        "source": "body"
+      },
+      {
+        "code": "unexpected_file",
+        "detail": "unexpected file upload",
+        "pointer": "/title",
+        "source": "body"
+      }
+    ]
+    "#);
+}
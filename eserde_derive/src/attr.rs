@@ -1,16 +1,22 @@
 use std::ops::ControlFlow::{self, Break, Continue};
 
-use proc_macro2::Span;
+use proc_macro2::{Group, Span};
 use quote::ToTokens;
-use syn::{meta::ParseNestedMeta, punctuated::Punctuated, Error, Expr, Meta, Path, Result, Token};
+use syn::{
+    meta::ParseNestedMeta, parse::Parser, punctuated::Punctuated, token, Error, Expr, ExprLit, Lit,
+    Meta, MetaNameValue, Path, Result, Token,
+};
 
 /// Represents a single meta item within an attribute.
 ///
-/// For example, `default` and `rename = "foo"` within `#[serde(default, rename = "foo")]`).
+/// For example, `default`, `rename = "foo"` and `rename_all(deserialize = "camelCase")`
+/// within `#[serde(default, rename = "foo", rename_all(deserialize = "camelCase"))]`).
 #[derive(Clone, Debug)]
 pub struct MetaItem {
     pub key: Path,
     pub value: Option<(Token![=], Expr)>,
+    /// The parenthesized arguments, e.g. `(deserialize = "camelCase")`.
+    pub list: Option<Group>,
 }
 impl MetaItem {
     pub fn parse(parser: ParseNestedMeta) -> Result<Self> {
@@ -22,7 +28,29 @@ impl MetaItem {
         } else {
             None
         };
-        Ok(Self { key, value })
+        let list = if value.is_none() && parser.input.peek(token::Paren) {
+            Some(parser.input.parse()?)
+        } else {
+            None
+        };
+        Ok(Self { key, value, list })
+    }
+
+    /// The string value of `key = "..."` within the parenthesized arguments—e.g.
+    /// `camelCase` for `deserialize` in `rename_all(deserialize = "camelCase")`.
+    pub fn list_str_value(&self, key: &str) -> Option<String> {
+        let list = self.list.as_ref()?;
+        let args = Punctuated::<MetaNameValue, Token![,]>::parse_terminated
+            .parse2(list.stream())
+            .ok()?;
+        args.into_iter()
+            .find(|arg| arg.path.is_ident(key))
+            .and_then(|arg| match arg.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value()),
+                _ => None,
+            })
     }
 }
 impl ToTokens for MetaItem {
@@ -32,6 +60,9 @@ impl ToTokens for MetaItem {
             eq.to_tokens(tokens);
             expr.to_tokens(tokens);
         }
+        if let Some(list) = &self.list {
+            list.to_tokens(tokens);
+        }
     }
 }
 
@@ -49,6 +80,8 @@ impl ToTokens for MetaItem {
 /// assert!(find_attr_meta(&[parse_quote!( #[serde(default, rename = "bar")] )], "serde", "rename").is_some());
 /// assert!(find_attr_meta(&[parse_quote!( #[serde(rename, rename = "baz", rename)] )], "serde", "rename").is_some());
 /// assert!(find_attr_meta(&[parse_quote!( #[serde(rename, rename)] )], "serde", "rename").is_some());
+/// assert!(find_attr_meta(&[parse_quote!( #[serde(rename(deserialize = "foo"))] )], "serde", "rename").is_some());
+/// assert!(find_attr_meta(&[parse_quote!( #[serde(rename(deserialize = "foo"), default)] )], "serde", "default").is_some());
 /// // `None`
 /// assert!(find_attr_meta(&[parse_quote!( #[serde(default)] )], "serde", "rename").is_none());
 /// assert!(find_attr_meta(&[parse_quote!( #[ignore(rename = "bing")] )], "serde", "rename").is_none());
@@ -1,8 +1,14 @@
 use indexmap::IndexSet;
 use quote::{format_ident, quote, ToTokens};
-use syn::{Data, DeriveInput, GenericParam, Generics, Lifetime};
+use syn::{ext::IdentExt, Data, DeriveInput, GenericParam, Generics, Lifetime};
 
-use crate::model::{PermissiveCompanionType, ShadowType};
+use crate::{
+    attr::find_attr_meta,
+    model::{
+        eserde_code, eserde_message, override_errors, type_name, FieldNamesType,
+        PermissiveCompanionType, ShadowType,
+    },
+};
 
 impl ToTokens for ShadowType {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
@@ -17,6 +23,20 @@ impl ToTokens for ShadowType {
     }
 }
 
+impl ToTokens for FieldNamesType {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let Self(input) = self;
+
+        quote! {
+            #[derive(::eserde::_serde::Deserialize)]
+            #[serde(crate = "eserde::_serde")]
+            #[allow(dead_code)]
+            #input
+        }
+        .to_tokens(tokens);
+    }
+}
+
 impl ToTokens for PermissiveCompanionType {
     fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
         let Self { ty_, impl_, .. } = self;
@@ -163,23 +183,30 @@ pub fn initialize_from_shadow(
 
 /// Walk all fields on the companion types to report errors about missing values, if any.
 pub fn collect_missing_errors(
-    input: &Data,
+    input: &DeriveInput,
     companion_type: &syn::Ident,
+    field_names_type: &syn::Ident,
     companion_binding: &syn::Ident,
     n_errors: &syn::Ident,
 ) -> proc_macro2::TokenStream {
-    match input {
+    match &input.data {
         Data::Struct(data) => {
-            let accumulate = data.fields.members().map(|field| {
-                let field_str = match &field {
-                    syn::Member::Named(ident) => ident.to_string(),
-                    // TODO: Improve naming for unnamed fields
-                    syn::Member::Unnamed(index) => format!("{}", index.index),
-                };
-                quote! {
-                    #companion_binding.#field.push_error_if_missing(#field_str);
-                }
-            });
+            let accumulate =
+                data.fields
+                    .members()
+                    .zip(&data.fields)
+                    .enumerate()
+                    .map(|(i, (field, f))| {
+                        let field_name = field_name(field_names_type, None, i, &field);
+                        let push_error = push_error_if_missing(f);
+                        with_custom_errors(
+                            f,
+                            &field_name,
+                            quote! {
+                                #companion_binding.#field.#push_error(|| #field_name);
+                            },
+                        )
+                    });
             quote! {
                 #(#accumulate)*
                 let __n_new_errors = ::eserde::reporter::ErrorReporter::n_errors();
@@ -191,9 +218,21 @@ pub fn collect_missing_errors(
             }
         }
         Data::Enum(e) => {
-            let variants = e.variants.iter().map(|variant| {
+            let variants = e.variants.iter().enumerate().map(|(index, variant)| {
                 let variant_ident = &variant.ident;
 
+                let has_deserialize_with = ["deserialize_with", "with"]
+                    .iter()
+                    .any(|key| find_attr_meta(&variant.attrs, "serde", key).is_some());
+                if has_deserialize_with {
+                    // The companion variant wraps the custom deserializer, which reports its own errors.
+                    return quote! {
+                        #companion_type::#variant_ident(__v0) => match __v0 {
+                            ::eserde::_macro_impl::MaybeInvalid::Invalid => Err(()),
+                            ::eserde::_macro_impl::MaybeInvalid::Valid(_) => Ok(()),
+                        }
+                    };
+                }
                 if matches!(variant.fields, syn::Fields::Unit) {
                     return quote! {
                         #companion_type::#variant_ident => Ok(())
@@ -218,16 +257,19 @@ pub fn collect_missing_errors(
                 let accumulate = variant
                     .fields
                     .members()
+                    .zip(&variant.fields)
                     .zip(bindings.iter())
-                    .map(|(field, v)| {
-                        let field_str = match &field {
-                            syn::Member::Named(ident) => ident.to_string(),
-                            // TODO: Improve naming for unnamed fields
-                            syn::Member::Unnamed(index) => format!("{}", index.index),
-                        };
-                        quote! {
-                            #v.push_error_if_missing(#field_str);
-                        }
+                    .enumerate()
+                    .map(|(i, ((field, f), v))| {
+                        let field_name = field_name(field_names_type, Some(index), i, &field);
+                        let push_error = push_error_if_missing(f);
+                        with_custom_errors(
+                            f,
+                            &field_name,
+                            quote! {
+                                #v.#push_error(|| #field_name);
+                            },
+                        )
                     });
                 quote! {
                     #companion_type::#variant_ident { #(#destructure),* } => {
@@ -250,3 +292,76 @@ pub fn collect_missing_errors(
         Data::Union(_) => unreachable!(),
     }
 }
+
+/// The method of the companion field that reports it as missing, if it was.
+///
+/// Fields with `#[eserde(with = "..")]` may have a type that doesn't implement `Deserialize`,
+/// so we can't check if it tolerates missing values.
+fn push_error_if_missing(field: &syn::Field) -> syn::Ident {
+    if find_attr_meta(&field.attrs, "eserde", "with").is_some() {
+        format_ident!("push_error_if_missing_with")
+    } else {
+        format_ident!("push_error_if_missing")
+    }
+}
+
+/// Rewrite the error reported by `push_error` if the field has a custom message for
+/// missing values—i.e. `#[eserde(missing = "..")]` or `#[eserde(message = "..")]`—or
+/// a custom code—i.e. `#[eserde(code = "..")]`.
+fn with_custom_errors(
+    field: &syn::Field,
+    field_name: &proc_macro2::TokenStream,
+    push_error: proc_macro2::TokenStream,
+) -> proc_macro2::TokenStream {
+    let message = eserde_message(&field.attrs, "missing");
+    let code = eserde_code(&field.attrs);
+    if message.is_none() && code.is_none() {
+        return push_error;
+    }
+    let n_errors = format_ident!("__n_field_errors");
+    let override_errors = override_errors(
+        &n_errors,
+        message.as_deref(),
+        code.as_deref(),
+        field_name,
+        &type_name(&field.ty),
+    );
+    quote! {
+        let #n_errors = ::eserde::reporter::ErrorReporter::n_errors();
+        #push_error
+        #override_errors
+    }
+}
+
+/// An expression for the name `serde` gives to a field—e.g. `"displayName"` for `display_name`
+/// with `#[serde(rename_all = "camelCase")]`.
+///
+/// Named fields are looked up in the [`FieldNamesType`], by `index` within the struct or,
+/// for enums, within the variant at `variant`.
+pub fn field_name(
+    field_names_type: &syn::Ident,
+    variant: Option<usize>,
+    index: usize,
+    member: &syn::Member,
+) -> proc_macro2::TokenStream {
+    match member {
+        syn::Member::Named(ident) => {
+            let fallback = ident.unraw().to_string();
+            let variant = match variant {
+                Some(variant) => {
+                    let variant = variant as u32;
+                    quote! { ::core::option::Option::Some(#variant) }
+                }
+                None => quote! { ::core::option::Option::None },
+            };
+            quote! {
+                ::eserde::_macro_impl::field_name::<#field_names_type>(#variant, #index, #fallback)
+            }
+        }
+        // TODO: Improve naming for unnamed fields
+        syn::Member::Unnamed(index) => {
+            let name = index.index.to_string();
+            quote! { #name }
+        }
+    }
+}
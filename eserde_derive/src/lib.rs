@@ -9,6 +9,8 @@ use indexmap::IndexSet;
 use proc_macro::TokenStream;
 use quote::{format_ident, quote};
 use syn::{parse_macro_input, DeriveInput};
+
+use crate::attr::find_attr_meta;
 use unsupported::reject_unsupported_inputs;
 
 mod attr;
@@ -37,22 +39,74 @@ pub fn derive_deserialize(input: TokenStream) -> TokenStream {
         shadow_type_ident,
     );
 
-    let companion_type =
-        model::PermissiveCompanionType::new(format_ident!("__ImplEDeserializeFor{}", name), &input);
+    let deser_generics = ImplDeserGenerics::new(&input, &IndexSet::new());
+    let (impl_generics, ty_generics, where_clause) = deser_generics.split_for_impl();
+
+    let deserializer_generic_ident = format_ident!("__D");
+    let deserialize_impl = quote! {
+        #[automatically_derived]
+        impl #impl_generics ::eserde::_serde::Deserialize<'de> for #name #ty_generics
+        #where_clause
+        {
+            fn deserialize<#deserializer_generic_ident>(__deserializer: #deserializer_generic_ident) -> Result<Self, #deserializer_generic_ident::Error>
+            where
+                #deserializer_generic_ident: ::eserde::_serde::Deserializer<'de>,
+            {
+                let #shadow_binding = #shadow_type_ident::deserialize(__deserializer)?;
+                Ok(#initialize_from_shadow)
+            }
+        }
+    };
+
+    // Identifier enums are leaves: they are deserialized from a single string
+    // or integer, so there is at most one error to report.
+    // `serde`'s own error for an unknown identifier already lists the accepted ones.
+    if find_attr_meta(&input.attrs, "serde", "variant_identifier").is_some()
+        || find_attr_meta(&input.attrs, "serde", "field_identifier").is_some()
+    {
+        let expanded = quote! {
+            const _: () = {
+                #shadow_type
+
+                #[automatically_derived]
+                impl #impl_generics ::eserde::EDeserialize<'de> for #name #ty_generics
+                #where_clause
+                {
+                    fn deserialize_for_errors<#deserializer_generic_ident>(__deserializer: #deserializer_generic_ident) -> Result<(), ()>
+                    where
+                        #deserializer_generic_ident: ::eserde::_serde::Deserializer<'de>,
+                    {
+                        <Self as ::eserde::_serde::Deserialize>::deserialize(__deserializer)
+                            .map(|_| ())
+                            .map_err(::eserde::reporter::ErrorReporter::report)
+                    }
+                }
+
+                #deserialize_impl
+            };
+        };
+        return TokenStream::from(expanded);
+    }
+
+    let field_names_type =
+        model::FieldNamesType::new(format_ident!("__FieldNamesFor{}", name), &input);
+    let field_names_type_ident = &field_names_type.0.ident;
+    let companion_type = model::PermissiveCompanionType::new(
+        format_ident!("__ImplEDeserializeFor{}", name),
+        &input,
+        field_names_type_ident,
+    );
     let companion_type_ident = &companion_type.ty_.ident;
     let companion_binding = format_ident!("__companion");
-    let deserializer_generic_ident = format_ident!("__D");
     let n_errors = format_ident!("__n_errors");
     let collect_missing_errors = collect_missing_errors(
-        &input.data,
+        &input,
         companion_type_ident,
+        field_names_type_ident,
         &companion_binding,
         &n_errors,
     );
 
-    let deser_generics = ImplDeserGenerics::new(&input, &IndexSet::new());
-    let (impl_generics, ty_generics, where_clause) = deser_generics.split_for_impl();
-
     let human_deser_generics =
         ImplDeserGenerics::new(&input, &companion_type.eserde_aware_generics);
     let (human_impl_generics, human_ty_generics, human_where_clause) =
@@ -64,6 +118,8 @@ pub fn derive_deserialize(input: TokenStream) -> TokenStream {
 
             #shadow_type
 
+            #field_names_type
+
             #[automatically_derived]
             impl #human_impl_generics ::eserde::EDeserialize<'de> for #name #human_ty_generics
             #human_where_clause
@@ -79,18 +135,7 @@ pub fn derive_deserialize(input: TokenStream) -> TokenStream {
                 }
             }
 
-            #[automatically_derived]
-            impl #impl_generics ::eserde::_serde::Deserialize<'de> for #name #ty_generics
-            #where_clause
-            {
-                fn deserialize<#deserializer_generic_ident>(__deserializer: #deserializer_generic_ident) -> Result<Self, #deserializer_generic_ident::Error>
-                where
-                    #deserializer_generic_ident: ::eserde::_serde::Deserializer<'de>,
-                {
-                    let #shadow_binding = #shadow_type_ident::deserialize(__deserializer)?;
-                    Ok(#initialize_from_shadow)
-                }
-            }
+            #deserialize_impl
         };
     };
 
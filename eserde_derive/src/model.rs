@@ -1,11 +1,16 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    ops::ControlFlow::{Break, Continue},
+};
 
 use crate::{
-    attr::{find_attr_meta, remove_attr_meta},
+    attr::{find_attr_meta, remove_attr_meta, visit_attr_metas},
+    emit::field_name,
     filter_attributes::FilterAttributes,
 };
 use indexmap::IndexSet;
-use syn::{spanned::Spanned, DeriveInput};
+use quote::{format_ident, quote, ToTokens};
+use syn::{ext::IdentExt, spanned::Spanned, DeriveInput};
 
 /// A type with exactly the same set of fields/variants as the original type, but with a different name.
 /// This type is used to derive `Deserialize`, thus obtaining from `serde` the same deserialize implementation
@@ -18,17 +23,240 @@ fn keep_serde_attributes(attr: &syn::Attribute) -> bool {
 
 impl ShadowType {
     pub fn new(ident: syn::Ident, input: &syn::DeriveInput) -> Self {
-        let shadow = DeriveInput {
+        let mut shadow = DeriveInput {
             vis: syn::Visibility::Inherited,
             ident,
             // We don't want to keep _all_ attributes for the shadow type, only the `serde` ones
             // (e.g. `#[serde(default)]`), so we filter out the others.
             ..input.filter_attributes(|attr| attr.meta.path().is_ident("serde"))
         };
+        // `#[eserde(with = "..")]` fields are deserialized by the module's `deserialize` function.
+        for (shadow_field, field) in fields_mut(&mut shadow.data).zip(fields(&input.data)) {
+            if let Some(module) = eserde_with(field) {
+                let module = module.to_token_stream();
+                let path = syn::LitStr::new(&format!("{module}::deserialize"), field.span());
+                shadow_field.attrs.push(
+                    syn::parse_quote_spanned!(field.span()=> #[serde(deserialize_with = #path)]),
+                );
+            }
+        }
+        keep_original_name(&mut shadow, &input.ident);
         Self(shadow)
     }
 }
 
+/// The module set via `#[eserde(with = "..")]` on the field, if any.
+///
+/// The module must provide both a `deserialize` and a `deserialize_for_errors` function,
+/// with the same signatures as the homonymous methods of `Deserialize` and `EDeserialize`.
+fn eserde_with(field: &syn::Field) -> Option<syn::Path> {
+    find_attr_meta(&field.attrs, "eserde", "with")
+        .and_then(|meta_item| meta_str_value(&meta_item))
+        .and_then(|s| syn::parse_str::<syn::Path>(&s).ok())
+}
+
+/// The custom message set for errors of the given `kind` (`invalid` or `missing`),
+/// via `#[eserde(<kind> = "..")]` or, for all kinds, `#[eserde(message = "..")]`.
+pub fn eserde_message(attrs: &[syn::Attribute], kind: &str) -> Option<String> {
+    find_attr_meta(attrs, "eserde", kind)
+        .or_else(|| find_attr_meta(attrs, "eserde", "message"))
+        .and_then(|meta_item| meta_str_value(&meta_item))
+}
+
+/// The custom error code set via `#[eserde(code = "..")]`, if any.
+pub fn eserde_code(attrs: &[syn::Attribute]) -> Option<String> {
+    find_attr_meta(attrs, "eserde", "code").and_then(|meta_item| meta_str_value(&meta_item))
+}
+
+/// The note set via `#[eserde(deprecated = "..")]`, if the field is deprecated.
+///
+/// It's `Some(None)` for a bare `#[eserde(deprecated)]`.
+fn eserde_deprecated(attrs: &[syn::Attribute]) -> Option<Option<String>> {
+    find_attr_meta(attrs, "eserde", "deprecated").map(|meta_item| meta_str_value(&meta_item))
+}
+
+/// Remove the `#[serde(deserialize_with = "..")]` picked for a field of the companion type,
+/// and return an expression for the function it points to, so that it can be wrapped.
+fn take_deserialize_with(attrs: &mut [syn::Attribute], wrapper_ty: &syn::Type) -> syn::Expr {
+    match remove_attr_meta(attrs, "serde", "deserialize_with")
+        .and_then(|meta_item| meta_str_value(&meta_item))
+        .and_then(|s| syn::parse_str::<syn::Path>(&s).ok())
+    {
+        Some(path) => syn::parse_quote!(#path),
+        None => {
+            syn::parse_quote!(<#wrapper_ty as ::eserde::_serde::Deserialize<'de>>::deserialize)
+        }
+    }
+}
+
+/// Apply the custom message and code of a field to the errors reported after
+/// the first `n_errors`.
+pub fn override_errors(
+    n_errors: &syn::Ident,
+    message: Option<&str>,
+    code: Option<&str>,
+    field_name: impl ToTokens,
+    expected: &str,
+) -> proc_macro2::TokenStream {
+    let message = match message {
+        Some(message) => quote! { ::core::option::Option::Some(#message) },
+        None => quote! { ::core::option::Option::None },
+    };
+    let code = match code {
+        Some(code) => quote! { ::core::option::Option::Some(#code) },
+        None => quote! { ::core::option::Option::None },
+    };
+    quote! {
+        ::eserde::_macro_impl::override_errors(#n_errors, #message, #code, || #field_name, #expected);
+    }
+}
+
+/// The type of a field, as written in the source—e.g. `Vec<String>`.
+///
+/// It's used to fill in the `{expected}` placeholder of custom messages.
+pub fn type_name(ty: &syn::Type) -> String {
+    let tokens = ty.to_token_stream().to_string();
+    // Token streams are rendered with a space between each token:
+    // only keep the ones that separate two words (e.g. `dyn Trait`).
+    let chars: Vec<char> = tokens.chars().collect();
+    let is_word = |c: Option<&char>| c.is_some_and(|c| c.is_alphanumeric() || *c == '_');
+    chars
+        .iter()
+        .enumerate()
+        .filter(|(i, c)| {
+            **c != ' ' || (is_word(chars.get(i.wrapping_sub(1))) && is_word(chars.get(i + 1)))
+        })
+        .map(|(_, c)| c)
+        .collect()
+}
+
+/// All the fields of a struct, or of all the variants of an enum.
+fn fields(data: &syn::Data) -> Box<dyn Iterator<Item = &syn::Field> + '_> {
+    match data {
+        syn::Data::Struct(data_struct) => Box::new(data_struct.fields.iter()),
+        syn::Data::Enum(data_enum) => {
+            Box::new(data_enum.variants.iter().flat_map(|v| v.fields.iter()))
+        }
+        syn::Data::Union(_) => unreachable!(),
+    }
+}
+
+/// See [`fields`].
+fn fields_mut(data: &mut syn::Data) -> Box<dyn Iterator<Item = &mut syn::Field> + '_> {
+    match data {
+        syn::Data::Struct(data_struct) => Box::new(data_struct.fields.iter_mut()),
+        syn::Data::Enum(data_enum) => Box::new(
+            data_enum
+                .variants
+                .iter_mut()
+                .flat_map(|v| v.fields.iter_mut()),
+        ),
+        syn::Data::Union(_) => unreachable!(),
+    }
+}
+
+/// Use the name of the original type as the name `serde` sees for the generated type,
+/// unless it was already renamed for deserialization via `#[serde(rename = "..")]`
+/// or `#[serde(rename(deserialize = ".."))]`.
+///
+/// Some formats (e.g. RON) check it against the input.
+fn keep_original_name(generated: &mut DeriveInput, original: &syn::Ident) {
+    // Either `rename = ".."` or `rename(deserialize = "..")`.
+    let renamed = visit_attr_metas(&generated.attrs, "serde", |meta| {
+        if meta.key.is_ident("rename")
+            && (meta.value.is_some() || meta.list_str_value("deserialize").is_some())
+        {
+            Break(())
+        } else {
+            Continue(())
+        }
+    });
+    if renamed.is_none() {
+        let name = original.unraw().to_string();
+        generated
+            .attrs
+            .push(syn::parse_quote!(#[serde(rename(deserialize = #name))]));
+    }
+}
+
+/// A type with the same fields as the original type, as far as their names are concerned:
+/// no generics, no custom deserialization logic, all fields of type `IgnoredAny`.
+///
+/// `serde` lists the names of the fields it expects when deserializing a struct
+/// (or a struct variant). We read them at runtime, through this type, to name missing
+/// fields the way `serde` does—rather than replicating its renaming rules.
+pub struct FieldNamesType(pub DeriveInput);
+
+impl FieldNamesType {
+    pub fn new(ident: syn::Ident, input: &DeriveInput) -> Self {
+        let data = match &input.data {
+            syn::Data::Struct(data) => syn::Data::Struct(syn::DataStruct {
+                fields: field_names_fields(&data.fields),
+                ..data.clone()
+            }),
+            syn::Data::Enum(data) => syn::Data::Enum(syn::DataEnum {
+                variants: data
+                    .variants
+                    .iter()
+                    .map(|variant| {
+                        let has_deserialize_with = ["deserialize_with", "with"]
+                            .iter()
+                            .any(|key| find_attr_meta(&variant.attrs, "serde", key).is_some());
+                        // Only struct variants have field names. Other variants are kept as
+                        // unit variants, so that variants can be looked up by index.
+                        let fields = match &variant.fields {
+                            syn::Fields::Named(_) if !has_deserialize_with => {
+                                field_names_fields(&variant.fields)
+                            }
+                            _ => syn::Fields::Unit,
+                        };
+                        syn::Variant {
+                            attrs: serde_metas(&variant.attrs, "rename_all"),
+                            ident: variant.ident.clone(),
+                            fields,
+                            discriminant: None,
+                        }
+                    })
+                    .collect(),
+                ..data.clone()
+            }),
+            syn::Data::Union(_) => unreachable!(),
+        };
+        // `rename_all` renames the variants of an enum, rather than their fields.
+        let rename_all = match &input.data {
+            syn::Data::Enum(_) => "rename_all_fields",
+            _ => "rename_all",
+        };
+        Self(DeriveInput {
+            attrs: serde_metas(&input.attrs, rename_all),
+            vis: syn::Visibility::Inherited,
+            ident,
+            generics: syn::Generics::default(),
+            data,
+        })
+    }
+}
+
+/// The fields of [`FieldNamesType`]: the original fields, with their `rename` attribute
+/// and nothing else (e.g. neither `skip` nor `flatten`, which would change the list of names).
+fn field_names_fields(fields: &syn::Fields) -> syn::Fields {
+    let mut fields = fields.clone();
+    for field in fields.iter_mut() {
+        field.attrs = serde_metas(&field.attrs, "rename");
+        field.vis = syn::Visibility::Inherited;
+        field.ty = syn::parse_quote!(::eserde::_serde::de::IgnoredAny);
+    }
+    fields
+}
+
+/// `#[serde(KEY ..)]`, if `KEY` is set within the attributes.
+fn serde_metas(attrs: &[syn::Attribute], key: &str) -> Vec<syn::Attribute> {
+    find_attr_meta(attrs, "serde", key)
+        .map(|meta| syn::parse_quote!(#[serde(#meta)]))
+        .into_iter()
+        .collect()
+}
+
 /// A companion type that, unlike the original, uses `MaybeInvalidOrMissing<T>` for all fields, where
 /// `T` is the original field type.
 /// This type should never fail to deserialize, thus allowing us to collect all errors in one go.
@@ -41,7 +269,7 @@ pub struct PermissiveCompanionType {
 }
 
 impl PermissiveCompanionType {
-    pub fn new(ident: syn::Ident, input: &syn::DeriveInput) -> Self {
+    pub fn new(ident: syn::Ident, input: &syn::DeriveInput, field_names_type: &syn::Ident) -> Self {
         let mut companion = DeriveInput {
             vis: syn::Visibility::Inherited,
             ident,
@@ -50,6 +278,7 @@ impl PermissiveCompanionType {
                 attr.meta.path().is_ident("serde") || attr.meta.path().is_ident("eserde")
             })
         };
+        keep_original_name(&mut companion, &input.ident);
 
         // We keep track of the generic parameters that are used in the fields of the companion type
         // that have not been marked with `#[serde(compat)]`. We'll use this information to generate
@@ -62,15 +291,28 @@ impl PermissiveCompanionType {
         let mut eserde_aware_generics = IndexSet::new();
 
         let mut impl_items: Vec<syn::ImplItem> = Vec::new();
+        // Methods for `#[serde(deserialize_with)]` attributes on enum variants.
+        let mut variant_impl_items: Vec<syn::ImplItem> = Vec::new();
 
-        let mut modify_field_types = |fields: &mut syn::Fields| {
+        let mut modify_field_types = |fields: &mut syn::Fields, variant: Option<usize>| {
             for (i, field) in fields.iter_mut().enumerate() {
                 let span = field.span();
 
                 // Process all `eserde` attributes, then remove them since
                 // they are not valid `serde` attributes.
-                let is_eserde_compatible =
-                    find_attr_meta(&field.attrs, "eserde", "compat").is_none();
+                let invalid_message = eserde_message(&field.attrs, "invalid");
+                let code = eserde_code(&field.attrs);
+                let deprecated = eserde_deprecated(&field.attrs);
+                let member = match &field.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(i.into()),
+                };
+                let field_name = field_name(field_names_type, variant, i, &member);
+                let expected = type_name(&field.ty);
+                let eserde_with = eserde_with(field);
+                let is_eserde_compatible = find_attr_meta(&field.attrs, "eserde", "compat")
+                    .is_none()
+                    && eserde_with.is_none();
                 field.attrs.retain(keep_serde_attributes);
 
                 if is_eserde_compatible {
@@ -119,7 +361,41 @@ impl PermissiveCompanionType {
                     syn::parse_quote_spanned!(field_ty.span()=> ::eserde::_macro_impl::MaybeInvalidOrMissing::<#field_ty>)
                 };
 
-                if is_eserde_compatible {
+                if let Some(module) = eserde_with {
+                    // The module knows how to collect errors: call its `deserialize_for_errors`
+                    // function instead of wrapping its `deserialize` function.
+                    let fn_name = format!(
+                        "__eserde_deserialize_for_errors_with_{}",
+                        field
+                            .ident
+                            .as_ref()
+                            .map(|ident| ident.unraw().to_string())
+                            .unwrap_or_else(|| i.to_string()),
+                    );
+                    let fn_ident = syn::Ident::new(&fn_name, field.span());
+                    impl_items.push(syn::parse_quote_spanned! {field.span()=>
+                        fn #fn_ident<'de, D>(deserializer: D) -> ::core::result::Result<#wrapper_ty, D::Error>
+                        where
+                            D: ::eserde::_serde::Deserializer<'de>,
+                        {
+                            let value = match #module::deserialize_for_errors(deserializer) {
+                                Ok(()) => #wrapper_ty::Valid(::core::marker::PhantomData),
+                                Err(()) if ::eserde::reporter::ErrorReporter::should_abort() => {
+                                    return Err(::eserde::_macro_impl::abort_deserialization());
+                                }
+                                Err(()) => #wrapper_ty::Invalid,
+                            };
+                            Ok(value)
+                        }
+                    });
+                    let new_path = syn::LitStr::new(
+                        &format!("{}::{}", companion.ident, fn_name),
+                        field.span(),
+                    );
+                    field
+                        .attrs
+                        .push(syn::parse_quote!(#[serde(deserialize_with = #new_path)]));
+                } else if is_eserde_compatible {
                     // Add or replace `#[serde(deserialize_with = "..")]` for our wrapper.
 
                     // Handle user `#[serde(deserialize_with = "..")]` or `#[serde(with = "..')]` attributes.
@@ -159,7 +435,11 @@ impl PermissiveCompanionType {
                                     let value = match result {
                                         Ok(_) => #wrapper_ty::Valid(::core::marker::PhantomData),
                                         Err(e) => {
-                                            ::eserde::reporter::ErrorReporter::report(e);
+                                            ::eserde::reporter::ErrorReporter::report(&e);
+                                            // Stop once the error budget has been exhausted.
+                                            if ::eserde::reporter::ErrorReporter::should_abort() {
+                                                return Err(e);
+                                            }
                                             #wrapper_ty::Invalid
                                         }
                                     };
@@ -181,6 +461,76 @@ impl PermissiveCompanionType {
                     field.attrs.push(attr);
                 }
 
+                if invalid_message.is_some() || code.is_some() {
+                    // Wrap the deserializer we picked above, rewriting the errors it reports.
+                    let inner = take_deserialize_with(&mut field.attrs, &wrapper_ty);
+                    let fn_name = format!(
+                        "__eserde_custom_errors_{}",
+                        field
+                            .ident
+                            .as_ref()
+                            .map(|ident| ident.unraw().to_string())
+                            .unwrap_or_else(|| i.to_string()),
+                    );
+                    let fn_ident = syn::Ident::new(&fn_name, span);
+                    let override_errors = override_errors(
+                        &format_ident!("n_errors"),
+                        invalid_message.as_deref(),
+                        code.as_deref(),
+                        &field_name,
+                        &expected,
+                    );
+                    impl_items.push(syn::parse_quote_spanned! {span=>
+                        fn #fn_ident<'de, D>(deserializer: D) -> ::core::result::Result<#wrapper_ty, D::Error>
+                        where
+                            D: ::eserde::_serde::Deserializer<'de>,
+                        {
+                            let n_errors = ::eserde::reporter::ErrorReporter::n_errors();
+                            let result = (#inner)(deserializer);
+                            #override_errors
+                            result
+                        }
+                    });
+                    let new_path =
+                        syn::LitStr::new(&format!("{}::{}", companion.ident, fn_name), span);
+                    field
+                        .attrs
+                        .push(syn::parse_quote!(#[serde(deserialize_with = #new_path)]));
+                }
+
+                if let Some(note) = deprecated {
+                    // Wrap the deserializer we picked above, warning that the field is
+                    // present in the input.
+                    let inner = take_deserialize_with(&mut field.attrs, &wrapper_ty);
+                    let note = match note {
+                        Some(note) => quote! { ::core::option::Option::Some(#note) },
+                        None => quote! { ::core::option::Option::None },
+                    };
+                    let fn_name = format!(
+                        "__eserde_deprecated_{}",
+                        field
+                            .ident
+                            .as_ref()
+                            .map(|ident| ident.unraw().to_string())
+                            .unwrap_or_else(|| i.to_string()),
+                    );
+                    let fn_ident = syn::Ident::new(&fn_name, span);
+                    impl_items.push(syn::parse_quote_spanned! {span=>
+                        fn #fn_ident<'de, D>(deserializer: D) -> ::core::result::Result<#wrapper_ty, D::Error>
+                        where
+                            D: ::eserde::_serde::Deserializer<'de>,
+                        {
+                            ::eserde::_macro_impl::report_deprecated(#note);
+                            (#inner)(deserializer)
+                        }
+                    });
+                    let new_path =
+                        syn::LitStr::new(&format!("{}::{}", companion.ident, fn_name), span);
+                    field
+                        .attrs
+                        .push(syn::parse_quote!(#[serde(deserialize_with = #new_path)]));
+                }
+
                 // Done last for ownership.
                 field.ty = wrapper_ty;
             }
@@ -188,16 +538,26 @@ impl PermissiveCompanionType {
 
         match &mut companion.data {
             syn::Data::Struct(data_struct) => {
-                (modify_field_types)(&mut data_struct.fields);
+                (modify_field_types)(&mut data_struct.fields, None);
             }
             syn::Data::Enum(data_enum) => {
-                data_enum
-                    .variants
-                    .iter_mut()
-                    .for_each(|variant| (modify_field_types)(&mut variant.fields));
+                for (variant_index, variant) in data_enum.variants.iter_mut().enumerate() {
+                    match variant_deserialize_with(variant) {
+                        Some(dewith_path) => {
+                            wrap_variant_deserialize_with(
+                                &companion.ident,
+                                variant,
+                                dewith_path,
+                                &mut variant_impl_items,
+                            );
+                        }
+                        None => (modify_field_types)(&mut variant.fields, Some(variant_index)),
+                    }
+                }
             }
             syn::Data::Union(_) => unreachable!(),
         };
+        impl_items.extend(variant_impl_items);
 
         let bounds: Vec<String> = companion
             .generics
@@ -245,6 +605,74 @@ impl PermissiveCompanionType {
     }
 }
 
+/// Remove `#[serde(deserialize_with = "..")]` or `#[serde(with = "..")]` from the variant
+/// and return the path of the function that deserializes its fields, if any.
+fn variant_deserialize_with(variant: &mut syn::Variant) -> Option<syn::Path> {
+    remove_attr_meta(&mut variant.attrs, "serde", "deserialize_with")
+        .and_then(|meta_item| meta_str_value(&meta_item))
+        .or_else(|| {
+            remove_attr_meta(&mut variant.attrs, "serde", "with")
+                .and_then(|meta_item| meta_str_value(&meta_item))
+                .map(|s| format!("{}::deserialize", s))
+        })
+        .and_then(|s| syn::parse_str::<syn::Path>(&s).ok())
+}
+
+/// Replace the fields of a variant that has a custom `deserialize_with` function with
+/// a single `MaybeInvalid` field, deserialized by a wrapper around the user function.
+///
+/// `serde` always deserializes such variants as newtype variants, no matter their shape,
+/// so the wrapper receives the same input as the user function.
+fn wrap_variant_deserialize_with(
+    companion_ident: &syn::Ident,
+    variant: &mut syn::Variant,
+    dewith_path: syn::Path,
+    impl_items: &mut Vec<syn::ImplItem>,
+) {
+    let span = variant.span();
+    // `serde` expects the function to return a tuple of all the fields of the variant.
+    let field_tys: Vec<_> = variant.fields.iter().map(|field| &field.ty).collect();
+    let fields_ty: syn::Type = match field_tys.as_slice() {
+        [field_ty] => (*field_ty).clone(),
+        _ => syn::parse_quote_spanned!(span=> (#(#field_tys),*)),
+    };
+    let wrapper_ty: syn::Type =
+        syn::parse_quote_spanned!(span=> ::eserde::_macro_impl::MaybeInvalid::<#fields_ty>);
+
+    let fn_name = format!(
+        "__eserde_deserialize_with_variant_{}",
+        variant.ident.unraw()
+    );
+    let fn_ident = syn::Ident::new(&fn_name, span);
+    impl_items.push(syn::parse_quote_spanned! {span=>
+        #[allow(non_snake_case)]
+        fn #fn_ident<'de, D>(deserializer: D) -> ::core::result::Result<#wrapper_ty, D::Error>
+        where
+            D: ::eserde::_serde::Deserializer<'de>,
+        {
+            let result: ::core::result::Result<#fields_ty, D::Error> = (#dewith_path)(deserializer);
+            let value = match result {
+                Ok(_) => #wrapper_ty::Valid(::core::marker::PhantomData),
+                Err(e) => {
+                    ::eserde::reporter::ErrorReporter::report(&e);
+                    // Stop once the error budget has been exhausted.
+                    if ::eserde::reporter::ErrorReporter::should_abort() {
+                        return Err(e);
+                    }
+                    #wrapper_ty::Invalid
+                }
+            };
+            Ok(value)
+        }
+    });
+
+    let new_path = syn::LitStr::new(&format!("{}::{}", companion_ident, fn_name), span);
+    variant
+        .attrs
+        .push(syn::parse_quote_spanned!(span=> #[serde(deserialize_with = #new_path)]));
+    variant.fields = syn::Fields::Unnamed(syn::parse_quote_spanned!(span=> (#wrapper_ty)));
+}
+
 fn collect_generic_type_params(
     ty_: &syn::Type,
     set: &mut IndexSet<syn::Ident>,
@@ -291,7 +719,7 @@ fn collect_generic_type_params(
 }
 
 /// If the `MetaItem` has a string literal value, return it as `Some(String)`, otherwise return `None`.
-fn meta_str_value(meta: &crate::attr::MetaItem) -> Option<String> {
+pub fn meta_str_value(meta: &crate::attr::MetaItem) -> Option<String> {
     let (_eq, expr) = meta.value.as_ref()?;
     if let syn::Expr::Lit(syn::ExprLit {
         attrs: _,
@@ -1,12 +1,19 @@
+use quote::ToTokens;
 use syn::DeriveInput;
 
-use crate::{attr::find_attr_meta, filter_attributes::FilterAttributes};
+use crate::{
+    attr::{find_attr_meta, MetaItem},
+    filter_attributes::FilterAttributes,
+    model::meta_str_value,
+};
 
 /// Return a compiler error if the input contains data types or
 /// `serde` attributes that are not supported by our custom derive.
 pub fn reject_unsupported_inputs(input: &DeriveInput) -> Result<(), syn::Error> {
     let mut errors = Vec::new();
 
+    reject_invalid_eserde_attributes(&mut errors, &input.data);
+
     let input = input.filter_attributes(|a| a.meta.path().is_ident("serde"));
     reject_container_attributes(&mut errors, &input.attrs);
 
@@ -40,6 +47,95 @@ pub fn reject_unsupported_inputs(input: &DeriveInput) -> Result<(), syn::Error>
     }
 }
 
+/// Field-level `eserde` attributes that are malformed or can't be combined.
+fn reject_invalid_eserde_attributes(errors: &mut Vec<syn::Error>, data: &syn::Data) {
+    let fields: Vec<&syn::Field> = match data {
+        syn::Data::Struct(data_struct) => data_struct.fields.iter().collect(),
+        syn::Data::Enum(data_enum) => data_enum
+            .variants
+            .iter()
+            .flat_map(|variant| variant.fields.iter())
+            .collect(),
+        syn::Data::Union(_) => return,
+    };
+    for field in &fields {
+        for kind in ["message", "missing", "invalid"] {
+            if let Some(meta_item) = find_attr_meta(&field.attrs, "eserde", kind) {
+                reject_invalid_message(errors, &meta_item);
+            }
+        }
+        if let Some(meta_item) = find_attr_meta(&field.attrs, "eserde", "code") {
+            if meta_str_value(&meta_item).is_none() {
+                errors.push(syn::Error::new_spanned(
+                    &meta_item,
+                    "Expected a string literal—e.g. `#[eserde(code = \"..\")]`.",
+                ));
+            }
+        }
+        if let Some(meta_item) = find_attr_meta(&field.attrs, "eserde", "deprecated") {
+            if meta_item.value.is_some() && meta_str_value(&meta_item).is_none() {
+                errors.push(syn::Error::new_spanned(
+                    &meta_item,
+                    "Expected a string literal—e.g. `#[eserde(deprecated = \"..\")]`.",
+                ));
+            }
+        }
+    }
+
+    // `#[eserde(with = "..")]` replaces the deserialization logic of a field, so it can't be
+    // combined with other attributes that do the same.
+    for field in fields {
+        let Some(meta_item) = find_attr_meta(&field.attrs, "eserde", "with") else {
+            continue;
+        };
+        for (attr_name, path, example) in [
+            ("eserde", "compat", "`#[eserde(compat)]`"),
+            (
+                "serde",
+                "deserialize_with",
+                "`#[serde(deserialize_with = \"..\")]`",
+            ),
+            ("serde", "with", "`#[serde(with = \"..\")]`"),
+        ] {
+            if find_attr_meta(&field.attrs, attr_name, path).is_some() {
+                errors.push(syn::Error::new_spanned(
+                    &meta_item,
+                    format!("`#[eserde(with = \"..\")]` can't be combined with {example}."),
+                ));
+            }
+        }
+    }
+}
+
+/// Custom messages must be string literals, and they can only use the placeholders
+/// we know how to fill in.
+fn reject_invalid_message(errors: &mut Vec<syn::Error>, meta_item: &MetaItem) {
+    let Some(template) = meta_str_value(meta_item) else {
+        errors.push(syn::Error::new_spanned(
+            meta_item,
+            format!(
+                "Expected a string literal—e.g. `#[eserde({} = \"..\")]`.",
+                meta_item.key.to_token_stream()
+            ),
+        ));
+        return;
+    };
+    let mut rest = template.as_str();
+    while let Some(start) = rest.find('{') {
+        rest = &rest[start + 1..];
+        let name = rest.split('}').next().unwrap_or_default();
+        if !["message", "field", "expected"].contains(&name) {
+            errors.push(syn::Error::new_spanned(
+                meta_item,
+                format!(
+                    "Unknown placeholder `{{{name}}}`. \
+                    Supported placeholders: `{{message}}`, `{{field}}` and `{{expected}}`."
+                ),
+            ));
+        }
+    }
+}
+
 /// Attributes from <https://serde.rs/container-attrs.html> that we either
 /// can't support or haven't implemented yet.
 fn reject_container_attributes(errors: &mut Vec<syn::Error>, attrs: &[syn::Attribute]) {
@@ -68,8 +164,6 @@ fn reject_container_attributes(errors: &mut Vec<syn::Error>, attrs: &[syn::Attri
         ("try_from", "`#[serde(try_from = \"..\")]`", ""),
         ("from", "`#[serde(from = \"..\")]`", ""),
         ("bound", "`#[serde(bound = \"..\")]`", ""),
-        ("variant_identifier", "`#[serde(variant_identifier)]`", ""),
-        ("field_identifier", "`#[serde(field_identifier)]`", ""),
     ] {
         if let Some(meta_item) = find_attr_meta(attrs, "serde", path) {
             errors.push(syn::Error::new_spanned(
@@ -103,8 +197,6 @@ fn reject_field_attributes(errors: &mut Vec<syn::Error>, field: &syn::Field) {
 fn reject_variant_attributes(errors: &mut Vec<syn::Error>, variant: &syn::Variant) {
     for (path, example) in [
         ("skip_deserializing", "`#[serde(skip_deserializing)]`"),
-        ("deserialize_with", "`#[serde(deserialize_with = \"..\")]`"),
-        ("with", "`#[serde(with = \"..\")]`"),
         ("bound", "`#[serde(bound = \"..\")]`"),
         ("untagged", "`#[serde(untagged)]`"),
     ] {
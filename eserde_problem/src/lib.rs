@@ -0,0 +1,323 @@
+//! # eserde_problem
+//!
+//! Types to represent a problem details error response, as specified in
+//! [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+//!
+//! They are framework-agnostic: `eserde`'s web framework integrations
+//! (e.g. [`eserde_axum`] and [`eserde_actix`]) build on top of them, so that
+//! deserialization errors have the same shape no matter which framework is
+//! serving the request.
+//!
+//! # Example
+//!
+//! ```rust
+//! use eserde_problem::{ProblemDetails, ValidationErrors};
+//!
+//! #[derive(eserde::Deserialize, Debug)]
+//! struct CreateUser {
+//!     email: String,
+//!     age: u8,
+//! }
+//!
+//! let errors = eserde::json::from_str::<CreateUser>(r#"{ "age": 300 }"#).unwrap_err();
+//! let problem = ProblemDetails::from(errors).instance("/users");
+//! assert_eq!(problem.status, 400);
+//! assert_eq!(problem.extensions.unwrap().errors.len(), 2);
+//! ```
+//!
+//! # Custom problems
+//!
+//! Use [`ProblemDetails::new`] and its builder methods to describe problems
+//! that aren't caused by deserialization errors, with the same response shape:
+//!
+//! ```rust
+//! use eserde_problem::ProblemDetails;
+//!
+//! let problem = ProblemDetails::new(403)
+//!     .type_("https://example.com/probs/out-of-credit")
+//!     .title("You do not have enough credit")
+//!     .detail("Your current balance is 30, but that costs 50")
+//!     .instance("/account/12345/msgs/abc")
+//!     .member("balance", 30.into());
+//! assert_eq!(
+//!     serde_json::to_value(&problem).unwrap(),
+//!     serde_json::json!({
+//!         "type": "https://example.com/probs/out-of-credit",
+//!         "status": 403,
+//!         "title": "You do not have enough credit",
+//!         "detail": "Your current balance is 30, but that costs 50",
+//!         "instance": "/account/12345/msgs/abc",
+//!         "balance": 30
+//!     })
+//! );
+//! ```
+//!
+//! [`eserde_axum`]: https://docs.rs/eserde_axum
+//! [`eserde_actix`]: https://docs.rs/eserde_actix
+#![deny(missing_docs)]
+use std::{borrow::Cow, collections::BTreeMap};
+
+use eserde::{
+    path::{Path, Segment},
+    DeserializationErrors,
+};
+
+/// A problem details object, the body of an error response.
+///
+/// It serializes to the JSON representation defined in
+/// [RFC 9457](https://www.rfc-editor.org/rfc/rfc9457.html).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ProblemDetails<Extension> {
+    /// An identifier for the problem type.
+    #[serde(rename = "type")]
+    pub type_: Cow<'static, str>,
+    /// The HTTP status code of the response.
+    pub status: u16,
+    /// A short, human-readable summary of the problem type.
+    ///
+    /// Omitted from the response when empty.
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub title: Cow<'static, str>,
+    /// A human-readable explanation specific to this occurrence of the problem.
+    ///
+    /// Omitted from the response when empty.
+    #[serde(skip_serializing_if = "str::is_empty")]
+    pub detail: Cow<'static, str>,
+    /// An identifier for this specific occurrence of the problem.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<Cow<'static, str>>,
+    /// Additional, strongly-typed members, flattened into the top-level object.
+    #[serde(flatten)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<Extension>,
+    /// Additional, ad-hoc members, flattened into the top-level object.
+    ///
+    /// See [`ProblemDetails::member`].
+    #[serde(flatten)]
+    pub members: BTreeMap<String, serde_json::Value>,
+}
+
+impl ProblemDetails<()> {
+    /// A problem with the given HTTP status code.
+    ///
+    /// The type is set to `about:blank`, as recommended by the RFC
+    /// for problems without additional semantics.
+    /// Use the builder methods (e.g. [`title`](Self::title)) to set the other members;
+    /// the ones left empty are omitted from the response.
+    ///
+    /// ```rust
+    /// use eserde_problem::ProblemDetails;
+    ///
+    /// assert_eq!(
+    ///     serde_json::to_value(ProblemDetails::new(404)).unwrap(),
+    ///     serde_json::json!({ "type": "about:blank", "status": 404 })
+    /// );
+    /// ```
+    pub fn new(status: u16) -> Self {
+        ProblemDetails {
+            type_: "about:blank".into(),
+            status,
+            title: "".into(),
+            detail: "".into(),
+            instance: None,
+            extensions: None,
+            members: BTreeMap::new(),
+        }
+    }
+}
+
+impl ProblemDetails<ValidationErrors> {
+    /// The problem used when the request is malformed or doesn't match the
+    /// expected schema. Its status is `400 Bad Request`.
+    pub fn invalid_request(errors: ValidationErrors) -> Self {
+        ProblemDetails::new(400)
+            .type_("invalid_request")
+            .title("The request is invalid")
+            .detail("The request is either malformed or doesn't match the expected schema")
+            .extensions(errors)
+    }
+}
+
+impl<Extension> ProblemDetails<Extension> {
+    /// Set the identifier of the problem type.
+    pub fn type_(mut self, type_: impl Into<Cow<'static, str>>) -> Self {
+        self.type_ = type_.into();
+        self
+    }
+
+    /// Set the short, human-readable summary of the problem type.
+    pub fn title(mut self, title: impl Into<Cow<'static, str>>) -> Self {
+        self.title = title.into();
+        self
+    }
+
+    /// Set the human-readable explanation of this occurrence of the problem.
+    pub fn detail(mut self, detail: impl Into<Cow<'static, str>>) -> Self {
+        self.detail = detail.into();
+        self
+    }
+
+    /// Set the identifier of this occurrence of the problem—e.g. the request path.
+    pub fn instance(mut self, instance: impl Into<Cow<'static, str>>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+
+    /// Attach strongly-typed extension members, replacing the current ones.
+    ///
+    /// `extensions` must serialize to a map: its entries are flattened
+    /// into the top-level object.
+    pub fn extensions<E>(self, extensions: E) -> ProblemDetails<E> {
+        ProblemDetails {
+            type_: self.type_,
+            status: self.status,
+            title: self.title,
+            detail: self.detail,
+            instance: self.instance,
+            extensions: Some(extensions),
+            members: self.members,
+        }
+    }
+
+    /// Add an ad-hoc extension member.
+    pub fn member(mut self, name: impl Into<String>, value: serde_json::Value) -> Self {
+        self.members.insert(name.into(), value);
+        self
+    }
+}
+
+/// Errors in the request body, with [JSON pointers](ValidationErrors::from_body)
+/// to the problematic properties.
+impl From<DeserializationErrors> for ProblemDetails<ValidationErrors> {
+    fn from(errors: DeserializationErrors) -> Self {
+        ProblemDetails::invalid_request(ValidationErrors::from_body(&errors))
+    }
+}
+
+/// The list of validation errors attached to an
+/// [invalid request problem](ProblemDetails::invalid_request).
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ValidationErrors {
+    /// The individual errors.
+    pub errors: Vec<ValidationError>,
+}
+
+impl ValidationErrors {
+    /// Convert deserialization errors for the request body.
+    ///
+    /// Each error points at the problematic body property via a
+    /// [JSON pointer](https://www.rfc-editor.org/info/rfc6901)—e.g. `/items/2/quantity`.
+    pub fn from_body(errors: &DeserializationErrors) -> Self {
+        let errors = errors
+            .iter()
+            .map(|e| ValidationError {
+                detail: e.message().into(),
+                code: e.code().to_owned().into(),
+                source: Source::Body {
+                    pointer: e.path().map(json_pointer),
+                },
+            })
+            .collect();
+        ValidationErrors { errors }
+    }
+
+    /// Convert deserialization errors for the request query string.
+    ///
+    /// Each error names the problematic query parameter—e.g. `page`.
+    /// Missing parameters are named too, even though they are reported against their parent.
+    pub fn from_query(errors: &DeserializationErrors) -> Self {
+        let errors = errors
+            .iter()
+            .map(|e| {
+                let path = e
+                    .path()
+                    .filter(|path| !path.is_empty())
+                    .map(|path| path.to_string());
+                let parameter = match (path, e.field()) {
+                    (Some(path), Some(field)) if e.code() == "missing_field" => {
+                        Some(format!("{path}.{field}"))
+                    }
+                    (None, Some(field)) if e.code() == "missing_field" => Some(field.to_owned()),
+                    (path, _) => path,
+                };
+                ValidationError {
+                    detail: e.message().into(),
+                    code: e.code().to_owned().into(),
+                    source: Source::Query { parameter },
+                }
+            })
+            .collect();
+        ValidationErrors { errors }
+    }
+}
+
+/// See [`ValidationErrors::from_body`].
+impl From<DeserializationErrors> for ValidationErrors {
+    fn from(errors: DeserializationErrors) -> Self {
+        ValidationErrors::from_body(&errors)
+    }
+}
+
+/// A single validation error.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ValidationError {
+    /// A human-readable description of the error.
+    pub detail: String,
+    /// A stable, machine-readable identifier for the kind of error—e.g. `missing_field`.
+    ///
+    /// See [`DeserializationError::code`](eserde::DeserializationError::code)
+    /// for the codes used for deserialization errors.
+    pub code: Cow<'static, str>,
+    /// The request part where the error occurred.
+    #[serde(flatten)]
+    pub source: Source,
+}
+
+/// The request part where the problem occurred.
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "source", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Source {
+    /// The request body.
+    Body {
+        /// A [JSON pointer](https://www.rfc-editor.org/info/rfc6901) targeted
+        /// at the problematic body property.
+        pointer: Option<String>,
+    },
+    /// A request header.
+    Header {
+        /// The name of the problematic header.
+        name: Cow<'static, str>,
+    },
+    /// The query string.
+    Query {
+        /// The name of the problematic query parameter.
+        parameter: Option<String>,
+    },
+}
+
+/// The media type of a problem details JSON body.
+pub const APPLICATION_PROBLEM_JSON: &str = "application/problem+json";
+
+/// A pre-serialized problem details body for `500 Internal Server Error`,
+/// to be used when the actual error response can't be serialized.
+pub const INTERNAL_SERVER_ERROR_PROBLEM: &[u8] = br#"{
+    "type": "internal_server_error",
+    "title": "Internal Server Error",
+    "detail": "Something went wrong when processing your request. Please try again later.",
+    "status": 500
+}"#;
+
+fn json_pointer(path: &Path) -> String {
+    path.iter().fold(String::new(), |mut acc, segment| {
+        acc.push('/');
+        match segment {
+            Segment::Seq { index } => acc.push_str(&index.to_string()),
+            Segment::Map { key } | Segment::Enum { variant: key } => {
+                // Escape the characters that have a special meaning in JSON pointers.
+                acc.push_str(&key.replace('~', "~0").replace('/', "~1"))
+            }
+        }
+        acc
+    })
+}